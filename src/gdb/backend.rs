@@ -0,0 +1,82 @@
+use std::io;
+
+// ─── Backend ──────────────────────────────────────────────────────────────────
+
+/// Where `run_loop` sends MI commands and reads GDB's output from: the real
+/// process, or a scripted stand-in in tests.
+pub trait GdbBackend: Send {
+    /// Writes one command line, token included.
+    fn send(&mut self, line: &str) -> io::Result<()>;
+
+    /// Next output line if one is ready; `Err` for a read problem to report.
+    fn try_recv(&mut self) -> Option<Result<String, String>>;
+
+    /// SIGINT to GDB, for interrupting in sync mode.
+    fn interrupt(&mut self) -> io::Result<()>;
+
+    fn kill(&mut self);
+}
+
+// ─── Fake ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+pub mod fake {
+    use std::collections::VecDeque;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use super::GdbBackend;
+
+    /// Replays canned MI output: each command starting with a scripted prefix
+    /// gets that reply, tagged with its token; anything else a bare `^done`.
+    /// Lines sent are kept in `sent` for the test to inspect.
+    #[derive(Default)]
+    pub struct FakeGdb {
+        replies: Vec<(String, Vec<String>)>,
+        output: VecDeque<String>,
+        pub sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FakeGdb {
+        /// Reply lines starting with `^` are the result record and get the
+        /// command's token; the rest (`*stopped`, `=…`, `~…`) go out as is.
+        pub fn reply(mut self, prefix: &str, lines: &[&str]) -> Self {
+            let lines = lines.iter().map(|l| l.to_string()).collect();
+            self.replies.push((prefix.to_owned(), lines));
+            self
+        }
+    }
+
+    impl GdbBackend for FakeGdb {
+        fn send(&mut self, line: &str) -> io::Result<()> {
+            self.sent.lock().unwrap().push(line.to_owned());
+            let split = line.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+            let (token, mi) = line.split_at(split);
+            let reply = self
+                .replies
+                .iter()
+                .find(|(prefix, _)| mi.starts_with(prefix.as_str()))
+                .map(|(_, lines)| lines.clone())
+                .unwrap_or_else(|| vec!["^done".into()]);
+            for l in reply {
+                if l.starts_with('^') {
+                    self.output.push_back(format!("{token}{l}"));
+                } else {
+                    self.output.push_back(l);
+                }
+            }
+            self.output.push_back("(gdb)".into());
+            Ok(())
+        }
+
+        fn try_recv(&mut self) -> Option<Result<String, String>> {
+            self.output.pop_front().map(Ok)
+        }
+
+        fn interrupt(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn kill(&mut self) {}
+    }
+}
@@ -1,5 +1,14 @@
+mod backend;
 mod parser;
 mod process;
+mod script;
 mod writer;
 
+pub use process::{STALL_TIMEOUT, run_gdb};
+pub use script::{export_breakpoints, parse_breakpoint_script};
+pub use writer::{command_to_console, command_to_mi};
+
+#[cfg(test)]
+pub use backend::fake::FakeGdb;
+#[cfg(test)]
 pub use process::run_loop;
@@ -1,6 +1,6 @@
-#[allow(unused_imports)]
 use crate::state::{
-    Breakpoint, DebuggerEvent, Frame, PauseState, StateEvent, StopReason, UiEvent, Variable,
+    Breakpoint, BreakpointKind, DebuggerEvent, Frame, Language, MemoryBlock, MemoryRegion,
+    PauseState, StateEvent, StopReason, Symbol, Thread, UiEvent, VarChange, Variable, WatchValue,
 };
 
 pub fn parse_line(line: &str) -> Option<DebuggerEvent> {
@@ -13,7 +13,7 @@ pub fn parse_line(line: &str) -> Option<DebuggerEvent> {
     match line.chars().next()? {
         '~' => parse_console_stream(line),
         '@' => parse_target_stream(line),
-        '&' => None, // log interno, ignorar
+        '&' => parse_log_stream(line), // log interno, salvo la caída del remoto
         '*' => parse_exec_async(line),
         '=' => parse_notify_async(line),
         '^' => parse_result(line),
@@ -22,8 +22,59 @@ pub fn parse_line(line: &str) -> Option<DebuggerEvent> {
 }
 
 fn strip_token(line: &str) -> &str {
-    let end = line.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
-    &line[end..]
+    line.trim_start_matches(|c: char| c.is_ascii_digit())
+}
+
+/// Token prefix of a record (`42^done` → 42), used to match results to commands.
+pub fn parse_token(line: &str) -> Option<u32> {
+    let end = line.find(|c: char| !c.is_ascii_digit())?;
+    line[..end].parse().ok()
+}
+
+/// Class of a result record (`7^done,...` → "done"), None for other records.
+pub fn result_class(line: &str) -> Option<&str> {
+    let rest = strip_token(line).strip_prefix('^')?;
+    Some(split_class_fields(rest).0)
+}
+
+/// `^done,value="..."` → Ok(value), `^error,msg="..."` → Err(msg).
+pub fn parse_eval_result(line: &str) -> Option<Result<String, String>> {
+    let line = strip_token(line);
+    let rest = line.strip_prefix('^')?;
+    let (class, fields) = split_class_fields(rest);
+
+    match class {
+        "done" => extract_str(fields, "value").map(Ok),
+        "error" => Some(Err(
+            extract_str(fields, "msg").unwrap_or_else(|| "GDB error".into())
+        )),
+        _ => None,
+    }
+}
+
+/// `^done,name="var1",numchild="0",value="5",type="int"` → the new varobj,
+/// `^error,msg="..."` → Err(msg).
+pub fn parse_var_create(line: &str) -> Option<Result<WatchValue, String>> {
+    let rest = strip_token(line).strip_prefix('^')?;
+    let (class, fields) = split_class_fields(rest);
+
+    match class {
+        "done" => Some(Ok(WatchValue {
+            var: extract_str(fields, "name")?,
+            value: extract_str(fields, "value").unwrap_or_default(),
+            type_: extract_str(fields, "type").unwrap_or_default(),
+            in_scope: true,
+        })),
+        "error" => Some(Err(
+            extract_str(fields, "msg").unwrap_or_else(|| "GDB error".into())
+        )),
+        _ => None,
+    }
+}
+
+/// Text of a `~` console record, for commands that answer only there.
+pub fn console_text(line: &str) -> Option<String> {
+    unquote(strip_token(line).strip_prefix('~')?)
 }
 
 // ─── Stream outputs ───────────────────────────────────────────────────────────
@@ -31,15 +82,110 @@ fn strip_token(line: &str) -> &str {
 fn parse_console_stream(line: &str) -> Option<DebuggerEvent> {
     // ~"some text\n"
     let text = unquote(&line[1..])?;
+    if let Some(event) = parse_checkpoint(&text) {
+        return Some(event);
+    }
+    if let Some(query) = parse_query(&text) {
+        return Some(DebuggerEvent::Ui(query));
+    }
+    if let Some(language) = parse_language(&text) {
+        return Some(DebuggerEvent::State(StateEvent::LanguageDetected {
+            language,
+        }));
+    }
     Some(DebuggerEvent::Ui(UiEvent::ConsoleOutput(text)))
 }
 
+/// `checkpoint 1: fork returned pid 4242.` — the only report of a new checkpoint.
+fn parse_checkpoint(text: &str) -> Option<DebuggerEvent> {
+    let (id, rest) = text
+        .strip_prefix("checkpoint ")?
+        .split_once(": fork returned pid ")?;
+    let pid = rest.trim_end().trim_end_matches('.');
+    Some(DebuggerEvent::State(StateEvent::CheckpointCreated {
+        id: id.parse().ok()?,
+        pid: pid.parse().ok()?,
+    }))
+}
+
+/// A prompt GDB waits on. Pagination and confirm are off from the start, so
+/// these only come from commands that ask regardless, or from a user who
+/// turned them back on.
+fn parse_query(text: &str) -> Option<UiEvent> {
+    if text.starts_with("--Type <RET> for more") {
+        return Some(UiEvent::Query {
+            question: text.trim().to_owned(),
+            pagination: true,
+        });
+    }
+    let at = ["(y or n)", "(y or [n])", "([y] or n)"]
+        .iter()
+        .find_map(|p| text.find(p))?;
+    Some(UiEvent::Query {
+        question: text[..at].trim().to_owned(),
+        pagination: false,
+    })
+}
+
+/// `show language`: `The current source language is "auto; currently c++".`,
+/// or just `"rust"` once it's been set.
+fn parse_language(text: &str) -> Option<Language> {
+    let quoted = text.strip_prefix("The current source language is \"")?;
+    let (name, _) = quoted.split_once('"')?;
+    let name = name.rsplit("currently ").next()?;
+    Some(Language::from_gdb(name))
+}
+
+/// The table of `info proc mappings`:
+/// `0x400000 0x401000 0x1000 0x0 r--p /tmp/app`. The Perms column is only
+/// there from GDB 12 on; header and other lines are skipped.
+pub fn parse_mappings(text: &str) -> Vec<MemoryRegion> {
+    let hex = |s: &str| u64::from_str_radix(s.strip_prefix("0x")?, 16).ok();
+    let is_perms = |s: &str| s.len() == 4 && s.chars().all(|c| "rwxsp-".contains(c));
+    text.lines()
+        .filter_map(|line| {
+            let mut cols = line.split_whitespace();
+            let start = hex(cols.next()?)?;
+            let end = hex(cols.next()?)?;
+            let _size = hex(cols.next()?)?;
+            let offset = hex(cols.next()?)?;
+            let rest: Vec<_> = cols.collect();
+            let (perms, path) = match rest.split_first() {
+                Some((p, path)) if is_perms(p) => (p.to_string(), path.join(" ")),
+                _ => (String::new(), rest.join(" ")),
+            };
+            Some(MemoryRegion {
+                start,
+                end,
+                offset,
+                perms,
+                path,
+            })
+        })
+        .collect()
+}
+
+fn parse_log_stream(line: &str) -> Option<DebuggerEvent> {
+    let text = unquote(&line[1..])?;
+    remote_lost(&text).then(|| {
+        DebuggerEvent::State(StateEvent::RemoteDisconnected {
+            reason: text.trim_end().to_owned(),
+        })
+    })
+}
+
+/// GDB's messages for a `target remote` link that went away: EOF from the
+/// remote, or a read/write error on the socket or serial line.
+fn remote_lost(msg: &str) -> bool {
+    msg.starts_with("Remote connection closed")
+        || msg.starts_with("Remote communication error")
+        || msg.contains("Target disconnected")
+}
+
 fn parse_target_stream(line: &str) -> Option<DebuggerEvent> {
     // @"some text\n"  → stdout del programa que se está depurando
     let text = unquote(&line[1..])?;
-    Some(DebuggerEvent::Ui(UiEvent::ConsoleOutput(format!(
-        "[target] {text}"
-    ))))
+    Some(DebuggerEvent::State(StateEvent::ProgramOutput { text }))
 }
 
 // ─── Exec async (*) ───────────────────────────────────────────────────────────
@@ -52,8 +198,23 @@ fn parse_exec_async(line: &str) -> Option<DebuggerEvent> {
         "running" => Some(DebuggerEvent::State(StateEvent::ProgramStarted)),
 
         "stopped" => {
+            if let Some(exited) = parse_exit(fields) {
+                return Some(exited);
+            }
+
             let reason = parse_stop_reason(fields);
-            let frame = parse_frame_field(fields)?;
+            // No frame={…} when stopped somewhere without symbols: still
+            // paused, the stack request that follows fills in frame 0
+            let frame = parse_frame_field(fields).unwrap_or_else(|| Frame {
+                level: 0,
+                args: vec![],
+                addr: extract_str(fields, "addr")
+                    .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+                    .unwrap_or(0),
+                function: "??".into(),
+                file: None,
+                line: None,
+            });
             let stack = vec![frame.clone()];
             let thread_id = extract_str(fields, "thread-id")
                 .and_then(|s| s.parse().ok())
@@ -73,6 +234,24 @@ fn parse_exec_async(line: &str) -> Option<DebuggerEvent> {
     }
 }
 
+/// `*stopped,reason="exited-normally"` / `"exited"` / `"exited-signalled"`.
+fn parse_exit(fields: &str) -> Option<DebuggerEvent> {
+    let (code, signal) = match extract_str(fields, "reason").as_deref()? {
+        "exited-normally" => (Some(0), None),
+        // GDB imprime exit-code en octal ("012" = 10)
+        "exited" => (
+            extract_str(fields, "exit-code").and_then(|s| i32::from_str_radix(&s, 8).ok()),
+            None,
+        ),
+        "exited-signalled" => (None, extract_str(fields, "signal-name")),
+        _ => return None,
+    };
+    Some(DebuggerEvent::State(StateEvent::ProgramExited {
+        code,
+        signal,
+    }))
+}
+
 fn parse_stop_reason(fields: &str) -> StopReason {
     match extract_str(fields, "reason").as_deref() {
         Some("breakpoint-hit") => {
@@ -81,11 +260,35 @@ fn parse_stop_reason(fields: &str) -> StopReason {
                 .unwrap_or(0);
             StopReason::BreakpointHit(id)
         }
+        Some(
+            kind @ ("watchpoint-trigger" | "read-watchpoint-trigger" | "access-watchpoint-trigger"),
+        ) => {
+            let key = match kind {
+                "watchpoint-trigger" => "wpt",
+                "read-watchpoint-trigger" => "hw-rwpt",
+                _ => "hw-awpt",
+            };
+            let wpt = extract_block(fields, key).unwrap_or_default();
+            let value = extract_block(fields, "value").unwrap_or_default();
+            StopReason::WatchpointTrigger {
+                id: extract_str(wpt, "number")
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(0),
+                expr: extract_str(wpt, "exp").unwrap_or_default(),
+                old: extract_str(value, "old"),
+                // rwpt / awpt sin cambio reportan value={value="..."}
+                new: extract_str(value, "new").or_else(|| extract_str(value, "value")),
+            }
+        }
         Some("end-stepping-range") | Some("step-over-range") => StopReason::EndStepping,
+        Some("function-finished") => StopReason::FunctionFinished,
         Some("signal-received") => {
             let sig = extract_str(fields, "signal-name").unwrap_or_default();
             StopReason::Signal(sig)
         }
+        Some("syscall-return") => {
+            StopReason::SyscallReturn(extract_str(fields, "syscall-name").unwrap_or_default())
+        }
         _ => StopReason::Unknown,
     }
 }
@@ -107,6 +310,41 @@ fn parse_notify_async(line: &str) -> Option<DebuggerEvent> {
             let id = extract_str(fields, "id").and_then(|s| s.parse().ok())?;
             Some(DebuggerEvent::State(StateEvent::BreakpointRemoved { id }))
         }
+
+        // =thread-created,id="2",group-id="i1"
+        "thread-created" | "thread-exited" => {
+            let id = extract_str(fields, "id")?.parse().ok()?;
+            Some(DebuggerEvent::State(match class {
+                "thread-created" => StateEvent::ThreadCreated { id },
+                _ => StateEvent::ThreadExited { id },
+            }))
+        }
+        // A console `thread 2`: =thread-selected,id="2",frame={...}
+        "thread-selected" => {
+            let id = extract_str(fields, "id")?.parse().ok()?;
+            Some(DebuggerEvent::State(StateEvent::ThreadSelected {
+                id,
+                frame: parse_frame_field(fields),
+            }))
+        }
+
+        // =thread-group-added,id="i2" (inferiores, no threads)
+        "thread-group-added"
+        | "thread-group-started"
+        | "thread-group-exited"
+        | "thread-group-removed" => {
+            let id = extract_str(fields, "id")?.strip_prefix('i')?.parse().ok()?;
+            let event = match class {
+                "thread-group-added" => StateEvent::InferiorAdded { id },
+                "thread-group-started" => StateEvent::InferiorStarted {
+                    id,
+                    pid: extract_str(fields, "pid")?.parse().ok()?,
+                },
+                "thread-group-exited" => StateEvent::InferiorExited { id },
+                _ => StateEvent::InferiorRemoved { id },
+            };
+            Some(DebuggerEvent::State(event))
+        }
         _ => None,
     }
 }
@@ -119,18 +357,23 @@ fn parse_result(line: &str) -> Option<DebuggerEvent> {
 
     match class {
         "error" => {
-            let msg = extract_str(&fields, "msg").unwrap_or_else(|| "GDB error".into());
+            let msg = extract_str(fields, "msg").unwrap_or_else(|| "GDB error".into());
+            if remote_lost(&msg) {
+                return Some(DebuggerEvent::State(StateEvent::RemoteDisconnected {
+                    reason: msg,
+                }));
+            }
             Some(DebuggerEvent::Ui(UiEvent::GdbError(msg)))
         }
 
         "done" => {
             // -break-insert → ^done,bkpt={...}
-            if fields.contains("bkpt=") {
-                if let Some(bp) = parse_breakpoint_field(&fields, "bkpt") {
-                    return Some(DebuggerEvent::State(StateEvent::BreakpointAdded {
-                        breakpoint: bp,
-                    }));
-                }
+            if fields.contains("bkpt=")
+                && let Some(bp) = parse_breakpoint_field(fields, "bkpt")
+            {
+                return Some(DebuggerEvent::State(StateEvent::BreakpointAdded {
+                    breakpoint: bp,
+                }));
             }
 
             // -stack-list-variables → ^done,variables=[...]
@@ -141,6 +384,48 @@ fn parse_result(line: &str) -> Option<DebuggerEvent> {
                 }
             }
 
+            // -var-update → ^done,changelist=[{name="var1",value="6",in_scope="true",...}]
+            if fields.starts_with("changelist=") {
+                return Some(DebuggerEvent::State(StateEvent::WatchesUpdated {
+                    changes: parse_changelist(fields),
+                }));
+            }
+
+            // -stack-list-arguments → ^done,stack-args=[frame={level="0",args=[...]},...]
+            if fields.starts_with("stack-args=") {
+                let frames = parse_frame_args(fields);
+                return Some(DebuggerEvent::State(StateEvent::FrameArgsUpdated {
+                    frames,
+                }));
+            }
+
+            // -stack-list-frames → ^done,stack=[frame={level="0",...},...]
+            if fields.starts_with("stack=") {
+                let frames = parse_stack(fields);
+                return Some(DebuggerEvent::State(StateEvent::StackUpdated { frames }));
+            }
+
+            // -environment-directory → ^done,source-path="/src:$cdir:$cwd"
+            if let Some(path) = extract_str(fields, "source-path") {
+                return Some(DebuggerEvent::State(StateEvent::SourceDirsUpdated {
+                    dirs: parse_source_path(&path),
+                }));
+            }
+
+            // -thread-info → ^done,threads=[{id="1",target-id="...",frame={...},state="stopped"}],current-thread-id="1"
+            if fields.starts_with("threads=") {
+                return Some(DebuggerEvent::State(StateEvent::ThreadsUpdated {
+                    threads: parse_threads(fields),
+                    current: extract_str(fields, "current-thread-id").and_then(|s| s.parse().ok()),
+                }));
+            }
+
+            // -data-read-memory-bytes → ^done,memory=[{begin="0x...",offset="0x0",end="0x...",contents="0a1b..."}]
+            if fields.starts_with("memory=") {
+                return parse_memory(fields)
+                    .map(|block| DebuggerEvent::State(StateEvent::MemoryRead { block }));
+            }
+
             // -data-list-register-names → ^done,register-names=["rax","rbx",...]
             if fields.contains("register-names=") {
                 let names = parse_register_names(fields);
@@ -174,6 +459,7 @@ fn parse_result(line: &str) -> Option<DebuggerEvent> {
 
         "exit" => Some(DebuggerEvent::State(StateEvent::ProgramExited {
             code: None,
+            signal: None,
         })),
 
         _ => None,
@@ -216,6 +502,12 @@ fn extract_list<'a>(fields: &'a str, key: &str) -> Option<&'a str> {
     None
 }
 
+/// The `frame={…}` of a result record, e.g. `^connected` after loading a core.
+pub fn result_frame(line: &str) -> Option<Frame> {
+    let rest = strip_token(line).strip_prefix('^')?;
+    parse_frame_field(split_class_fields(rest).1)
+}
+
 fn parse_frame_field(fields: &str) -> Option<Frame> {
     let block = extract_block(fields, "frame")?;
     parse_frame(block)
@@ -228,8 +520,13 @@ fn parse_frame(block: &str) -> Option<Frame> {
     let function = extract_str(block, "func").unwrap_or_else(|| "??".into());
     let file = extract_str(block, "fullname").or_else(|| extract_str(block, "file"));
     let line = extract_str(block, "line").and_then(|s| s.parse().ok());
+    let level = extract_str(block, "level")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
 
     Some(Frame {
+        level,
+        args: vec![],
         addr,
         function,
         file,
@@ -237,34 +534,217 @@ fn parse_frame(block: &str) -> Option<Frame> {
     })
 }
 
+/// Splits GDB's source path on the host's path-list separator.
+fn parse_source_path(path: &str) -> Vec<String> {
+    let sep = if cfg!(windows) { ';' } else { ':' };
+    path.split(sep)
+        .filter(|d| !d.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// `-stack-list-arguments` → (level, args) for each frame.
+fn parse_frame_args(fields: &str) -> Vec<(u32, Vec<Variable>)> {
+    let list = match extract_list(fields, "stack-args") {
+        Some(l) => l,
+        None => return vec![],
+    };
+
+    let mut frames = vec![];
+    let mut rest = list;
+    while let Some(start) = rest.find("frame={") {
+        rest = &rest[start + "frame={".len()..];
+        let Some(end) = find_closing_brace(rest) else {
+            break;
+        };
+        let block = &rest[..end];
+        if let Some(level) = extract_str(block, "level").and_then(|s| s.parse().ok()) {
+            let args = extract_list(block, "args")
+                .map(parse_variable_list)
+                .unwrap_or_default();
+            frames.push((level, args));
+        }
+        rest = &rest[end + 1..];
+    }
+    frames
+}
+
+fn parse_stack(fields: &str) -> Vec<Frame> {
+    let list = match extract_list(fields, "stack") {
+        Some(l) => l,
+        None => return vec![],
+    };
+
+    let mut frames = vec![];
+    let mut rest = list;
+    while let Some(start) = rest.find("frame={") {
+        rest = &rest[start + "frame={".len()..];
+        let Some(end) = find_closing_brace(rest) else {
+            break;
+        };
+        if let Some(frame) = parse_frame(&rest[..end]) {
+            frames.push(frame);
+        }
+        rest = &rest[end + 1..];
+    }
+    frames
+}
+
+fn parse_threads(fields: &str) -> Vec<Thread> {
+    let Some(list) = extract_list(fields, "threads") else {
+        return vec![];
+    };
+
+    let mut threads = vec![];
+    let mut rest = list;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = find_closing_brace(rest) else {
+            break;
+        };
+        let block = &rest[..end];
+        // Sin el frame: sus args también tienen name="..."
+        let frame = parse_frame_field(block);
+        let own = match block.find("frame={") {
+            Some(i) => {
+                let after = &block[i + "frame={".len()..];
+                let close = find_closing_brace(after).map_or(after.len(), |e| e + 1);
+                format!("{}{}", &block[..i], &after[close..])
+            }
+            None => block.to_owned(),
+        };
+        if let Some(id) = extract_str(&own, "id").and_then(|s| s.parse().ok()) {
+            threads.push(Thread {
+                id,
+                target_id: extract_str(&own, "target-id").unwrap_or_default(),
+                name: extract_str(&own, "name"),
+                state: extract_str(&own, "state").unwrap_or_default(),
+                frame,
+            });
+        }
+        rest = &rest[end + 1..];
+    }
+    threads
+}
+
+fn parse_changelist(fields: &str) -> Vec<VarChange> {
+    let Some(list) = extract_list(fields, "changelist") else {
+        return vec![];
+    };
+
+    let mut changes = vec![];
+    let mut rest = list;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = find_closing_brace(rest) else {
+            break;
+        };
+        let block = &rest[..end];
+        if let Some(var) = extract_str(block, "name") {
+            changes.push(VarChange {
+                var,
+                value: extract_str(block, "value"),
+                // "false" once its frame is gone, "invalid" if it can't be
+                // re-evaluated at all
+                in_scope: !matches!(
+                    extract_str(block, "in_scope").as_deref(),
+                    Some("false" | "invalid")
+                ),
+            });
+        }
+        rest = &rest[end + 1..];
+    }
+    changes
+}
+
 fn parse_breakpoint_field(fields: &str, key: &str) -> Option<Breakpoint> {
     let block = extract_block(fields, key)?;
 
     let id = extract_str(block, "number")
         .and_then(|s| s.parse().ok())
         .unwrap_or(0);
-    let file = extract_str(block, "fullname").or_else(|| extract_str(&block, "file"))?;
-    let line = extract_str(block, "line")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
+    let type_ = extract_str(block, "type").unwrap_or_default();
+    let mut kind = if type_.ends_with("watchpoint") {
+        BreakpointKind::Watch
+    } else if type_ == "catchpoint" || extract_str(block, "catch-type").is_some() {
+        BreakpointKind::Catch
+    } else if type_.ends_with("tracepoint") {
+        BreakpointKind::Trace
+    } else {
+        BreakpointKind::Line
+    };
+    let mut what = match kind {
+        BreakpointKind::Watch => extract_str(block, "what"),
+        BreakpointKind::Catch => {
+            extract_str(block, "what").or_else(|| extract_str(block, "catch-type"))
+        }
+        _ => None,
+    };
+    // Pending: no file/line/addr yet (or addr="<PENDING>"), only pending="loc"
+    let pending_loc = extract_str(block, "pending");
+    let source = extract_str(block, "fullname").or_else(|| extract_str(block, "file"));
+    let (file, line) = match (&pending_loc, extract_str(block, "line")) {
+        (Some(loc), None) => match loc.rsplit_once(':') {
+            Some((file, line)) if line.parse::<u32>().is_ok() => {
+                (file.to_owned(), line.parse().unwrap_or(0))
+            }
+            _ => (loc.clone(), 0),
+        },
+        (None, Some(line)) if source.is_some() => {
+            (source.unwrap_or_default(), line.parse().unwrap_or(0))
+        }
+        _ if what.is_some() => (String::new(), 0),
+        // No line info: say where by function and address instead
+        _ => {
+            let addr = extract_str(block, "addr").filter(|a| a.starts_with("0x"))?;
+            if kind == BreakpointKind::Line {
+                kind = BreakpointKind::Address;
+            }
+            what = Some(match extract_str(block, "func") {
+                Some(func) => format!("{func} @ {addr}"),
+                None => addr,
+            });
+            (String::new(), 0)
+        }
+    };
+    let pending = pending_loc.is_some() && extract_str(block, "fullname").is_none();
     let enabled = extract_str(block, "enabled")
         .map(|s| s == "y")
         .unwrap_or(true);
+    let temporary = extract_str(block, "disp").as_deref() == Some("del");
+    let condition = extract_str(block, "cond");
+    let ignore_count = extract_str(block, "ignore")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
 
     Some(Breakpoint {
         id,
+        kind,
         file,
         line,
         enabled,
+        temporary,
+        condition,
+        ignore_count,
+        pending,
+        log_message: None,
+        original_location: extract_str(block, "original-location"),
+        what,
+        commands: extract_block(block, "script")
+            .map(parse_string_list)
+            .unwrap_or_default(),
     })
 }
 
 fn parse_variables(fields: &str) -> Vec<Variable> {
-    let list = match extract_list(fields, "variables") {
-        Some(l) => l,
-        None => return vec![],
-    };
+    match extract_list(fields, "variables") {
+        Some(list) => parse_variable_list(list),
+        None => vec![],
+    }
+}
 
+/// `{name="a",value="1"},{name="b",value="2"}` (or a single bare entry).
+fn parse_variable_list(list: &str) -> Vec<Variable> {
     let mut vars = vec![];
 
     if list.contains('{') {
@@ -315,34 +795,51 @@ fn unquote(s: &str) -> Option<String> {
     let s = s.trim();
     if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
         Some(unescape(&s[1..s.len() - 1]))
-    } else if s.starts_with('"') {
-        Some(unescape(&s[1..]))
+    } else if let Some(rest) = s.strip_prefix('"') {
+        Some(unescape(rest))
     } else {
         Some(s.to_owned())
     }
 }
 
+/// C-string escapes back to text. Depending on `set charset`, GDB sends
+/// non-ASCII either as is or as octal escapes of its UTF-8 bytes
+/// (`\303\251` for é), so the bytes are collected before decoding.
 fn unescape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
+    let mut out: Vec<u8> = Vec::with_capacity(s.len());
     let mut chars = s.chars().peekable();
+    let mut buf = [0u8; 4];
     while let Some(c) = chars.next() {
         if c == '\\' {
             match chars.next() {
-                Some('"') => out.push('"'),
-                Some('n') => out.push('\n'),
-                Some('t') => out.push('\t'),
-                Some('\\') => out.push('\\'),
+                Some('"') => out.push(b'"'),
+                Some('n') => out.push(b'\n'),
+                Some('t') => out.push(b'\t'),
+                Some('\\') => out.push(b'\\'),
+                Some(d @ '0'..='7') => {
+                    let mut byte = d as u32 - '0' as u32;
+                    for _ in 0..2 {
+                        match chars.peek() {
+                            Some(&d @ '0'..='7') => {
+                                byte = byte * 8 + (d as u32 - '0' as u32);
+                                chars.next();
+                            }
+                            _ => break,
+                        }
+                    }
+                    out.push(byte as u8);
+                }
                 Some(x) => {
-                    out.push('\\');
-                    out.push(x);
+                    out.push(b'\\');
+                    out.extend_from_slice(x.encode_utf8(&mut buf).as_bytes());
                 }
-                None => out.push('\\'),
+                None => out.push(b'\\'),
             }
         } else {
-            out.push(c);
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
         }
     }
-    out
+    String::from_utf8_lossy(&out).into_owned()
 }
 
 fn find_closing_quote(s: &str) -> Option<usize> {
@@ -404,6 +901,31 @@ fn find_closing(s: &str, open: char, close: char) -> Option<usize> {
     None
 }
 
+// ─── Memory ───────────────────────────────────────────────────────────────────
+
+/// First readable block of the reply. GDB splits the range around unmapped
+/// pages; the rest isn't worth a gap-aware view.
+fn parse_memory(fields: &str) -> Option<MemoryBlock> {
+    let block = extract_list(fields, "memory")?;
+    let hex_addr = |key| {
+        extract_str(block, key)
+            .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+    };
+    let addr = hex_addr("begin")? + hex_addr("offset").unwrap_or(0);
+    let bytes = decode_hex(&extract_str(block, "contents")?)?;
+    Some(MemoryBlock { addr, bytes })
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
 // ─── Register names ─────────────────────────────────────────────────────────
 
 fn parse_register_names(fields: &str) -> Vec<String> {
@@ -412,21 +934,25 @@ fn parse_register_names(fields: &str) -> Vec<String> {
         None => return vec![],
     };
 
-    // La lista es: "rax","rbx","rcx",... (strings separados por coma)
-    let mut names = vec![];
+    parse_string_list(list)
+}
+
+/// `"rax","rbx","rcx"`, the inside of a list of c-strings.
+fn parse_string_list(list: &str) -> Vec<String> {
+    let mut strings = vec![];
     let mut rest = list;
 
     while let Some(q) = rest.find('"') {
         rest = &rest[q + 1..];
         if let Some(end) = find_closing_quote(rest) {
-            names.push(rest[..end].to_owned());
+            strings.push(unescape(&rest[..end]));
             rest = &rest[end + 1..];
         } else {
             break;
         }
     }
 
-    names
+    strings
 }
 
 // ─── Registers ───────────────────────────────────────────────────────────────
@@ -467,11 +993,100 @@ fn parse_registers(fields: &str) -> Vec<crate::state::Register> {
 // ─── Disassembly ─────────────────────────────────────────────────────────────
 
 fn parse_disasm(fields: &str) -> Vec<crate::state::AsmLine> {
-    let list = match extract_list(fields, "asm_insns") {
-        Some(l) => l,
-        None => return vec![],
+    match extract_list(fields, "asm_insns") {
+        Some(list) => parse_asm_insns(list),
+        None => vec![],
+    }
+}
+
+/// `-data-disassemble -- 1` result: (address, source line) of each instruction.
+/// Empty for code without line info, where GDB answers like mode 0.
+pub fn parse_asm_source_lines(line: &str) -> Vec<(u64, u32)> {
+    const KEY: &str = "src_and_asm_line={";
+    let mut out = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find(KEY) {
+        rest = &rest[start + KEY.len()..];
+        let Some(end) = find_closing_brace(rest) else {
+            break;
+        };
+        let block = &rest[..end];
+        if let Some(line) = extract_str(block, "line").and_then(|s| s.parse().ok())
+            && let Some(insns) = extract_list(block, "line_asm_insn")
+        {
+            out.extend(parse_asm_insns(insns).into_iter().map(|i| (i.addr, line)));
+        }
+        rest = &rest[end + 1..];
+    }
+
+    out
+}
+
+/// `^done,symbols={debug=[{fullname="…",symbols=[{line="5",name="main",…}]}],
+/// nondebug=[{address="0x401000",name="_init"}]}` → debug symbols by file,
+/// then the others.
+pub fn parse_symbol_functions(line: &str) -> Vec<Symbol> {
+    let Some(outer) = extract_block(line, "symbols") else {
+        return vec![];
     };
+    let mut symbols = vec![];
 
+    if let Some(files) = extract_list(outer, "debug") {
+        let mut rest = files;
+        while let Some(start) = rest.find('{') {
+            rest = &rest[start + 1..];
+            let Some(end) = find_closing_brace(rest) else {
+                break;
+            };
+            let block = &rest[..end];
+            let file = extract_str(block, "fullname").or_else(|| extract_str(block, "filename"));
+            let mut entries = extract_list(block, "symbols").unwrap_or_default();
+            while let Some(start) = entries.find('{') {
+                entries = &entries[start + 1..];
+                let Some(end) = find_closing_brace(entries) else {
+                    break;
+                };
+                let entry = &entries[..end];
+                if let Some(name) = extract_str(entry, "name") {
+                    symbols.push(Symbol {
+                        name,
+                        file: file.clone(),
+                        line: extract_str(entry, "line").and_then(|s| s.parse().ok()),
+                        addr: None,
+                    });
+                }
+                entries = &entries[end + 1..];
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+
+    if let Some(list) = extract_list(outer, "nondebug") {
+        let mut rest = list;
+        while let Some(start) = rest.find('{') {
+            rest = &rest[start + 1..];
+            let Some(end) = find_closing_brace(rest) else {
+                break;
+            };
+            let block = &rest[..end];
+            if let Some(name) = extract_str(block, "name") {
+                symbols.push(Symbol {
+                    name,
+                    file: None,
+                    line: None,
+                    addr: extract_str(block, "address")
+                        .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()),
+                });
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+
+    symbols
+}
+
+fn parse_asm_insns(list: &str) -> Vec<crate::state::AsmLine> {
     let mut lines = vec![];
     let mut rest = list;
 
@@ -488,7 +1103,9 @@ fn parse_disasm(fields: &str) -> Vec<crate::state::AsmLine> {
             let inst = extract_str(block, "inst").unwrap_or_default();
             lines.push(crate::state::AsmLine {
                 addr,
+                function: extract_str(block, "func-name"),
                 offset,
+                opcodes: extract_str(block, "opcodes"),
                 inst,
                 current: false,
             });
@@ -553,6 +1170,675 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_token() {
+        assert_eq!(parse_token("42^done"), Some(42));
+        assert_eq!(parse_token("^done"), None);
+        assert_eq!(parse_token("*stopped"), None);
+    }
+
+    #[test]
+    fn test_parse_eval_result() {
+        assert_eq!(
+            parse_eval_result("7^done,value=\"0x2a\""),
+            Some(Ok("0x2a".into()))
+        );
+        assert_eq!(
+            parse_eval_result("8^error,msg=\"No symbol \\\"x\\\" in current context.\""),
+            Some(Err("No symbol \"x\" in current context.".into()))
+        );
+        assert_eq!(parse_eval_result("*running,thread-id=\"all\""), None);
+    }
+
+    #[test]
+    fn test_parse_utf8_values() {
+        // Sent as is
+        assert_eq!(
+            parse_eval_result(r#"9^done,value="0x4006f4 \"héllo wörld ✓\"""#),
+            Some(Ok("0x4006f4 \"héllo wörld ✓\"".into()))
+        );
+        // As octal escapes of the UTF-8 bytes
+        assert_eq!(
+            parse_eval_result(r#"10^done,value="\"caf\303\251 \342\234\223\"""#),
+            Some(Ok("\"café ✓\"".into()))
+        );
+        // Multibyte text right before and after the delimiters
+        let line = r#"=breakpoint-created,bkpt={number="1",type="breakpoint",addr="0x401126",func="größe",file="ñandú.c",fullname="/src/ñandú.c",line="7",original-location="größe"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.file, "/src/ñandú.c");
+                assert_eq!(bp.original_location.as_deref(), Some("größe"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(strip_token("é"), "é");
+        assert_eq!(parse_token("é"), None);
+        assert_eq!(console_text(r#"~"ü\n""#).as_deref(), Some("ü\n"));
+    }
+
+    #[test]
+    fn test_parse_exited() {
+        let event = parse_line("*stopped,reason=\"exited\",exit-code=\"012\"");
+        assert!(matches!(
+            event,
+            Some(DebuggerEvent::State(StateEvent::ProgramExited {
+                code: Some(10),
+                signal: None
+            }))
+        ));
+
+        let event = parse_line("*stopped,reason=\"exited-signalled\",signal-name=\"SIGSEGV\"");
+        assert!(matches!(
+            event,
+            Some(DebuggerEvent::State(StateEvent::ProgramExited { code: None, signal: Some(ref s) }))
+                if s == "SIGSEGV"
+        ));
+    }
+
+    #[test]
+    fn test_parse_breakpoint_options() {
+        let line = r#"^done,bkpt={number="2",type="breakpoint",disp="del",enabled="n",file="main.c",fullname="/src/main.c",line="10",cond="i > 3",ignore="2"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.id, 2);
+                assert_eq!(bp.file, "/src/main.c");
+                assert!(bp.temporary);
+                assert!(!bp.enabled);
+                assert_eq!(bp.condition.as_deref(), Some("i > 3"));
+                assert_eq!(bp.ignore_count, 2);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_pending_breakpoint() {
+        let line = r#"=breakpoint-created,bkpt={number="3",type="breakpoint",disp="keep",enabled="y",addr="<PENDING>",pending="libfoo.c:12",times="0",original-location="libfoo.c:12"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert!(bp.pending);
+                assert_eq!(bp.file, "libfoo.c");
+                assert_eq!(bp.line, 12);
+                assert_eq!(bp.original_location.as_deref(), Some("libfoo.c:12"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let line = r#"=breakpoint-created,bkpt={number="4",type="breakpoint",disp="keep",enabled="y",pending="foo_init",times="0"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert!(bp.pending);
+                assert_eq!(bp.file, "foo_init");
+                assert_eq!(bp.line, 0);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        // Resolved after the library loads
+        let line = r#"=breakpoint-modified,bkpt={number="3",type="breakpoint",disp="keep",enabled="y",addr="0x7ffff7fc1109",func="foo",file="libfoo.c",fullname="/src/libfoo.c",line="12",times="0"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert!(!bp.pending);
+                assert_eq!(bp.file, "/src/libfoo.c");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_breakpoint_original_location() {
+        let line = r#"^done,bkpt={number="1",type="breakpoint",disp="keep",enabled="y",addr="0x401126",func="compute",file="main.c",fullname="/src/main.c",line="4",thread-groups=["i1"],times="0",original-location="compute"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.original_location.as_deref(), Some("compute"));
+                assert_eq!(bp.file, "/src/main.c");
+                assert_eq!(bp.line, 4);
+                assert_eq!(bp.kind, BreakpointKind::Line);
+                assert_eq!(bp.what, None);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        // Older GDBs leave it out
+        let line = r#"^done,bkpt={number="2",type="breakpoint",file="main.c",fullname="/src/main.c",line="9"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.original_location, None);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_watchpoint_breakpoint() {
+        let line = r#"=breakpoint-created,bkpt={number="5",type="hw watchpoint",disp="keep",enabled="y",addr="",what="data->count",times="0",original-location="-location data->count"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.id, 5);
+                assert_eq!(bp.kind, BreakpointKind::Watch);
+                assert_eq!(bp.what.as_deref(), Some("data->count"));
+                assert_eq!(bp.file, "");
+                assert_eq!(bp.line, 0);
+                assert!(!bp.pending);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let line = r#"=breakpoint-modified,bkpt={number="6",type="read watchpoint",disp="keep",enabled="y",addr="",what="flag",times="1"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.what.as_deref(), Some("flag"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_breakpoint_commands() {
+        let line = r#"=breakpoint-modified,bkpt={number="3",type="breakpoint",disp="keep",enabled="y",addr="0x401126",func="main",file="main.c",fullname="/src/main.c",line="4",thread-groups=["i1"],times="0",script={"silent","printf \"i=%d\\n\", i","continue"},original-location="main.c:4"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(
+                    bp.commands,
+                    vec!["silent", r#"printf "i=%d\n", i"#, "continue"]
+                );
+                assert_eq!(bp.original_location.as_deref(), Some("main.c:4"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_catchpoint() {
+        let line = r#"=breakpoint-created,bkpt={number="7",type="catchpoint",disp="keep",enabled="y",what="exception throw",catch-type="throw",thread-groups=["i1"],times="0"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.id, 7);
+                assert_eq!(bp.kind, BreakpointKind::Catch);
+                assert_eq!(bp.what.as_deref(), Some("exception throw"));
+                assert_eq!(bp.file, "");
+                assert_eq!(bp.line, 0);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let line = r#"^done,bkpt={number="8",type="catchpoint",disp="keep",enabled="y",what="fork",times="0"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.kind, BreakpointKind::Catch);
+                assert_eq!(bp.what.as_deref(), Some("fork"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_breakpoint_without_line() {
+        // A function from a library without debug info
+        let line = r#"^done,bkpt={number="2",type="breakpoint",disp="keep",enabled="y",addr="0x00007ffff7e50e50",func="puts",thread-groups=["i1"],times="0",original-location="puts"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.kind, BreakpointKind::Address);
+                assert_eq!(bp.what.as_deref(), Some("puts @ 0x00007ffff7e50e50"));
+                assert_eq!(bp.line, 0);
+                assert!(!bp.pending);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let line = r#"^done,bkpt={number="3",type="breakpoint",disp="keep",enabled="y",addr="0x401000",times="0",original-location="*0x401000"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.kind, BreakpointKind::Address);
+                assert_eq!(bp.what.as_deref(), Some("0x401000"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let line = r#"=breakpoint-created,bkpt={number="4",type="tracepoint",disp="keep",enabled="y",addr="0x401126",func="compute",file="main.c",fullname="/src/main.c",line="4",times="0"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::BreakpointAdded { breakpoint: bp })) => {
+                assert_eq!(bp.kind, BreakpointKind::Trace);
+                assert_eq!(bp.line, 4);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_breakpoint_line_round_trip() {
+        use crate::gdb::writer::command_to_mi;
+        use crate::state::DebuggerState;
+        use crate::ui::command::{BreakpointSpec, Command};
+
+        // The gutter's numbers are 1-based, and so are GDB's
+        let cmd = Command::InsertBreakpoint(BreakpointSpec::new("/src/main.c:43"));
+        assert_eq!(command_to_mi(&cmd), "-break-insert /src/main.c:43");
+
+        let line = r#"=breakpoint-created,bkpt={number="1",type="breakpoint",disp="keep",enabled="y",addr="0x401140",func="main",file="main.c",fullname="/src/main.c",line="43",times="0",original-location="/src/main.c:43"}"#;
+        let Some(DebuggerEvent::State(event)) = parse_line(line) else {
+            panic!("not a state event");
+        };
+        let mut state = DebuggerState::new();
+        state.apply(event);
+        assert_eq!(
+            state.breakpoint_at("/src/main.c", 43).map(|bp| bp.id),
+            Some(1)
+        );
+        assert!(state.breakpoint_at("/src/main.c", 42).is_none());
+        assert!(state.breakpoint_at("/src/main.c", 44).is_none());
+    }
+
+    #[test]
+    fn test_parse_source_path() {
+        let line = if cfg!(windows) {
+            r#"^done,source-path="C:\\src;$cdir;$cwd""#
+        } else {
+            r#"^done,source-path="/home/me/src:$cdir:$cwd""#
+        };
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::SourceDirsUpdated { dirs })) => {
+                assert_eq!(dirs.len(), 3);
+                assert_eq!(&dirs[1..], ["$cdir", "$cwd"]);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_asm_source_lines() {
+        let line = r#"^done,asm_insns=[src_and_asm_line={line="4",file="main.c",fullname="/src/main.c",line_asm_insn=[{address="0x0000000000401126",func-name="main",offset="0",inst="push   %rbp"},{address="0x0000000000401127",func-name="main",offset="1",inst="mov    %rsp,%rbp"}]},src_and_asm_line={line="5",file="main.c",fullname="/src/main.c",line_asm_insn=[{address="0x000000000040112a",func-name="main",offset="4",inst="mov    $0x0,%eax"}]}]"#;
+        assert_eq!(
+            parse_asm_source_lines(line),
+            vec![(0x401126, 4), (0x401127, 4), (0x40112a, 5)]
+        );
+
+        // Mode 2 still parses as plain disassembly, now with the function name
+        let line = r#"^done,asm_insns=[{address="0x0000000000401126",func-name="main",offset="0",opcodes="55",inst="push   %rbp"}]"#;
+        let Some(DebuggerEvent::State(StateEvent::DisasmUpdated { lines })) = parse_line(line)
+        else {
+            panic!("expected DisasmUpdated");
+        };
+        assert_eq!(lines[0].function.as_deref(), Some("main"));
+        assert_eq!(lines[0].opcodes.as_deref(), Some("55"));
+    }
+
+    #[test]
+    fn test_parse_watchpoint_stop() {
+        let line = r#"*stopped,reason="watchpoint-trigger",wpt={number="2",exp="count"},value={old="1",new="2"},frame={addr="0x401136",func="main",args=[],file="main.c",fullname="/src/main.c",line="7"},thread-id="1""#;
+        let Some(DebuggerEvent::State(StateEvent::ProgramPaused { pause })) = parse_line(line)
+        else {
+            panic!("expected ProgramPaused");
+        };
+        match pause.stop_reason {
+            StopReason::WatchpointTrigger { id, expr, old, new } => {
+                assert_eq!(id, 2);
+                assert_eq!(expr, "count");
+                assert_eq!(old.as_deref(), Some("1"));
+                assert_eq!(new.as_deref(), Some("2"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let line = r#"*stopped,reason="read-watchpoint-trigger",hw-rwpt={number="3",exp="flag"},value={value="0"},frame={addr="0x401136",func="main",args=[],line="9"},thread-id="1""#;
+        let Some(DebuggerEvent::State(StateEvent::ProgramPaused { pause })) = parse_line(line)
+        else {
+            panic!("expected ProgramPaused");
+        };
+        assert!(matches!(
+            pause.stop_reason,
+            StopReason::WatchpointTrigger { id: 3, old: None, new: Some(ref v), .. } if v == "0"
+        ));
+    }
+
+    #[test]
+    fn test_parse_stop_without_frame() {
+        let line = r#"*stopped,reason="signal-received",signal-name="SIGSEGV",signal-meaning="Segmentation fault",thread-id="2",stopped-threads="all",core="1""#;
+        let Some(DebuggerEvent::State(StateEvent::ProgramPaused { pause })) = parse_line(line)
+        else {
+            panic!("expected ProgramPaused");
+        };
+        assert_eq!(pause.thread_id, 2);
+        assert_eq!(pause.frame.function, "??");
+        assert_eq!(pause.frame.file, None);
+        assert_eq!(pause.frame.line, None);
+        assert_eq!(pause.stack.len(), 1);
+        assert!(matches!(pause.stop_reason, StopReason::Signal(ref s) if s == "SIGSEGV"));
+
+        let line = r#"*stopped,reason="syscall-return",syscall-number="1",syscall-name="write",thread-id="1",stopped-threads="all""#;
+        let Some(DebuggerEvent::State(StateEvent::ProgramPaused { pause })) = parse_line(line)
+        else {
+            panic!("expected ProgramPaused");
+        };
+        assert!(matches!(pause.stop_reason, StopReason::SyscallReturn(ref s) if s == "write"));
+    }
+
+    #[test]
+    fn test_parse_target_stream() {
+        assert!(matches!(
+            parse_line(r#"@"hello\n""#),
+            Some(DebuggerEvent::State(StateEvent::ProgramOutput { text })) if text == "hello\n"
+        ));
+    }
+
+    #[test]
+    fn test_parse_remote_disconnect() {
+        assert!(matches!(
+            parse_line(r#"&"Remote connection closed\n""#),
+            Some(DebuggerEvent::State(StateEvent::RemoteDisconnected { reason }))
+                if reason == "Remote connection closed"
+        ));
+        assert!(matches!(
+            parse_line(
+                r#"5^error,msg="Remote communication error.  Target disconnected: Connection reset by peer.""#
+            ),
+            Some(DebuggerEvent::State(StateEvent::RemoteDisconnected { .. }))
+        ));
+        // Other log lines are still ignored
+        assert!(parse_line(r#"&"warning: no symbols\n""#).is_none());
+    }
+
+    #[test]
+    fn test_parse_thread_notifications() {
+        assert!(matches!(
+            parse_line(r#"=thread-created,id="3",group-id="i1""#),
+            Some(DebuggerEvent::State(StateEvent::ThreadCreated { id: 3 }))
+        ));
+        assert!(matches!(
+            parse_line(r#"=thread-exited,id="3",group-id="i1""#),
+            Some(DebuggerEvent::State(StateEvent::ThreadExited { id: 3 }))
+        ));
+        let line = r#"=thread-selected,id="2",frame={level="0",addr="0x401200",func="worker",args=[],file="w.c",fullname="/src/w.c",line="8"}"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::ThreadSelected {
+                id: 2,
+                frame: Some(frame),
+            })) => assert_eq!(frame.function, "worker"),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_thread_info() {
+        let line = r#"^done,threads=[{id="2",target-id="Thread 0x7ffff7d89640 (LWP 1235)",name="worker",frame={level="0",addr="0x7ffff7e4a1b0",func="__futex_abstimed_wait_common",args=[{name="futex_word",value="0x4040a0"}],from="/lib/libc.so.6"},state="stopped",core="1"},{id="1",target-id="Thread 0x7ffff7d8a740 (LWP 1234)",frame={level="0",addr="0x401136",func="main",args=[],file="main.c",fullname="/src/main.c",line="12"},state="stopped",core="0"}],current-thread-id="1""#;
+        let Some(DebuggerEvent::State(StateEvent::ThreadsUpdated { threads, current })) =
+            parse_line(line)
+        else {
+            panic!("expected threads");
+        };
+        assert_eq!(current, Some(1));
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].id, 2);
+        assert_eq!(threads[0].name.as_deref(), Some("worker"));
+        assert_eq!(threads[0].state, "stopped");
+        assert_eq!(
+            threads[0].frame.as_ref().map(|f| f.function.as_str()),
+            Some("__futex_abstimed_wait_common")
+        );
+        // No name of its own: the frame's args don't count
+        assert_eq!(threads[1].name, None);
+        assert_eq!(threads[1].target_id, "Thread 0x7ffff7d8a740 (LWP 1234)");
+        assert_eq!(threads[1].frame.as_ref().and_then(|f| f.line), Some(12));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = |line| match parse_line(line) {
+            Some(DebuggerEvent::Ui(UiEvent::Query {
+                question,
+                pagination,
+            })) => Some((question, pagination)),
+            _ => None,
+        };
+        assert_eq!(
+            query(r#"~"Make breakpoint pending on future shared library load? (y or [n]) ""#),
+            Some((
+                "Make breakpoint pending on future shared library load?".into(),
+                false
+            ))
+        );
+        assert_eq!(
+            query(r#"~"Delete all breakpoints? (y or n) ""#),
+            Some(("Delete all breakpoints?".into(), false))
+        );
+        assert_eq!(
+            query(r#"~"--Type <RET> for more, q to quit, c to continue without paging--""#),
+            Some((
+                "--Type <RET> for more, q to quit, c to continue without paging--".into(),
+                true
+            ))
+        );
+        // GDB's note after answering by itself is plain output
+        assert_eq!(query(r#"~"[answered Y; input not from terminal]\n""#), None);
+    }
+
+    #[test]
+    fn test_parse_language() {
+        let language = |line| match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::LanguageDetected { language })) => Some(language),
+            _ => None,
+        };
+        assert_eq!(
+            language(r#"~"The current source language is \"auto; currently c++\".\n""#),
+            Some(Language::Cpp)
+        );
+        assert_eq!(
+            language(r#"~"The current source language is \"rust\".\n""#),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            language(r#"~"The current source language is \"auto; currently minimal\".\n""#),
+            Some(Language::Unknown)
+        );
+        assert_eq!(language(r#"~"Starting program: /tmp/a.out\n""#), None);
+    }
+
+    #[test]
+    fn test_parse_mappings() {
+        let text = "process 4242\nMapped address spaces:\n\n          \
+            Start Addr           End Addr       Size     Offset  Perms  objfile\n            \
+            0x400000           0x401000     0x1000        0x0  r--p   /src/my app\n      \
+            0x7ffff7fc1000     0x7ffff7fc5000     0x4000        0x0  rw-p   \n";
+        let regions = parse_mappings(text);
+        assert_eq!(
+            regions,
+            vec![
+                MemoryRegion {
+                    start: 0x400000,
+                    end: 0x401000,
+                    offset: 0,
+                    perms: "r--p".into(),
+                    path: "/src/my app".into(),
+                },
+                MemoryRegion {
+                    start: 0x7ffff7fc1000,
+                    end: 0x7ffff7fc5000,
+                    offset: 0,
+                    perms: "rw-p".into(),
+                    path: String::new(),
+                },
+            ]
+        );
+
+        // Before GDB 12: no Perms column
+        let old = "0x555555554000 0x555555556000 0x2000 0x1000 /usr/lib/libc.so.6";
+        let region = &parse_mappings(old)[0];
+        assert_eq!(region.perms, "");
+        assert_eq!(region.offset, 0x1000);
+        assert_eq!(region.path, "/usr/lib/libc.so.6");
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        let line = r#"^done,memory=[{begin="0x00007fffffffe0a0",offset="0x0000000000000004",end="0x00007fffffffe0a8",contents="48690a00"}]"#;
+        assert_eq!(
+            parse_memory(&line[6..]),
+            Some(MemoryBlock {
+                addr: 0x7fffffffe0a4,
+                bytes: vec![0x48, 0x69, 0x0a, 0x00],
+            })
+        );
+        assert!(matches!(
+            parse_line(line),
+            Some(DebuggerEvent::State(StateEvent::MemoryRead { .. }))
+        ));
+        assert_eq!(decode_hex("0g"), None);
+        assert_eq!(decode_hex("abc"), None);
+    }
+
+    #[test]
+    fn test_parse_checkpoint() {
+        assert!(matches!(
+            parse_line(r#"~"checkpoint 1: fork returned pid 4242.\n""#),
+            Some(DebuggerEvent::State(StateEvent::CheckpointCreated {
+                id: 1,
+                pid: 4242
+            }))
+        ));
+        assert!(matches!(
+            parse_line(r#"~"checkpoint is a nice word\n""#),
+            Some(DebuggerEvent::Ui(UiEvent::ConsoleOutput(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_thread_groups() {
+        assert!(matches!(
+            parse_line(r#"=thread-group-added,id="i2""#),
+            Some(DebuggerEvent::State(StateEvent::InferiorAdded { id: 2 }))
+        ));
+        assert!(matches!(
+            parse_line(r#"=thread-group-started,id="i1",pid="4242""#),
+            Some(DebuggerEvent::State(StateEvent::InferiorStarted {
+                id: 1,
+                pid: 4242
+            }))
+        ));
+        assert!(matches!(
+            parse_line(r#"=thread-group-exited,id="i1",exit-code="0""#),
+            Some(DebuggerEvent::State(StateEvent::InferiorExited { id: 1 }))
+        ));
+        assert!(matches!(
+            parse_line(r#"=thread-group-removed,id="i2""#),
+            Some(DebuggerEvent::State(StateEvent::InferiorRemoved { id: 2 }))
+        ));
+        assert!(parse_line(r#"=thread-group-added,id="x""#).is_none());
+    }
+
+    #[test]
+    fn test_parse_frame_args() {
+        let line = r#"9^done,stack-args=[frame={level="0",args=[{name="n",value="3"},{name="s",value="0x4006f4 \"a, b\""}]},frame={level="1",args=[]}]"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::FrameArgsUpdated { frames })) => {
+                assert_eq!(frames.len(), 2);
+                let (level, args) = &frames[0];
+                assert_eq!(*level, 0);
+                assert_eq!(args.len(), 2);
+                assert_eq!(args[0].name, "n");
+                assert_eq!(args[0].value, "3");
+                assert_eq!(args[1].value, "0x4006f4 \"a, b\"");
+                assert_eq!(frames[1].0, 1);
+                assert!(frames[1].1.is_empty());
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_partial_stack() {
+        let line = r#"12^done,stack=[frame={level="32",addr="0x401136",func="recurse",file="r.c",fullname="/src/r.c",line="4",arch="i386:x86-64"},frame={level="33",addr="0x401150",func="recurse",file="r.c",line="6"}]"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::StackUpdated { frames })) => {
+                assert_eq!(frames.len(), 2);
+                assert_eq!(frames[0].level, 32);
+                assert_eq!(frames[0].addr, 0x401136);
+                assert_eq!(frames[0].file.as_deref(), Some("/src/r.c"));
+                assert_eq!(frames[1].level, 33);
+                assert_eq!(frames[1].line, Some(6));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        assert!(matches!(
+            parse_line("13^done,stack=[]"),
+            Some(DebuggerEvent::State(StateEvent::StackUpdated { frames })) if frames.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_symbol_functions() {
+        let line = r#"7^done,symbols={debug=[{filename="util.c",fullname="/src/util.c",symbols=[{line="12",name="parse_args",type="int (int, char **)",description="int parse_args(int, char **);"},{line="40",name="parse_line",type="char *(char *)",description="static char *parse_line(char *);"}]},{filename="main.c",fullname="/src/main.c",symbols=[{line="5",name="main",type="int (void)",description="int main(void);"}]}],nondebug=[{address="0x0000000000401000",name="_init"}]}"#;
+        let symbols = parse_symbol_functions(line);
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(
+            symbols[0],
+            Symbol {
+                name: "parse_args".into(),
+                file: Some("/src/util.c".into()),
+                line: Some(12),
+                addr: None,
+            }
+        );
+        assert_eq!(symbols[1].name, "parse_line");
+        assert_eq!(symbols[2].file.as_deref(), Some("/src/main.c"));
+        assert_eq!(
+            symbols[3],
+            Symbol {
+                name: "_init".into(),
+                file: None,
+                line: None,
+                addr: Some(0x401000),
+            }
+        );
+
+        assert!(parse_symbol_functions("8^done,symbols={}").is_empty());
+    }
+
+    #[test]
+    fn test_parse_var_create() {
+        assert_eq!(
+            parse_var_create(
+                r#"12^done,name="var1",numchild="0",value="42",type="int",thread-id="1",has_more="0""#
+            ),
+            Some(Ok(WatchValue {
+                var: "var1".into(),
+                value: "42".into(),
+                type_: "int".into(),
+                in_scope: true,
+            }))
+        );
+        assert_eq!(
+            parse_var_create(r#"13^error,msg="No symbol \"nope\" in current context.""#),
+            Some(Err(r#"No symbol "nope" in current context."#.into()))
+        );
+        assert_eq!(parse_var_create("*running,thread-id=\"all\""), None);
+    }
+
+    #[test]
+    fn test_parse_var_update() {
+        let line = r#"14^done,changelist=[{name="var1",value="43",in_scope="true",type_changed="false",has_more="0"},{name="var2",in_scope="false",type_changed="false",has_more="0"}]"#;
+        match parse_line(line) {
+            Some(DebuggerEvent::State(StateEvent::WatchesUpdated { changes })) => {
+                assert_eq!(
+                    changes,
+                    vec![
+                        VarChange {
+                            var: "var1".into(),
+                            value: Some("43".into()),
+                            in_scope: true,
+                        },
+                        VarChange {
+                            var: "var2".into(),
+                            value: None,
+                            in_scope: false,
+                        },
+                    ]
+                );
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
     #[test]
     fn test_ignore_prompt() {
         assert!(parse_line("(gdb)").is_none());
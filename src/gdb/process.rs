@@ -1,71 +1,200 @@
 use std::{
-    io::{BufRead, BufReader, Write},
+    collections::{HashMap, VecDeque},
+    io::{self, BufRead, BufReader, Write},
     process::{Child, ChildStdin, ChildStdout, Command, Stdio},
     sync::mpsc::{Receiver, Sender},
     thread,
+    time::{Duration, Instant},
 };
 
-use super::parser::parse_line;
+use super::backend::GdbBackend;
+use super::parser::{
+    console_text, parse_asm_source_lines, parse_eval_result, parse_line, parse_mappings,
+    parse_symbol_functions, parse_token, parse_var_create, result_class, result_frame,
+};
 use super::writer::command_to_mi;
-use crate::state::{DebuggerEvent, StateEvent, UiEvent};
+use crate::state::{DebuggerEvent, PauseState, StateEvent, StopReason, UiEvent};
 use crate::ui::command::Command as DebuggerCommand;
 
+/// How long a command may go unanswered before the UI hears it's stalled.
+pub const STALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tags commands with increasing tokens on their way to the backend.
 struct GdbWriter {
-    stdin: ChildStdin,
+    backend: Box<dyn GdbBackend>,
     seq: u32,
 }
 
 impl GdbWriter {
-    fn send(&mut self, raw_mi: &str) -> std::io::Result<()> {
-        writeln!(self.stdin, "{}{}", self.seq, raw_mi)?;
-        self.stdin.flush()?;
+    /// Writes one command and returns the token it was tagged with.
+    fn send(&mut self, raw_mi: &str) -> std::io::Result<u32> {
+        let token = self.seq;
+        self.backend.send(&format!("{}{}", token, raw_mi))?;
         self.seq += 1;
-        Ok(())
+        Ok(token)
+    }
+}
+
+/// Longest MI line kept in full; anything past it (e.g. `print huge_array`) is dropped.
+const MAX_LINE_BYTES: usize = 1 << 20;
+
+/// Reads one line into `buf`, keeping at most `cap` bytes of it. The rest of an
+/// oversized line is consumed and discarded instead of buffered. Returns the
+/// line's full length (0 at EOF) and whether it was truncated.
+fn read_capped_line<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    cap: usize,
+) -> io::Result<(usize, bool)> {
+    let mut total = 0;
+    loop {
+        let chunk = match reader.fill_buf() {
+            Ok(chunk) => chunk,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if chunk.is_empty() {
+            break;
+        }
+        let (used, done) = match chunk.iter().position(|&b| b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (chunk.len(), false),
+        };
+        let room = cap.saturating_sub(buf.len());
+        buf.extend_from_slice(&chunk[..used.min(room)]);
+        reader.consume(used);
+        total += used;
+        if done {
+            break;
+        }
     }
+    Ok((total, total > buf.len()))
 }
 
 // ─── Spawn ────────────────────────────────────────────────────────────────────
 
-fn spawn_gdb(
-    executable: Option<&str>,
-) -> std::io::Result<(Child, GdbWriter, BufReader<ChildStdout>)> {
-    let mut cmd = Command::new("gdb");
-    cmd.arg("--interpreter=mi")
-        .arg("--quiet")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null());
+/// A real `gdb --interpreter=mi` child process.
+pub struct ProcessBackend {
+    child: Child,
+    stdin: ChildStdin,
+    line_rx: Receiver<Result<String, String>>,
+}
 
-    if let Some(exe) = executable {
-        cmd.arg(exe);
+impl ProcessBackend {
+    pub fn spawn(executable: Option<&str>) -> std::io::Result<Self> {
+        let mut cmd = Command::new("gdb");
+        cmd.arg("--interpreter=mi")
+            .arg("--quiet")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+
+        if let Some(exe) = executable {
+            cmd.arg(exe);
+        }
+
+        // Grupo de procesos propio: el SIGINT de interrupt no debe llegar a la GUI
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let mut child = cmd.spawn()?;
+        let stdin = child.stdin.take().expect("stdin piped");
+        let stdout_raw = child.stdout.take().expect("stdout piped");
+
+        let (line_tx, line_rx) = std::sync::mpsc::channel();
+        thread::spawn(move || read_lines(BufReader::new(stdout_raw), line_tx));
+
+        Ok(Self {
+            child,
+            stdin,
+            line_rx,
+        })
     }
+}
 
-    let mut child = cmd.spawn()?;
-    let stdin = child.stdin.take().expect("stdin piped");
-    let stdout_raw = child.stdout.take().expect("stdout piped");
+impl GdbBackend for ProcessBackend {
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.stdin, "{line}")?;
+        self.stdin.flush()
+    }
+
+    fn try_recv(&mut self) -> Option<Result<String, String>> {
+        self.line_rx.try_recv().ok()
+    }
 
-    let writer = GdbWriter { stdin, seq: 1 };
-    let reader = BufReader::new(stdout_raw);
+    fn interrupt(&mut self) -> io::Result<()> {
+        signal_interrupt(&self.child)
+    }
 
-    Ok((child, writer, reader))
+    fn kill(&mut self) {
+        let _ = self.child.kill();
+    }
 }
 
-// ─── run_loop ─────────────────────────────────────────────────────────────────
+/// Reader thread: GDB's stdout line by line, with read problems as `Err`.
+fn read_lines(mut reader: BufReader<ChildStdout>, line_tx: Sender<Result<String, String>>) {
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match read_capped_line(&mut reader, &mut buf, MAX_LINE_BYTES) {
+            Ok((0, _)) => break, // EOF
+            Ok((len, truncated)) => {
+                let text = String::from_utf8_lossy(&buf);
+                let mut line = text
+                    .trim_end_matches('\n')
+                    .trim_end_matches('\r')
+                    .to_owned();
+                if truncated {
+                    line.push_str(&format!(" …[truncated, {len} bytes]"));
+                }
+                if !line.is_empty() && line_tx.send(Ok(line)).is_err() {
+                    break;
+                }
+                // Tras su línea: el log MI lo muestra con ella
+                if truncated {
+                    let _ = line_tx.send(Err(format!(
+                        "Línea de GDB demasiado larga ({len} bytes), truncada a {MAX_LINE_BYTES}"
+                    )));
+                }
+            }
+            Err(e) => {
+                let _ = line_tx.send(Err(format!("Error leyendo GDB: {e}")));
+                break;
+            }
+        }
+    }
+}
 
-pub fn run_loop(
+/// Spawns GDB and runs the command/event loop on it until the UI goes away.
+pub fn run_gdb(
     executable: Option<String>,
+    mi_async: bool,
     cmd_rx: Receiver<DebuggerCommand>,
     event_tx: Sender<DebuggerEvent>,
 ) {
-    let (mut child, mut writer, reader) = match spawn_gdb(executable.as_deref()) {
-        Ok(parts) => parts,
+    match ProcessBackend::spawn(executable.as_deref()) {
+        Ok(backend) => run_loop(Box::new(backend), executable, mi_async, cmd_rx, event_tx),
         Err(e) => {
             let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::GdbError(format!(
                 "No se pudo lanzar GDB: {e}"
             ))));
-            return;
         }
-    };
+    }
+}
+
+// ─── run_loop ─────────────────────────────────────────────────────────────────
+
+pub fn run_loop(
+    backend: Box<dyn GdbBackend>,
+    executable: Option<String>,
+    mut mi_async: bool,
+    cmd_rx: Receiver<DebuggerCommand>,
+    event_tx: Sender<DebuggerEvent>,
+) {
+    let mut writer = GdbWriter { backend, seq: 1 };
 
     if let Some(exe) = &executable {
         let _ = event_tx.send(DebuggerEvent::State(StateEvent::ProgramLoaded {
@@ -73,59 +202,771 @@ pub fn run_loop(
         }));
     }
 
-    let (line_tx, line_rx) = std::sync::mpsc::channel::<String>();
-    let event_tx_reader = event_tx.clone();
+    // Antes que nada: con el inferior vivo GDB ya no deja cambiarlo.
+    // Nadie contesta un "--Type <RET> for more" o un "(y or n)": apagarlos
+    let startup = [
+        DebuggerCommand::SetMiAsync(mi_async),
+        DebuggerCommand::SetPagination(false),
+        DebuggerCommand::SetConfirm(false),
+    ];
+    for cmd in &startup {
+        let mi = command_to_mi(cmd);
+        let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::CommandSent(mi.clone())));
+        if let Err(e) = writer.send(&mi) {
+            let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::GdbError(format!(
+                "Error escribiendo a GDB: {e}"
+            ))));
+        }
+    }
 
-    thread::spawn(move || {
-        let mut reader = reader;
-        let mut buf = String::new();
-        loop {
-            buf.clear();
-            match reader.read_line(&mut buf) {
-                Ok(0) => break, // EOF
-                Ok(_) => {
-                    let line = buf.trim_end_matches('\n').trim_end_matches('\r').to_owned();
-                    if !line.is_empty() && line_tx.send(line).is_err() {
-                        break;
+    // Commands whose result needs the request for context, keyed by token
+    let mut pending: HashMap<u32, DebuggerCommand> = HashMap::new();
+    // MI text of every command still waiting for its result, for error
+    // reports, and when it was sent
+    let mut issued: HashMap<u32, (String, Instant)> = HashMap::new();
+    // Last stalled command reported, to only send changes
+    let mut stall: Option<String> = None;
+    // Between *running and *stopped GDB (sync mode) isn't reading stdin
+    let mut target_running = false;
+    // Commands that answer on the console stream, oldest first, with their
+    // text so far. GDB runs commands in order, so `~` lines are the oldest's
+    // once every command sent before it has its result.
+    let mut captures: VecDeque<(u32, DebuggerCommand, String)> = VecDeque::new();
+    // GDB reads the executable's symbols before its next prompt
+    let mut symbols_loading = executable.is_some();
+
+    loop {
+        while let Ok(cmd) = cmd_rx.try_recv() {
+            if let DebuggerCommand::SetMiAsync(on) = cmd {
+                mi_async = on;
+            }
+            if cmd == DebuggerCommand::Interrupt
+                && interrupt_via(mi_async, target_running) == InterruptVia::Signal
+            {
+                let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::CommandSent("SIGINT".into())));
+                match writer.backend.interrupt() {
+                    Ok(()) => continue,
+                    Err(e) => {
+                        let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::GdbError(format!(
+                            "No se pudo enviar SIGINT a GDB: {e}"
+                        ))));
+                    }
+                }
+            }
+
+            let mi = command_to_mi(&cmd);
+
+            let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::CommandSent(mi.clone())));
+
+            match writer.send(&mi) {
+                Ok(token) => {
+                    issued.insert(token, (mi, Instant::now()));
+                    if is_captured(&cmd) {
+                        captures.push_back((token, cmd.clone(), String::new()));
+                    }
+                    if is_correlated(&cmd) {
+                        pending.insert(token, cmd);
                     }
                 }
                 Err(e) => {
-                    let _ = event_tx_reader.send(DebuggerEvent::Ui(UiEvent::GdbError(format!(
-                        "Error leyendo GDB: {e}"
+                    let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::GdbError(format!(
+                        "Error escribiendo a GDB: {e}"
                     ))));
-                    break;
+                    writer.backend.kill();
+                    return;
                 }
             }
         }
-    });
-
-    loop {
-        while let Ok(cmd) = cmd_rx.try_recv() {
-            let mi = command_to_mi(&cmd);
 
-            let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::ConsoleOutput(format!("> {mi}"))));
+        while let Some(line) = writer.backend.try_recv() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::GdbError(e)));
+                    continue;
+                }
+            };
+            let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::MiRecord(line.clone())));
 
-            if let Err(e) = writer.send(&mi) {
-                let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::GdbError(format!(
-                    "Error escribiendo a GDB: {e}"
-                ))));
-                let _ = child.kill();
-                return;
+            if symbols_loading && line.trim_end() == "(gdb)" {
+                symbols_loading = false;
+                let _ = event_tx.send(DebuggerEvent::State(StateEvent::SymbolsLoaded));
             }
-        }
 
-        while let Ok(line) = line_rx.try_recv() {
-            let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::ConsoleOutput(line.clone())));
+            let token = parse_token(&line).filter(|_| result_class(&line).is_some());
+            let command = token.and_then(|t| issued.remove(&t)).map(|(mi, _)| mi);
+            if let Some((first, _, text)) = captures.front_mut()
+                && issued.keys().all(|t| t >= first)
+                && let Some(part) = console_text(&line)
+            {
+                text.push_str(&part);
+            }
+            if let Some(i) = captures.iter().position(|(t, ..)| Some(*t) == token)
+                && let Some((_, cmd, text)) = captures.remove(i)
+                && result_class(&line) == Some("done")
+                && let Some(event) = captured(cmd, text)
+            {
+                let _ = event_tx.send(event);
+            }
+            if let Some(cmd) = token.and_then(|t| pending.remove(&t)) {
+                // Sin info de línea el modo 1 se ve igual que un -data-disassemble
+                // común: no dejar que el parser genérico reemplace la vista
+                let exclusive = matches!(cmd, DebuggerCommand::RequestAsmSourceLines { .. });
+                if let Some(event) = correlate(cmd, &line) {
+                    symbols_loading |= matches!(
+                        event,
+                        DebuggerEvent::State(StateEvent::ProgramLoaded { .. })
+                    );
+                    let _ = event_tx.send(event);
+                }
+                if exclusive {
+                    continue;
+                }
+            }
 
             if let Some(event) = parse_line(&line) {
+                let event = match (event, command) {
+                    (DebuggerEvent::Ui(UiEvent::GdbError(msg)), Some(command)) => {
+                        DebuggerEvent::Ui(UiEvent::CommandFailed { command, msg })
+                    }
+                    (event, _) => event,
+                };
+                match &event {
+                    DebuggerEvent::State(StateEvent::ProgramStarted) => target_running = true,
+                    DebuggerEvent::State(
+                        StateEvent::ProgramPaused { .. }
+                        | StateEvent::ProgramExited { .. }
+                        | StateEvent::ProgramKilled,
+                    ) => target_running = false,
+                    _ => {}
+                }
                 // None = línea ignorable, no es error
                 if event_tx.send(event).is_err() {
-                    let _ = child.kill();
+                    writer.backend.kill();
                     return; // UI cerrada
                 }
             }
         }
 
+        let stalled = stalled(&issued, Instant::now());
+        if stalled != stall {
+            stall = stalled.clone();
+            let _ = event_tx.send(DebuggerEvent::Ui(UiEvent::CommandStalled(stalled)));
+        }
+
         thread::sleep(std::time::Duration::from_millis(10));
     }
 }
+
+// ─── Stall detection ──────────────────────────────────────────────────────────
+
+/// The oldest command without a result after `STALL_TIMEOUT`: GDB is busy
+/// or the target stopped answering.
+fn stalled(issued: &HashMap<u32, (String, Instant)>, now: Instant) -> Option<String> {
+    issued
+        .values()
+        .filter(|(_, sent)| now.duration_since(*sent) >= STALL_TIMEOUT)
+        .min_by_key(|(_, sent)| *sent)
+        .map(|(mi, _)| mi.clone())
+}
+
+// ─── Interrupt ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum InterruptVia {
+    Mi,
+    Signal,
+}
+
+/// In sync mode GDB doesn't read commands while the inferior runs, so an
+/// `-exec-interrupt` would wait in the pipe until the target stops by itself.
+/// Then only a SIGINT to GDB gets through.
+fn interrupt_via(mi_async: bool, target_running: bool) -> InterruptVia {
+    if cfg!(unix) && target_running && !mi_async {
+        InterruptVia::Signal
+    } else {
+        InterruptVia::Mi
+    }
+}
+
+#[cfg(unix)]
+fn signal_interrupt(child: &Child) -> io::Result<()> {
+    use nix::sys::signal::{Signal, killpg};
+    use nix::unistd::Pid;
+
+    killpg(Pid::from_raw(child.id() as i32), Signal::SIGINT).map_err(io::Error::from)
+}
+
+#[cfg(not(unix))]
+fn signal_interrupt(_child: &Child) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+// ─── Result correlation ───────────────────────────────────────────────────────
+
+fn is_correlated(cmd: &DebuggerCommand) -> bool {
+    matches!(
+        cmd,
+        DebuggerCommand::Evaluate(_)
+            | DebuggerCommand::Kill
+            | DebuggerCommand::SelectInferior(_)
+            | DebuggerCommand::SelectThread(_)
+            | DebuggerCommand::DeleteCheckpoint(_)
+            | DebuggerCommand::ToggleBreakpoint { .. }
+            | DebuggerCommand::RequestAsmSourceLines { .. }
+            | DebuggerCommand::SearchSymbols { .. }
+            | DebuggerCommand::CreateVarObject(_)
+            | DebuggerCommand::ConnectRemote(_)
+            | DebuggerCommand::LoadCore(_)
+            | DebuggerCommand::LoadExecutable(_)
+            | DebuggerCommand::WriteMemory { .. }
+    )
+}
+
+fn is_captured(cmd: &DebuggerCommand) -> bool {
+    matches!(
+        cmd,
+        DebuggerCommand::RequestMappings | DebuggerCommand::StopHook(_)
+    )
+}
+
+/// Event for a command that answered on the console stream, from its text.
+fn captured(cmd: DebuggerCommand, text: String) -> Option<DebuggerEvent> {
+    match cmd {
+        DebuggerCommand::RequestMappings => Some(DebuggerEvent::State(StateEvent::MappingsRead {
+            regions: parse_mappings(&text),
+        })),
+        DebuggerCommand::StopHook(command) => Some(DebuggerEvent::Ui(UiEvent::StopHookOutput {
+            command,
+            output: text,
+        })),
+        _ => None,
+    }
+}
+
+/// Event for a `^` result that only makes sense knowing the command behind it.
+fn correlate(cmd: DebuggerCommand, line: &str) -> Option<DebuggerEvent> {
+    match cmd {
+        DebuggerCommand::Evaluate(expr) => parse_eval_result(line)
+            .map(|value| DebuggerEvent::Ui(UiEvent::EvaluationResult { expr, value })),
+        DebuggerCommand::Kill => (result_class(line) == Some("done"))
+            .then_some(DebuggerEvent::State(StateEvent::ProgramKilled)),
+        DebuggerCommand::SelectInferior(id) => (result_class(line) == Some("done"))
+            .then_some(DebuggerEvent::State(StateEvent::InferiorSelected { id })),
+        // MI doesn't announce its own -thread-select with =thread-selected
+        DebuggerCommand::SelectThread(id) => (result_class(line) == Some("done")).then(|| {
+            DebuggerEvent::State(StateEvent::ThreadSelected {
+                id,
+                frame: result_frame(line),
+            })
+        }),
+        // MI doesn't announce its own -break-enable/-break-disable either
+        DebuggerCommand::ToggleBreakpoint { id, enable } => (result_class(line) == Some("done"))
+            .then_some(DebuggerEvent::State(StateEvent::BreakpointToggled {
+                id,
+                enabled: enable,
+            })),
+        DebuggerCommand::DeleteCheckpoint(id) => (result_class(line) == Some("done"))
+            .then_some(DebuggerEvent::State(StateEvent::CheckpointDeleted { id })),
+        DebuggerCommand::RequestAsmSourceLines { .. } => {
+            (result_class(line) == Some("done")).then(|| {
+                DebuggerEvent::State(StateEvent::AsmSourceLinesUpdated {
+                    lines: parse_asm_source_lines(line),
+                })
+            })
+        }
+        DebuggerCommand::SearchSymbols { regex, .. } => {
+            (result_class(line) == Some("done")).then(|| {
+                DebuggerEvent::Ui(UiEvent::SymbolsFound {
+                    regex,
+                    symbols: parse_symbol_functions(line),
+                })
+            })
+        }
+        DebuggerCommand::CreateVarObject(expr) => parse_var_create(line)
+            .map(|result| DebuggerEvent::State(StateEvent::WatchCreated { expr, result })),
+        DebuggerCommand::LoadExecutable(executable) => (result_class(line) == Some("done"))
+            .then_some(DebuggerEvent::State(StateEvent::ProgramLoaded {
+                executable,
+            })),
+        DebuggerCommand::WriteMemory { addr, .. } => {
+            let result = match parse_eval_result(line) {
+                Some(Err(msg)) => Err(msg),
+                _ if result_class(line) == Some("done") => Ok(()),
+                _ => return None,
+            };
+            Some(DebuggerEvent::Ui(UiEvent::MemoryWritten { addr, result }))
+        }
+        DebuggerCommand::ConnectRemote(addr) => match parse_eval_result(line) {
+            Some(Err(msg)) => Some(DebuggerEvent::State(StateEvent::RemoteConnectFailed {
+                addr,
+                msg,
+            })),
+            _ => (result_class(line) == Some("connected"))
+                .then_some(DebuggerEvent::State(StateEvent::RemoteConnected { addr })),
+        },
+        // A core has no *stopped: its frame comes with the result
+        DebuggerCommand::LoadCore(_) => {
+            let frame = result_frame(line)?;
+            Some(DebuggerEvent::State(StateEvent::ProgramPaused {
+                pause: PauseState {
+                    thread_id: 1,
+                    frame: frame.clone(),
+                    stack: vec![frame],
+                    stop_reason: StopReason::Unknown,
+                },
+            }))
+        }
+        _ => None,
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stalled_command() {
+        let t0 = Instant::now();
+        let mut issued = HashMap::from([
+            (4, ("-stack-list-locals 1".to_owned(), t0)),
+            (
+                5,
+                (
+                    "-data-list-register-values x".to_owned(),
+                    t0 + Duration::from_secs(1),
+                ),
+            ),
+        ]);
+        assert_eq!(stalled(&issued, t0), None);
+        assert_eq!(stalled(&issued, t0 + STALL_TIMEOUT / 2), None);
+        // The oldest one is reported
+        assert_eq!(
+            stalled(&issued, t0 + STALL_TIMEOUT * 2).as_deref(),
+            Some("-stack-list-locals 1")
+        );
+        // Once it's answered, the next one if it's late too
+        issued.remove(&4);
+        assert_eq!(stalled(&issued, t0 + STALL_TIMEOUT), None);
+        assert_eq!(
+            stalled(&issued, t0 + STALL_TIMEOUT * 2).as_deref(),
+            Some("-data-list-register-values x")
+        );
+        issued.clear();
+        assert_eq!(stalled(&issued, t0 + STALL_TIMEOUT * 2), None);
+    }
+
+    #[test]
+    fn test_interrupt_via() {
+        // Nothing running or async mode: GDB is reading, MI is enough
+        assert_eq!(interrupt_via(false, false), InterruptVia::Mi);
+        assert_eq!(interrupt_via(true, true), InterruptVia::Mi);
+
+        let expected = if cfg!(unix) {
+            InterruptVia::Signal
+        } else {
+            InterruptVia::Mi
+        };
+        assert_eq!(interrupt_via(false, true), expected);
+    }
+
+    #[test]
+    fn test_read_capped_line() {
+        let huge = format!("^done,value=\"{}\"\n", "x".repeat(100));
+        let input = format!("{huge}*stopped\n");
+        let mut reader = BufReader::with_capacity(16, input.as_bytes());
+        let mut buf = Vec::new();
+
+        let (len, truncated) = read_capped_line(&mut reader, &mut buf, 32).unwrap();
+        assert_eq!(len, huge.len());
+        assert!(truncated);
+        assert_eq!(buf.len(), 32);
+        assert!(buf.starts_with(b"^done,value=\"xxx"));
+
+        buf.clear();
+        let (len, truncated) = read_capped_line(&mut reader, &mut buf, 32).unwrap();
+        assert_eq!((len, truncated), (9, false));
+        assert_eq!(buf, b"*stopped\n");
+
+        buf.clear();
+        assert_eq!(
+            read_capped_line(&mut reader, &mut buf, 32).unwrap(),
+            (0, false)
+        );
+    }
+
+    #[test]
+    fn test_correlate_kill() {
+        assert!(matches!(
+            correlate(DebuggerCommand::Kill, "5^done"),
+            Some(DebuggerEvent::State(StateEvent::ProgramKilled))
+        ));
+        assert!(
+            correlate(
+                DebuggerCommand::Kill,
+                "5^error,msg=\"The program is not being run.\""
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_correlate_select_inferior() {
+        assert!(matches!(
+            correlate(DebuggerCommand::SelectInferior(2), "7^done"),
+            Some(DebuggerEvent::State(StateEvent::InferiorSelected { id: 2 }))
+        ));
+    }
+
+    #[test]
+    fn test_correlate_select_thread() {
+        let line = r#"8^done,new-thread-id="2",frame={level="0",addr="0x401200",func="worker",args=[],file="w.c",fullname="/src/w.c",line="8"}"#;
+        match correlate(DebuggerCommand::SelectThread(2), line) {
+            Some(DebuggerEvent::State(StateEvent::ThreadSelected {
+                id: 2,
+                frame: Some(frame),
+            })) => assert_eq!(frame.line, Some(8)),
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(
+            correlate(
+                DebuggerCommand::SelectThread(9),
+                "8^error,msg=\"Invalid thread id: 9\""
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_correlate_create_var_object() {
+        let cmd = || DebuggerCommand::CreateVarObject("n".into());
+        match correlate(
+            cmd(),
+            r#"5^done,name="var1",numchild="0",value="3",type="int",has_more="0""#,
+        ) {
+            Some(DebuggerEvent::State(StateEvent::WatchCreated {
+                expr,
+                result: Ok(watch),
+            })) => assert_eq!((expr.as_str(), watch.var.as_str()), ("n", "var1")),
+            other => panic!("unexpected {other:?}"),
+        }
+        match correlate(
+            cmd(),
+            r#"6^error,msg="No symbol \"n\" in current context.""#,
+        ) {
+            Some(DebuggerEvent::State(StateEvent::WatchCreated {
+                result: Err(msg), ..
+            })) => assert!(msg.starts_with("No symbol")),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_correlate_toggle_breakpoint() {
+        let disable = || DebuggerCommand::ToggleBreakpoint {
+            id: 2,
+            enable: false,
+        };
+        assert!(matches!(
+            correlate(disable(), "9^done"),
+            Some(DebuggerEvent::State(StateEvent::BreakpointToggled {
+                id: 2,
+                enabled: false
+            }))
+        ));
+        assert!(correlate(disable(), "9^error,msg=\"No breakpoint number 2.\"").is_none());
+    }
+
+    #[test]
+    fn test_correlate_delete_checkpoint() {
+        assert!(matches!(
+            correlate(DebuggerCommand::DeleteCheckpoint(3), "4^done"),
+            Some(DebuggerEvent::State(StateEvent::CheckpointDeleted {
+                id: 3
+            }))
+        ));
+        assert!(correlate(DebuggerCommand::DeleteCheckpoint(3), "4^error,msg=\"x\"").is_none());
+    }
+
+    #[test]
+    fn test_correlate_load_executable() {
+        let cmd = || DebuggerCommand::LoadExecutable("./app".into());
+        assert!(matches!(
+            correlate(cmd(), "6^done"),
+            Some(DebuggerEvent::State(StateEvent::ProgramLoaded { executable }))
+                if executable == "./app"
+        ));
+        assert!(correlate(cmd(), "6^error,msg=\"./app: No such file or directory.\"").is_none());
+    }
+
+    #[test]
+    fn test_correlate_load_core() {
+        let cmd = || DebuggerCommand::LoadCore("core.4242".into());
+        let line = "7^connected,frame={level=\"0\",addr=\"0x401136\",func=\"crash\",\
+                    file=\"main.c\",fullname=\"/src/main.c\",line=\"9\"}";
+        let Some(DebuggerEvent::State(StateEvent::ProgramPaused { pause })) =
+            correlate(cmd(), line)
+        else {
+            panic!("no pause for the core's frame");
+        };
+        assert_eq!(pause.frame.function, "crash");
+        assert_eq!(pause.frame.file.as_deref(), Some("/src/main.c"));
+        assert_eq!(pause.frame.line, Some(9));
+        assert_eq!(pause.stack.len(), 1);
+        assert!(
+            correlate(
+                cmd(),
+                "7^error,msg=\"core.4242: No such file or directory.\""
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_correlate_write_memory() {
+        let cmd = || DebuggerCommand::WriteMemory {
+            addr: 0x401000,
+            bytes: vec![0x90],
+        };
+        assert!(matches!(
+            correlate(cmd(), "8^done"),
+            Some(DebuggerEvent::Ui(UiEvent::MemoryWritten {
+                addr: 0x401000,
+                result: Ok(())
+            }))
+        ));
+        assert!(matches!(
+            correlate(cmd(), "8^error,msg=\"Cannot access memory at address 0x401000\""),
+            Some(DebuggerEvent::Ui(UiEvent::MemoryWritten { result: Err(msg), .. }))
+                if msg.starts_with("Cannot access memory")
+        ));
+    }
+
+    #[test]
+    fn test_correlate_connect_remote() {
+        let cmd = || DebuggerCommand::ConnectRemote("localhost:2345".into());
+        assert!(matches!(
+            correlate(cmd(), "3^connected,frame={level=\"0\",addr=\"0x7ffff7fe3290\"}"),
+            Some(DebuggerEvent::State(StateEvent::RemoteConnected { addr }))
+                if addr == "localhost:2345"
+        ));
+        assert!(matches!(
+            correlate(cmd(), "3^error,msg=\"localhost:2345: Connection refused.\""),
+            Some(DebuggerEvent::State(StateEvent::RemoteConnectFailed { msg, .. }))
+                if msg == "localhost:2345: Connection refused."
+        ));
+    }
+
+    #[test]
+    fn test_correlate_asm_source_lines() {
+        let cmd = || DebuggerCommand::RequestAsmSourceLines { addr: 0x401126 };
+        assert!(is_correlated(&cmd()));
+
+        // No line info: answered like mode 0, yields an empty table
+        let plain = r#"9^done,asm_insns=[{address="0x0000000000401126",inst="push   %rbp"}]"#;
+        match correlate(cmd(), plain) {
+            Some(DebuggerEvent::State(StateEvent::AsmSourceLinesUpdated { lines })) => {
+                assert!(lines.is_empty())
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(
+            correlate(
+                cmd(),
+                "9^error,msg=\"No function contains specified address.\""
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_correlate_search_symbols() {
+        let cmd = || DebuggerCommand::SearchSymbols {
+            regex: "main".into(),
+            max: 10,
+        };
+        assert!(is_correlated(&cmd()));
+
+        let line = r#"4^done,symbols={debug=[{filename="main.c",fullname="/src/main.c",symbols=[{line="5",name="main",type="int (void)",description="int main(void);"}]}]}"#;
+        match correlate(cmd(), line) {
+            Some(DebuggerEvent::Ui(UiEvent::SymbolsFound { regex, symbols })) => {
+                assert_eq!(regex, "main");
+                assert_eq!(symbols.len(), 1);
+                assert_eq!(symbols[0].line, Some(5));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(correlate(cmd(), "4^error,msg=\"Invalid regexp\"").is_none());
+    }
+
+    #[test]
+    fn test_pipeline_break_insert() {
+        use crate::gdb::backend::fake::FakeGdb;
+        use crate::state::DebuggerState;
+        use crate::ui::command::BreakpointSpec;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let fake = FakeGdb::default().reply(
+            "-break-insert",
+            &[r#"^done,bkpt={number="1",type="breakpoint",disp="keep",enabled="y",addr="0x401136",func="main",file="main.c",fullname="/src/main.c",line="12"}"#],
+        );
+        let sent = fake.sent.clone();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        thread::spawn(move || {
+            run_loop(
+                Box::new(fake),
+                Some("/src/app".into()),
+                true,
+                cmd_rx,
+                event_tx,
+            )
+        });
+        cmd_tx
+            .send(DebuggerCommand::InsertBreakpoint(BreakpointSpec::new(
+                "main.c:12",
+            )))
+            .unwrap();
+
+        let mut state = DebuggerState::new();
+        loop {
+            let event = event_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("no breakpoint event");
+            let DebuggerEvent::State(s) = event else {
+                continue;
+            };
+            let added = matches!(s, StateEvent::BreakpointAdded { .. });
+            state.apply(s);
+            if added {
+                break;
+            }
+        }
+
+        let bp = state.breakpoint_at("/src/main.c", 12).expect("breakpoint");
+        assert_eq!(bp.id, 1);
+        assert!(bp.enabled);
+        assert_eq!(state.persistent.executable.as_deref(), Some("/src/app"));
+
+        // mi-async first, prompts off, then the insert, with increasing tokens
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0], "1-gdb-set mi-async on");
+        assert_eq!(sent[1], "2-gdb-set pagination off");
+        assert_eq!(sent[2], "3-gdb-set confirm off");
+        assert!(sent[3].starts_with("4-break-insert"), "{}", sent[3]);
+    }
+
+    #[test]
+    fn test_mappings_from_console() {
+        use crate::gdb::backend::fake::FakeGdb;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let fake = FakeGdb::default().reply(
+            "info proc mappings",
+            &[
+                r#"~"process 4242\n""#,
+                r#"~"Mapped address spaces:\n\n""#,
+                r#"~"          Start Addr           End Addr       Size     Offset  Perms  objfile\n""#,
+                r#"~"            0x400000           0x401000     0x1000        0x0  r--p   /src/app\n""#,
+                r#"~"      0x7ffffffde000     0x7ffffffff000    0x21000        0x0  rw-p   [stack]\n""#,
+                "^done",
+            ],
+        );
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        thread::spawn(move || run_loop(Box::new(fake), None, true, cmd_rx, event_tx));
+        cmd_tx.send(DebuggerCommand::RequestMappings).unwrap();
+
+        let regions = loop {
+            let event = event_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("no mappings event");
+            if let DebuggerEvent::State(StateEvent::MappingsRead { regions }) = event {
+                break regions;
+            }
+        };
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].path, "/src/app");
+        assert_eq!(regions[1].size(), 0x21000);
+    }
+
+    #[test]
+    fn test_error_names_its_command() {
+        use crate::gdb::backend::fake::FakeGdb;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let fake = FakeGdb::default().reply(
+            "-data-evaluate-expression",
+            &[r#"^error,msg="No symbol \"nope\" in current context.""#],
+        );
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        thread::spawn(move || run_loop(Box::new(fake), None, true, cmd_rx, event_tx));
+        cmd_tx
+            .send(DebuggerCommand::Evaluate("nope".into()))
+            .unwrap();
+
+        let failed = loop {
+            let event = event_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("no error event");
+            if let DebuggerEvent::Ui(UiEvent::CommandFailed { command, msg }) = event {
+                break (command, msg);
+            }
+        };
+        assert_eq!(
+            failed,
+            (
+                r#"-data-evaluate-expression "nope""#.to_string(),
+                r#"No symbol "nope" in current context."#.to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_interleaved_captures() {
+        use crate::gdb::backend::fake::FakeGdb;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let fake = FakeGdb::default()
+            .reply("info frame", &[r#"~"Stack level 0\n""#, "^done"])
+            .reply("p 1", &[r#"~"$1 = 1\n""#, "^done"])
+            .reply("p 2", &[r#"~"$2 = 2\n""#, "^done"]);
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        // Queued before the loop starts: all three are in flight at once
+        cmd_tx
+            .send(DebuggerCommand::Raw("info frame".into()))
+            .unwrap();
+        cmd_tx
+            .send(DebuggerCommand::StopHook("p 1".into()))
+            .unwrap();
+        cmd_tx
+            .send(DebuggerCommand::StopHook("p 2".into()))
+            .unwrap();
+        thread::spawn(move || run_loop(Box::new(fake), None, true, cmd_rx, event_tx));
+
+        let mut outputs = vec![];
+        while outputs.len() < 2 {
+            let event = event_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("no stop hook output");
+            if let DebuggerEvent::Ui(UiEvent::StopHookOutput { command, output }) = event {
+                outputs.push((command, output));
+            }
+        }
+        assert_eq!(
+            outputs,
+            vec![
+                ("p 1".to_string(), "$1 = 1\n".to_string()),
+                ("p 2".to_string(), "$2 = 2\n".to_string()),
+            ]
+        );
+    }
+}
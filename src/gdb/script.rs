@@ -0,0 +1,182 @@
+use crate::state::{Breakpoint, BreakpointKind};
+use crate::ui::command::BreakpointSpec;
+
+// ─── Export ───────────────────────────────────────────────────────────────────
+
+/// Breakpoints as a GDB command script, loadable with `source file.gdb`.
+/// Options refer to `$bpnum` so the script doesn't depend on GDB's numbering.
+/// Only line breakpoints are exported: watch expressions only make sense in
+/// a frame, and the rest aren't `break` commands.
+pub fn export_breakpoints(breakpoints: &[Breakpoint]) -> String {
+    let mut out = String::from("# Breakpoints exported by GDB GUI\n");
+    if breakpoints.iter().any(|bp| bp.pending) {
+        out.push_str("set breakpoint pending on\n");
+    }
+    for bp in breakpoints
+        .iter()
+        .filter(|bp| bp.kind == BreakpointKind::Line)
+    {
+        if let Some(msg) = &bp.log_message {
+            out.push_str(&format!("# logpoint: {msg}\n"));
+        }
+        let cmd = if bp.temporary { "tbreak" } else { "break" };
+        out.push_str(&format!("{cmd} {}:{}\n", bp.file, bp.line));
+        if let Some(cond) = &bp.condition {
+            out.push_str(&format!("condition $bpnum {cond}\n"));
+        }
+        if bp.ignore_count > 0 {
+            out.push_str(&format!("ignore $bpnum {}\n", bp.ignore_count));
+        }
+        if !bp.enabled {
+            out.push_str("disable $bpnum\n");
+        }
+    }
+    out
+}
+
+// ─── Import ───────────────────────────────────────────────────────────────────
+
+/// Parses `break`/`tbreak`/`condition`/`ignore`/`disable` lines, plus
+/// `set breakpoint pending`. Returns the breakpoints found and the lines that
+/// weren't understood.
+pub fn parse_breakpoint_script(script: &str) -> (Vec<BreakpointSpec>, Vec<String>) {
+    let mut specs: Vec<BreakpointSpec> = vec![];
+    let mut unknown = vec![];
+    let mut pending = false;
+
+    for raw in script.lines() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (cmd, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        match cmd {
+            "set" if rest.starts_with("breakpoint pending ") => {
+                match rest["breakpoint pending ".len()..].trim() {
+                    "on" => pending = true,
+                    "off" | "auto" => pending = false,
+                    _ => unknown.push(raw.to_owned()),
+                }
+            }
+            "break" | "b" | "br" | "tbreak" | "tb" if !rest.is_empty() => {
+                let (location, condition) = match rest.split_once(" if ") {
+                    Some((loc, cond)) => (loc.trim(), Some(cond.trim().to_owned())),
+                    None => (rest, None),
+                };
+                let mut spec = BreakpointSpec::new(location);
+                spec.temporary = cmd.starts_with('t');
+                spec.condition = condition;
+                spec.pending = pending;
+                specs.push(spec);
+            }
+            "condition" | "ignore" | "disable" => {
+                let (target, arg) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let Some(spec) = target_spec(&mut specs, target) else {
+                    unknown.push(raw.to_owned());
+                    continue;
+                };
+                match cmd {
+                    "condition" if !arg.trim().is_empty() => {
+                        spec.condition = Some(arg.trim().to_owned());
+                    }
+                    "ignore" => match arg.trim().parse() {
+                        Ok(n) => spec.ignore_count = n,
+                        Err(_) => unknown.push(raw.to_owned()),
+                    },
+                    "disable" => spec.enabled = false,
+                    _ => unknown.push(raw.to_owned()),
+                }
+            }
+            _ => unknown.push(raw.to_owned()),
+        }
+    }
+
+    (specs, unknown)
+}
+
+/// `$bpnum` (or nothing) → last breakpoint; `N` → the N-th breakpoint of the script.
+fn target_spec<'a>(
+    specs: &'a mut [BreakpointSpec],
+    target: &str,
+) -> Option<&'a mut BreakpointSpec> {
+    match target {
+        "" | "$bpnum" => specs.last_mut(),
+        n => {
+            let idx: usize = n.parse().ok()?;
+            specs.get_mut(idx.checked_sub(1)?)
+        }
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bp(id: u32, file: &str, line: u32) -> Breakpoint {
+        Breakpoint {
+            id,
+            kind: BreakpointKind::Line,
+            file: file.into(),
+            line,
+            enabled: true,
+            temporary: false,
+            condition: None,
+            ignore_count: 0,
+            pending: false,
+            log_message: None,
+            original_location: None,
+            what: None,
+            commands: vec![],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut second = bp(2, "/src/util.c", 7);
+        second.temporary = true;
+        second.condition = Some("n > 10".into());
+        second.ignore_count = 2;
+        second.enabled = false;
+        let bps = vec![bp(1, "/src/main.c", 42), second];
+
+        let (specs, unknown) = parse_breakpoint_script(&export_breakpoints(&bps));
+        assert!(unknown.is_empty());
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0], BreakpointSpec::new("/src/main.c:42"));
+        assert_eq!(
+            specs[1],
+            BreakpointSpec {
+                location: "/src/util.c:7".into(),
+                temporary: true,
+                enabled: false,
+                condition: Some("n > 10".into()),
+                ignore_count: 2,
+                pending: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_handwritten_script() {
+        let script = "\
+# my .gdbinit
+set pagination off
+b main
+break parse.c:99 if tok == 0
+condition 1 argc > 1
+ignore 2 5
+";
+        let (specs, unknown) = parse_breakpoint_script(script);
+        assert_eq!(unknown, vec!["set pagination off".to_string()]);
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].location, "main");
+        assert_eq!(specs[0].condition.as_deref(), Some("argc > 1"));
+        assert_eq!(specs[1].location, "parse.c:99");
+        assert_eq!(specs[1].condition.as_deref(), Some("tok == 0"));
+        assert_eq!(specs[1].ignore_count, 5);
+    }
+}
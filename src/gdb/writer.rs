@@ -1,16 +1,51 @@
-use crate::ui::command::Command;
+use crate::ui::command::{Command, WatchKind};
 
 pub fn command_to_mi(cmd: &Command) -> String {
     match cmd {
         Command::Run => "-exec-run".into(),
+        Command::StartInstruction => "starti".into(),
         Command::Continue => "-exec-continue".into(),
         Command::Step => "-exec-step".into(),
         Command::Next => "-exec-next".into(),
         Command::Finish => "-exec-finish".into(),
+        Command::Advance(location) => format!("-exec-until {}", mi_arg(location)),
+        Command::StepInstruction => "-exec-step-instruction".into(),
+        Command::NextInstruction => "-exec-next-instruction".into(),
         Command::Interrupt => "-exec-interrupt".into(),
         Command::Restart => "-exec-run".into(),
+        // -exec-abort no está implementado en GDB; `kill` vale desde MI
+        Command::Kill => "kill".into(),
 
-        Command::AddBreakpoint { file, line } => format!("-break-insert {file}:{line}"),
+        Command::InsertBreakpoint(spec) => {
+            let mut mi = String::from("-break-insert");
+            if spec.pending {
+                mi.push_str(" -f");
+            }
+            if spec.temporary {
+                mi.push_str(" -t");
+            }
+            if !spec.enabled {
+                mi.push_str(" -d");
+            }
+            if let Some(cond) = &spec.condition {
+                mi.push_str(&format!(" -c {}", mi_quote(cond)));
+            }
+            if spec.ignore_count > 0 {
+                mi.push_str(&format!(" -i {}", spec.ignore_count));
+            }
+            mi.push_str(&format!(" {}", mi_arg(&spec.location)));
+            mi
+        }
+        // Sin equivalente MI; GDB acepta el comando de consola tal cual
+        Command::AddRegexBreakpoints(regex) => format!("rbreak {regex}"),
+        Command::InsertWatchpoint { expr, kind } => {
+            let flag = match kind {
+                WatchKind::Write => "",
+                WatchKind::Read => "-r ",
+                WatchKind::Access => "-a ",
+            };
+            format!("-break-watch {flag}{}", mi_arg(expr))
+        }
         Command::RemoveBreakpoint(id) => format!("-break-delete {id}"),
         Command::ToggleBreakpoint { id, enable } => {
             if *enable {
@@ -19,21 +54,808 @@ pub fn command_to_mi(cmd: &Command) -> String {
                 format!("-break-disable {id}")
             }
         }
+        Command::SetBreakpointCommands { id, commands } => {
+            let mut mi = format!("-break-commands {id}");
+            for cmd in commands {
+                mi.push(' ');
+                mi.push_str(&mi_quote(cmd));
+            }
+            mi
+        }
+        Command::SetBreakpointCondition { id, condition } => match condition {
+            Some(cond) => format!("-break-condition {id} {cond}"),
+            None => format!("-break-condition {id}"),
+        },
 
-        Command::LoadExecutable(path) => format!("-file-exec-and-symbols {path}"),
+        Command::LoadExecutable(path) => format!("-file-exec-and-symbols {}", mi_arg(path)),
+        Command::SelectInferior(id) => format!("inferior {id}"),
+        Command::SetFollowForkMode(mode) => format!("-gdb-set follow-fork-mode {}", mode.as_str()),
+        Command::SetDetachOnFork(on) => format!("-gdb-set detach-on-fork {}", on_off(*on)),
+        Command::SetMiAsync(on) => format!("-gdb-set mi-async {}", on_off(*on)),
+        Command::SetPagination(on) => format!("-gdb-set pagination {}", on_off(*on)),
+        Command::SetArguments(args) => format!("-exec-arguments {args}").trim_end().to_owned(),
+        Command::SetEnvironment { name, value } => {
+            format!("-gdb-set environment {name}={value}")
+        }
+        Command::ChangeDirectory(path) => format!("-environment-cd {}", mi_quote(path)),
+        Command::SetConfirm(on) => format!("-gdb-set confirm {}", on_off(*on)),
+        // Sin equivalente MI: van como comandos de consola
+        Command::UnsetEnvironment(name) => format!("unset environment {name}"),
+        Command::CreateCheckpoint => "checkpoint".into(),
+        Command::RestartCheckpoint(n) => format!("restart {n}"),
+        Command::DeleteCheckpoint(n) => format!("delete checkpoint {n}"),
 
+        Command::CreateVarObject(expr) => format!("-var-create - * {}", mi_quote(expr)),
+        Command::UpdateVarObjects => "-var-update --all-values *".into(),
+        Command::DeleteVarObject(var) => format!("-var-delete {var}"),
+        Command::SelectFrame(level) => format!("-stack-select-frame {level}"),
+        Command::SelectThread(id) => format!("-thread-select {id}"),
         Command::RequestLocals => "-stack-list-variables --all-values".into(),
 
-        Command::RequestStack => "-stack-list-frames".into(),
+        Command::RequestStack { low, high } => match (low, high) {
+            (None, None) => "-stack-list-frames".into(),
+            (low, high) => format!(
+                "-stack-list-frames {} {}",
+                low.unwrap_or(0),
+                high.unwrap_or(i32::MAX as u32)
+            ),
+        },
+
+        Command::RequestFrameArguments { low, high } => match (low, high) {
+            (None, None) => "-stack-list-arguments 1".into(),
+            (low, high) => format!(
+                "-stack-list-arguments 1 {} {}",
+                low.unwrap_or(0),
+                high.unwrap_or(i32::MAX as u32)
+            ),
+        },
 
         Command::RequestRegisterNames => "-data-list-register-names".into(),
+        Command::RequestThreads => "-thread-info".into(),
+        Command::SetLanguageAuto => "-gdb-set language auto".into(),
+        // Sin forma MI: la respuesta llega por el stream ~
+        Command::RequestLanguage => "show language".into(),
+        Command::RequestMappings => "info proc mappings".into(),
+        Command::EnablePrettyPrinting => "-enable-pretty-printing".into(),
 
         Command::RequestRegisters => "-data-list-register-values x".into(),
 
-        Command::RequestDisasm => "-data-disassemble -s $pc -e \"$pc + 64\" -- 0".into(),
+        Command::RequestDisasm => "-data-disassemble -s $pc -e \"$pc + 64\" -- 2".into(),
+
+        Command::SearchSymbols { regex, max } => format!(
+            "-symbol-info-functions --name {} --max-results {max}",
+            mi_arg(regex)
+        ),
+
+        Command::DisassembleAt { start } => format!(
+            "-data-disassemble -s {} -e {} -- 2",
+            mi_arg(start),
+            mi_quote(&format!("({start})+128"))
+        ),
+
+        Command::DisassembleRange { start, end } => {
+            format!("-data-disassemble -s 0x{start:x} -e 0x{end:x} -- 2")
+        }
+
+        Command::RequestAsmSourceLines { addr } => format!("-data-disassemble -a 0x{addr:x} -- 1"),
 
-        Command::Evaluate(expr) => format!("-data-evaluate-expression {expr}"),
+        Command::Evaluate(expr) => format!("-data-evaluate-expression {}", mi_quote(expr)),
+
+        Command::SubstitutePath { from, to } => {
+            format!("-gdb-set substitute-path {} {}", mi_arg(from), mi_arg(to))
+        }
+        // `unset` no tiene forma MI
+        Command::RemoveSubstitutePath(from) => {
+            format!("unset substitute-path {}", mi_arg(from))
+        }
+        Command::ConnectRemote(addr) => format!("-target-select remote {}", mi_arg(addr)),
+        Command::Attach(pid) => format!("-target-attach {pid}"),
+        Command::LoadCore(path) => format!("-target-select core {}", mi_arg(path)),
+
+        Command::SetRegister { name, value } => format!("-gdb-set ${name}={value}"),
+        Command::ReadMemory { addr, len } => {
+            format!("-data-read-memory-bytes {} {len}", mi_arg(addr))
+        }
+        Command::WriteMemory { addr, bytes } => {
+            format!(
+                "-data-write-memory-bytes 0x{addr:x} \"{}\"",
+                hex_encode(bytes)
+            )
+        }
+
+        Command::RequestSourceDirs => "-environment-directory".into(),
+        Command::AddSourceDir(path) => format!("-environment-directory {}", mi_quote(path)),
+
+        Command::Raw(s) | Command::StopHook(s) => s.clone(),
+    }
+}
+
+/// The CLI command a user would type for `cmd`, for the command log. Multi-line
+/// for breakpoints with options (they refer to `$bpnum`, like exported scripts).
+/// None for the data requests the UI issues on its own.
+pub fn command_to_console(cmd: &Command) -> Option<String> {
+    let console = match cmd {
+        Command::Run | Command::Restart => "run".into(),
+        Command::StartInstruction => "starti".into(),
+        Command::Continue => "continue".into(),
+        Command::Step => "step".into(),
+        Command::Next => "next".into(),
+        Command::Finish => "finish".into(),
+        Command::Advance(location) => format!("until {location}"),
+        Command::StepInstruction => "stepi".into(),
+        Command::NextInstruction => "nexti".into(),
+        Command::Interrupt => "interrupt".into(),
+        Command::Kill => "kill".into(),
+
+        Command::InsertBreakpoint(spec) => {
+            let mut out = String::new();
+            if spec.pending {
+                out.push_str("set breakpoint pending on\n");
+            }
+            out.push_str(if spec.temporary { "tbreak" } else { "break" });
+            out.push_str(&format!(" {}", spec.location));
+            if let Some(cond) = &spec.condition {
+                out.push_str(&format!(" if {cond}"));
+            }
+            if spec.ignore_count > 0 {
+                out.push_str(&format!("\nignore $bpnum {}", spec.ignore_count));
+            }
+            if !spec.enabled {
+                out.push_str("\ndisable $bpnum");
+            }
+            out
+        }
+        Command::AddRegexBreakpoints(regex) => format!("rbreak {regex}"),
+        Command::InsertWatchpoint { expr, kind } => match kind {
+            WatchKind::Write => format!("watch {expr}"),
+            WatchKind::Read => format!("rwatch {expr}"),
+            WatchKind::Access => format!("awatch {expr}"),
+        },
+        Command::RemoveBreakpoint(id) => format!("delete {id}"),
+        Command::ToggleBreakpoint { id, enable } => {
+            if *enable {
+                format!("enable {id}")
+            } else {
+                format!("disable {id}")
+            }
+        }
+        Command::SetBreakpointCommands { id, commands } => {
+            let mut console = format!("commands {id}\n");
+            for cmd in commands {
+                console.push_str(&format!("  {cmd}\n"));
+            }
+            console.push_str("end");
+            console
+        }
+        Command::SetBreakpointCondition { id, condition } => match condition {
+            Some(cond) => format!("condition {id} {cond}"),
+            None => format!("condition {id}"),
+        },
+
+        // The CLI splits its argument like a shell would
+        Command::LoadExecutable(path) => format!("file {}", mi_arg(path)),
+        Command::SelectInferior(id) => format!("inferior {id}"),
+        Command::SetFollowForkMode(mode) => format!("set follow-fork-mode {}", mode.as_str()),
+        Command::SetDetachOnFork(on) => format!("set detach-on-fork {}", on_off(*on)),
+        Command::SetMiAsync(on) => format!("set mi-async {}", on_off(*on)),
+        Command::SetPagination(on) => format!("set pagination {}", on_off(*on)),
+        Command::SetArguments(args) => format!("set args {args}").trim_end().to_owned(),
+        Command::SetEnvironment { name, value } => format!("set environment {name}={value}"),
+        Command::UnsetEnvironment(name) => format!("unset environment {name}"),
+        Command::ChangeDirectory(path) => format!("cd {path}"),
+        Command::SetConfirm(on) => format!("set confirm {}", on_off(*on)),
+        Command::CreateCheckpoint => "checkpoint".into(),
+        Command::RestartCheckpoint(n) => format!("restart {n}"),
+        Command::DeleteCheckpoint(n) => format!("delete checkpoint {n}"),
+
+        Command::SelectFrame(level) => format!("frame {level}"),
+        Command::SelectThread(id) => format!("thread {id}"),
+
+        Command::RequestLocals
+        | Command::RequestStack { .. }
+        | Command::RequestFrameArguments { .. }
+        | Command::RequestRegisterNames
+        | Command::RequestThreads
+        | Command::SetLanguageAuto
+        | Command::RequestLanguage
+        | Command::EnablePrettyPrinting
+        | Command::RequestRegisters
+        | Command::RequestDisasm
+        | Command::RequestAsmSourceLines { .. }
+        | Command::RequestSourceDirs
+        | Command::ReadMemory { .. }
+        | Command::RequestMappings
+        | Command::CreateVarObject(_)
+        | Command::UpdateVarObjects
+        | Command::DeleteVarObject(_)
+        | Command::StopHook(_) => return None,
+
+        Command::Evaluate(expr) => format!("print {expr}"),
+        Command::SetRegister { name, value } => format!("set var ${name} = {value}"),
+        Command::WriteMemory { addr, bytes } => {
+            let values: Vec<_> = bytes.iter().map(|b| format!("0x{b:02x}")).collect();
+            format!(
+                "set {{unsigned char[{}]}} 0x{addr:x} = {{{}}}",
+                bytes.len(),
+                values.join(", ")
+            )
+        }
+        Command::AddSourceDir(path) => format!("directory {path}"),
+        Command::SubstitutePath { from, to } => format!("set substitute-path {from} {to}"),
+        Command::RemoveSubstitutePath(from) => format!("unset substitute-path {from}"),
+        Command::ConnectRemote(addr) => format!("target remote {addr}"),
+        Command::Attach(pid) => format!("attach {pid}"),
+        Command::LoadCore(path) => format!("core-file {}", mi_arg(path)),
+        Command::SearchSymbols { regex, .. } => format!("info functions {regex}"),
+        Command::DisassembleAt { start } => format!("disassemble {start},+128"),
+        Command::DisassembleRange { start, end } => format!("disassemble 0x{start:x},0x{end:x}"),
 
         Command::Raw(s) => s.clone(),
+    };
+    Some(console)
+}
+
+/// `[0xde, 0xad]` → `dead`, the form `-data-write-memory-bytes` takes.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on { "on" } else { "off" }
+}
+
+/// `s` as-is when it's a single MI token, quoted otherwise (`$sp + 16`).
+fn mi_arg(s: &str) -> String {
+    if s.is_empty() || s.contains(|c: char| c.is_whitespace() || c == '"') {
+        mi_quote(s)
+    } else {
+        s.to_owned()
+    }
+}
+
+/// Wraps an argument in double quotes, escaping `\` and `"`, so MI reads it as
+/// a single token.
+pub fn mi_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::command::{BreakpointSpec, ForkMode};
+
+    #[test]
+    fn test_start_instruction() {
+        assert_eq!(command_to_mi(&Command::StartInstruction), "starti");
+        assert_eq!(
+            command_to_console(&Command::StartInstruction).as_deref(),
+            Some("starti")
+        );
+    }
+
+    #[test]
+    fn test_kill() {
+        assert_eq!(command_to_mi(&Command::Kill), "kill");
+    }
+
+    #[test]
+    fn test_view_requests() {
+        assert_eq!(
+            command_to_mi(&Command::RequestRegisterNames),
+            "-data-list-register-names"
+        );
+        assert_eq!(
+            command_to_mi(&Command::RequestRegisters),
+            "-data-list-register-values x"
+        );
+        // Mode 2: raw opcodes too, for the disassembly export
+        assert_eq!(
+            command_to_mi(&Command::RequestDisasm),
+            "-data-disassemble -s $pc -e \"$pc + 64\" -- 2"
+        );
+        assert_eq!(
+            command_to_mi(&Command::SetLanguageAuto),
+            "-gdb-set language auto"
+        );
+        assert_eq!(command_to_mi(&Command::RequestLanguage), "show language");
+        // The UI issues these itself; they stay out of the command log
+        assert_eq!(command_to_console(&Command::RequestRegisters), None);
+        assert_eq!(command_to_console(&Command::RequestDisasm), None);
+        assert_eq!(command_to_console(&Command::SetLanguageAuto), None);
+    }
+
+    #[test]
+    fn test_request_stack() {
+        let all = Command::RequestStack {
+            low: None,
+            high: None,
+        };
+        assert_eq!(command_to_mi(&all), "-stack-list-frames");
+        let page = Command::RequestStack {
+            low: Some(32),
+            high: Some(63),
+        };
+        assert_eq!(command_to_mi(&page), "-stack-list-frames 32 63");
+    }
+
+    #[test]
+    fn test_request_frame_arguments() {
+        let page = Command::RequestFrameArguments {
+            low: Some(0),
+            high: Some(31),
+        };
+        assert_eq!(command_to_mi(&page), "-stack-list-arguments 1 0 31");
+    }
+
+    #[test]
+    fn test_fork_settings() {
+        assert_eq!(
+            command_to_mi(&Command::SetFollowForkMode(ForkMode::Child)),
+            "-gdb-set follow-fork-mode child"
+        );
+        assert_eq!(
+            command_to_mi(&Command::SetFollowForkMode(ForkMode::Parent)),
+            "-gdb-set follow-fork-mode parent"
+        );
+        assert_eq!(
+            command_to_mi(&Command::SetDetachOnFork(false)),
+            "-gdb-set detach-on-fork off"
+        );
+        assert_eq!(
+            command_to_mi(&Command::SetDetachOnFork(true)),
+            "-gdb-set detach-on-fork on"
+        );
+    }
+
+    #[test]
+    fn test_request_asm_source_lines() {
+        let cmd = Command::RequestAsmSourceLines { addr: 0x401136 };
+        assert_eq!(command_to_mi(&cmd), "-data-disassemble -a 0x401136 -- 1");
+        assert_eq!(command_to_console(&cmd), None);
+    }
+
+    #[test]
+    fn test_substitute_path() {
+        let cmd = Command::SubstitutePath {
+            from: "/builds/project".into(),
+            to: "/home/me/project".into(),
+        };
+        assert_eq!(
+            command_to_mi(&cmd),
+            "-gdb-set substitute-path /builds/project /home/me/project"
+        );
+        assert_eq!(
+            command_to_console(&cmd).as_deref(),
+            Some("set substitute-path /builds/project /home/me/project")
+        );
+        let spaced = Command::SubstitutePath {
+            from: "/ci/my src".into(),
+            to: "/src".into(),
+        };
+        assert_eq!(
+            command_to_mi(&spaced),
+            "-gdb-set substitute-path \"/ci/my src\" /src"
+        );
+        assert_eq!(
+            command_to_mi(&Command::RemoveSubstitutePath("/builds/project".into())),
+            "unset substitute-path /builds/project"
+        );
+    }
+
+    #[test]
+    fn test_connect_remote() {
+        let cmd = Command::ConnectRemote("192.168.1.20:2345".into());
+        assert_eq!(
+            command_to_mi(&cmd),
+            "-target-select remote 192.168.1.20:2345"
+        );
+        assert_eq!(
+            command_to_console(&cmd).as_deref(),
+            Some("target remote 192.168.1.20:2345")
+        );
+    }
+
+    #[test]
+    fn test_attach_and_core() {
+        let attach = Command::Attach(4242);
+        assert_eq!(command_to_mi(&attach), "-target-attach 4242");
+        assert_eq!(command_to_console(&attach).as_deref(), Some("attach 4242"));
+        let core = Command::LoadCore("/tmp/my cores/core.4242".into());
+        assert_eq!(
+            command_to_mi(&core),
+            "-target-select core \"/tmp/my cores/core.4242\""
+        );
+        assert_eq!(
+            command_to_console(&core).as_deref(),
+            Some("core-file \"/tmp/my cores/core.4242\"")
+        );
+    }
+
+    #[test]
+    fn test_select_frame() {
+        assert_eq!(
+            command_to_mi(&Command::SelectFrame(3)),
+            "-stack-select-frame 3"
+        );
+        assert_eq!(
+            command_to_console(&Command::SelectFrame(3)).as_deref(),
+            Some("frame 3")
+        );
+    }
+
+    #[test]
+    fn test_var_objects() {
+        let create = Command::CreateVarObject("p->len + 1".into());
+        assert_eq!(command_to_mi(&create), "-var-create - * \"p->len + 1\"");
+        assert_eq!(command_to_console(&create), None);
+        assert_eq!(
+            command_to_mi(&Command::UpdateVarObjects),
+            "-var-update --all-values *"
+        );
+        assert_eq!(
+            command_to_mi(&Command::DeleteVarObject("var3".into())),
+            "-var-delete var3"
+        );
+    }
+
+    #[test]
+    fn test_select_thread() {
+        assert_eq!(command_to_mi(&Command::SelectThread(2)), "-thread-select 2");
+        assert_eq!(
+            command_to_console(&Command::SelectThread(2)).as_deref(),
+            Some("thread 2")
+        );
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        assert_eq!(command_to_mi(&Command::CreateCheckpoint), "checkpoint");
+        assert_eq!(command_to_mi(&Command::RestartCheckpoint(2)), "restart 2");
+        assert_eq!(
+            command_to_mi(&Command::DeleteCheckpoint(2)),
+            "delete checkpoint 2"
+        );
+        assert_eq!(
+            command_to_console(&Command::RestartCheckpoint(1)).as_deref(),
+            Some("restart 1")
+        );
+    }
+
+    #[test]
+    fn test_mi_async() {
+        assert_eq!(
+            command_to_mi(&Command::SetMiAsync(true)),
+            "-gdb-set mi-async on"
+        );
+        assert_eq!(
+            command_to_mi(&Command::SetMiAsync(false)),
+            "-gdb-set mi-async off"
+        );
+        assert_eq!(
+            command_to_console(&Command::SetMiAsync(true)).as_deref(),
+            Some("set mi-async on")
+        );
+    }
+
+    #[test]
+    fn test_set_register() {
+        let cmd = Command::SetRegister {
+            name: "rax".into(),
+            value: "0x2a".into(),
+        };
+        assert_eq!(command_to_mi(&cmd), "-gdb-set $rax=0x2a");
+        // Set PC in the Data tab
+        let cmd = Command::SetRegister {
+            name: "pc".into(),
+            value: "0x401130".into(),
+        };
+        assert_eq!(command_to_mi(&cmd), "-gdb-set $pc=0x401130");
+        assert_eq!(
+            command_to_console(&cmd).as_deref(),
+            Some("set var $pc = 0x401130")
+        );
+    }
+
+    #[test]
+    fn test_advance() {
+        let advance = Command::Advance("/src/main.c:42".into());
+        assert_eq!(command_to_mi(&advance), "-exec-until /src/main.c:42");
+        assert_eq!(
+            command_to_console(&advance).as_deref(),
+            Some("until /src/main.c:42")
+        );
+    }
+
+    #[test]
+    fn test_paths_with_spaces() {
+        let exe = Command::LoadExecutable("/home/user/my project/a.out".into());
+        assert_eq!(
+            command_to_mi(&exe),
+            "-file-exec-and-symbols \"/home/user/my project/a.out\""
+        );
+        assert_eq!(
+            command_to_console(&exe).as_deref(),
+            Some("file \"/home/user/my project/a.out\"")
+        );
+        assert_eq!(
+            command_to_mi(&Command::LoadExecutable("/bin/a.out".into())),
+            "-file-exec-and-symbols /bin/a.out"
+        );
+        let spec = Command::InsertBreakpoint(BreakpointSpec::new("/home/user/my project/main.c:7"));
+        assert_eq!(
+            command_to_mi(&spec),
+            "-break-insert \"/home/user/my project/main.c:7\""
+        );
+        let advance = Command::Advance("/home/user/my project/main.c:9".into());
+        assert_eq!(
+            command_to_mi(&advance),
+            "-exec-until \"/home/user/my project/main.c:9\""
+        );
+        assert_eq!(
+            command_to_mi(&Command::ChangeDirectory("C:\\my \"dir\"".into())),
+            "-environment-cd \"C:\\\\my \\\"dir\\\"\""
+        );
+    }
+
+    #[test]
+    fn test_launch_commands() {
+        assert_eq!(
+            command_to_mi(&Command::SetArguments("-v < in.txt".into())),
+            "-exec-arguments -v < in.txt"
+        );
+        assert_eq!(
+            command_to_mi(&Command::SetArguments(String::new())),
+            "-exec-arguments"
+        );
+        let env = Command::SetEnvironment {
+            name: "RUST_LOG".into(),
+            value: "debug".into(),
+        };
+        assert_eq!(command_to_mi(&env), "-gdb-set environment RUST_LOG=debug");
+        assert_eq!(
+            command_to_mi(&Command::UnsetEnvironment("RUST_LOG".into())),
+            "unset environment RUST_LOG"
+        );
+        assert_eq!(
+            command_to_mi(&Command::ChangeDirectory("/tmp/my run".into())),
+            "-environment-cd \"/tmp/my run\""
+        );
+    }
+
+    #[test]
+    fn test_hex_encode() {
+        assert_eq!(hex_encode(&[0xde, 0xad, 0x00, 0x0f]), "dead000f");
+        assert_eq!(hex_encode(&[]), "");
+    }
+
+    #[test]
+    fn test_memory_commands() {
+        let write = Command::WriteMemory {
+            addr: 0x7ffd_1000,
+            bytes: vec![0x41, 0x0a],
+        };
+        assert_eq!(
+            command_to_mi(&write),
+            "-data-write-memory-bytes 0x7ffd1000 \"410a\""
+        );
+        assert_eq!(
+            command_to_console(&write).as_deref(),
+            Some("set {unsigned char[2]} 0x7ffd1000 = {0x41, 0x0a}")
+        );
+
+        let read = |addr: &str| Command::ReadMemory {
+            addr: addr.into(),
+            len: 64,
+        };
+        assert_eq!(
+            command_to_mi(&read("&buf")),
+            "-data-read-memory-bytes &buf 64"
+        );
+        assert_eq!(
+            command_to_mi(&read("$sp + 8")),
+            "-data-read-memory-bytes \"$sp + 8\" 64"
+        );
+    }
+
+    #[test]
+    fn test_regex_breakpoints() {
+        assert_eq!(
+            command_to_mi(&Command::AddRegexBreakpoints("^parse_".into())),
+            "rbreak ^parse_"
+        );
+    }
+
+    #[test]
+    fn test_insert_watchpoint() {
+        let watch = |expr: &str, kind| {
+            command_to_mi(&Command::InsertWatchpoint {
+                expr: expr.into(),
+                kind,
+            })
+        };
+        assert_eq!(watch("counter", WatchKind::Write), "-break-watch counter");
+        assert_eq!(
+            watch("this->len", WatchKind::Read),
+            "-break-watch -r this->len"
+        );
+        assert_eq!(
+            watch("p->buf[3]", WatchKind::Access),
+            "-break-watch -a p->buf[3]"
+        );
+    }
+
+    #[test]
+    fn test_search_symbols() {
+        let cmd = Command::SearchSymbols {
+            regex: "^parse_".into(),
+            max: 501,
+        };
+        assert_eq!(
+            command_to_mi(&cmd),
+            "-symbol-info-functions --name ^parse_ --max-results 501"
+        );
+        assert_eq!(
+            command_to_console(&cmd).as_deref(),
+            Some("info functions ^parse_")
+        );
+    }
+
+    #[test]
+    fn test_disassemble_at() {
+        let at = |s: &str| command_to_mi(&Command::DisassembleAt { start: s.into() });
+        assert_eq!(
+            at("main"),
+            "-data-disassemble -s main -e \"(main)+128\" -- 2"
+        );
+        assert_eq!(
+            at("0x401000"),
+            "-data-disassemble -s 0x401000 -e \"(0x401000)+128\" -- 2"
+        );
+        assert_eq!(
+            at("$sp + 16"),
+            "-data-disassemble -s \"$sp + 16\" -e \"($sp + 16)+128\" -- 2"
+        );
+        let range = Command::DisassembleRange {
+            start: 0x401000,
+            end: 0x401040,
+        };
+        assert_eq!(
+            command_to_mi(&range),
+            "-data-disassemble -s 0x401000 -e 0x401040 -- 2"
+        );
+        assert_eq!(
+            command_to_console(&range).as_deref(),
+            Some("disassemble 0x401000,0x401040")
+        );
+    }
+
+    #[test]
+    fn test_source_dirs() {
+        assert_eq!(
+            command_to_mi(&Command::RequestSourceDirs),
+            "-environment-directory"
+        );
+        assert_eq!(
+            command_to_mi(&Command::AddSourceDir("/home/me/my src".into())),
+            "-environment-directory \"/home/me/my src\""
+        );
+    }
+
+    #[test]
+    fn test_insert_breakpoint() {
+        let mut spec = BreakpointSpec::new("main.c:42");
+        assert_eq!(
+            command_to_mi(&Command::InsertBreakpoint(spec.clone())),
+            "-break-insert main.c:42"
+        );
+
+        spec.temporary = true;
+        spec.enabled = false;
+        spec.condition = Some("s == \"x\"".into());
+        spec.ignore_count = 3;
+        assert_eq!(
+            command_to_mi(&Command::InsertBreakpoint(spec)),
+            "-break-insert -t -d -c \"s == \\\"x\\\"\" -i 3 main.c:42"
+        );
+    }
+
+    #[test]
+    fn test_breakpoint_commands() {
+        let cmd = Command::SetBreakpointCommands {
+            id: 3,
+            commands: vec!["silent".into(), "print \"hit\"".into(), "continue".into()],
+        };
+        assert_eq!(
+            command_to_mi(&cmd),
+            r#"-break-commands 3 "silent" "print \"hit\"" "continue""#
+        );
+        assert_eq!(
+            command_to_console(&cmd).as_deref(),
+            Some("commands 3\n  silent\n  print \"hit\"\n  continue\nend")
+        );
+        let clear = Command::SetBreakpointCommands {
+            id: 3,
+            commands: vec![],
+        };
+        assert_eq!(command_to_mi(&clear), "-break-commands 3");
+    }
+
+    #[test]
+    fn test_breakpoint_condition() {
+        let cmd = Command::SetBreakpointCondition {
+            id: 2,
+            condition: Some("len > 10 && buf != 0".into()),
+        };
+        assert_eq!(
+            command_to_mi(&cmd),
+            "-break-condition 2 len > 10 && buf != 0"
+        );
+        assert_eq!(
+            command_to_console(&cmd).as_deref(),
+            Some("condition 2 len > 10 && buf != 0")
+        );
+        let clear = Command::SetBreakpointCondition {
+            id: 2,
+            condition: None,
+        };
+        assert_eq!(command_to_mi(&clear), "-break-condition 2");
+        assert_eq!(command_to_console(&clear).as_deref(), Some("condition 2"));
+    }
+
+    #[test]
+    fn test_command_to_console() {
+        assert_eq!(command_to_console(&Command::Step).as_deref(), Some("step"));
+        assert_eq!(
+            command_to_console(&Command::ToggleBreakpoint {
+                id: 3,
+                enable: false
+            })
+            .as_deref(),
+            Some("disable 3")
+        );
+        assert_eq!(
+            command_to_console(&Command::SetRegister {
+                name: "rax".into(),
+                value: "1".into()
+            })
+            .as_deref(),
+            Some("set var $rax = 1")
+        );
+        assert_eq!(command_to_console(&Command::RequestLocals), None);
+    }
+
+    #[test]
+    fn test_console_breakpoint_reads_back() {
+        let mut spec = BreakpointSpec::new("util.c:7");
+        spec.temporary = true;
+        spec.enabled = false;
+        spec.condition = Some("n > 10".into());
+        spec.ignore_count = 2;
+        spec.pending = true;
+
+        let console = command_to_console(&Command::InsertBreakpoint(spec.clone())).unwrap();
+        let (specs, unknown) = crate::gdb::parse_breakpoint_script(&console);
+        assert!(unknown.is_empty());
+        assert_eq!(specs, vec![spec]);
+    }
+
+    #[test]
+    fn test_insert_pending_breakpoint() {
+        let mut spec = BreakpointSpec::new("libfoo.c:12");
+        spec.pending = true;
+        assert_eq!(
+            command_to_mi(&Command::InsertBreakpoint(spec)),
+            "-break-insert -f libfoo.c:12"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_quotes_expression() {
+        assert_eq!(
+            command_to_mi(&Command::Evaluate("a + 1".into())),
+            "-data-evaluate-expression \"a + 1\""
+        );
     }
 }
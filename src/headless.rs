@@ -0,0 +1,460 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::gdb;
+use crate::state::{
+    BreakpointKind, DebuggerEvent, DebuggerState, ProgramState, StateEvent, StopReason, UiEvent,
+};
+use crate::ui::command::{BreakpointSpec, Command};
+use crate::ui::settings::Settings;
+
+// Quiet time after which a non-execution command is considered done
+const SETTLE: Duration = Duration::from_millis(300);
+// Longest wait for run/continue/step to stop
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ─── Entry point ──────────────────────────────────────────────────────────────
+
+/// `gdb_gui --headless [EXECUTABLE] [SCRIPT]`: runs the commands in SCRIPT
+/// (stdin if missing or `-`) and prints each state transition to stdout.
+pub fn main(executable: Option<String>, script: Option<String>) -> io::Result<()> {
+    let (cmd_tx, cmd_rx) = mpsc::channel::<Command>();
+    let (event_tx, event_rx) = mpsc::channel::<DebuggerEvent>();
+
+    let settings = Settings::default();
+    let mi_async = settings.mi_async;
+    thread::spawn(move || {
+        gdb::run_gdb(executable, mi_async, cmd_rx, event_tx);
+    });
+    for cmd in settings.gdb_commands() {
+        let _ = cmd_tx.send(cmd);
+    }
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    match script.as_deref() {
+        None | Some("-") => run(io::stdin().lock(), &cmd_tx, event_rx, &mut out),
+        Some(path) => run(
+            BufReader::new(File::open(path)?),
+            &cmd_tx,
+            event_rx,
+            &mut out,
+        ),
+    }
+}
+
+/// Sends each script line and waits for its effect before the next one:
+/// execution commands until the program stops, the rest until GDB goes quiet.
+pub fn run(
+    script: impl BufRead,
+    cmd_tx: &Sender<Command>,
+    event_rx: Receiver<DebuggerEvent>,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut session = Session {
+        state: DebuggerState::new(),
+        event_rx,
+        out,
+    };
+    session.pump(false)?;
+
+    let mut pending = false;
+    for line in script.lines() {
+        let line = line?;
+        if let Some(on) = breakpoint_pending_setting(&line) {
+            pending = on;
+        }
+        let Some(cmd) = parse_command(&line, pending) else {
+            continue;
+        };
+        writeln!(session.out, "> {}", line.trim())?;
+        let resumes = is_execution(&cmd);
+        if cmd_tx.send(cmd).is_err() {
+            break;
+        }
+        session.pump(resumes)?;
+    }
+    Ok(())
+}
+
+// ─── Script ───────────────────────────────────────────────────────────────────
+
+/// One script line in GDB's CLI syntax. Execution, breakpoint and print
+/// commands are recognized so the driver knows what to wait for; anything
+/// else goes to GDB as is. None for blank lines and `#` comments. `pending`
+/// makes breakpoints pending when their location isn't found yet.
+pub fn parse_command(line: &str, pending: bool) -> Option<Command> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (word, arg) = line
+        .split_once(char::is_whitespace)
+        .map(|(w, a)| (w, a.trim()))
+        .unwrap_or((line, ""));
+
+    let cmd = match (word, arg) {
+        ("run" | "r", "") => Command::Run,
+        ("starti", "") => Command::StartInstruction,
+        ("continue" | "c", "") => Command::Continue,
+        ("step" | "s", "") => Command::Step,
+        ("next" | "n", "") => Command::Next,
+        ("finish", "") => Command::Finish,
+        ("until" | "u" | "advance", loc) if !loc.is_empty() => Command::Advance(loc.to_owned()),
+        ("stepi" | "si", "") => Command::StepInstruction,
+        ("nexti" | "ni", "") => Command::NextInstruction,
+        ("kill", "") => Command::Kill,
+        ("backtrace" | "bt" | "where", "") => Command::RequestStack {
+            low: None,
+            high: None,
+        },
+        ("break" | "b", loc) if !loc.is_empty() => BreakpointSpec::new(loc).insert(pending),
+        ("tbreak", loc) if !loc.is_empty() => BreakpointSpec {
+            temporary: true,
+            ..BreakpointSpec::new(loc)
+        }
+        .insert(pending),
+        ("print" | "p", expr) if !expr.is_empty() => Command::Evaluate(expr.to_owned()),
+        _ => Command::Raw(line.to_owned()),
+    };
+    Some(cmd)
+}
+
+/// `set breakpoint pending on|off`: -break-insert ignores GDB's own setting,
+/// so the driver applies it to the breakpoints that follow.
+fn breakpoint_pending_setting(line: &str) -> Option<bool> {
+    let words: Vec<_> = line.split_whitespace().collect();
+    match words[..] {
+        ["set", "breakpoint", "pending", "on"] => Some(true),
+        ["set", "breakpoint", "pending", "off" | "auto"] => Some(false),
+        _ => None,
+    }
+}
+
+fn is_execution(cmd: &Command) -> bool {
+    matches!(
+        cmd,
+        Command::Run
+            | Command::StartInstruction
+            | Command::Continue
+            | Command::Step
+            | Command::Next
+            | Command::Finish
+            | Command::Advance(_)
+            | Command::StepInstruction
+            | Command::NextInstruction
+            | Command::Restart
+    )
+}
+
+// ─── Output ───────────────────────────────────────────────────────────────────
+
+/// One line per transition worth printing; None for data refreshes.
+pub fn describe(event: &StateEvent) -> Option<String> {
+    let text = match event {
+        StateEvent::ProgramLoaded { executable } => format!("loaded {executable}"),
+        StateEvent::ProgramStarted => "running".into(),
+        StateEvent::ProgramPaused { pause } => {
+            let reason = match &pause.stop_reason {
+                StopReason::BreakpointHit(id) => format!("breakpoint {id}"),
+                StopReason::WatchpointTrigger { id, expr, old, new } => match (old, new) {
+                    (Some(old), Some(new)) => format!("watchpoint {id} {expr}: {old} → {new}"),
+                    _ => format!("watchpoint {id} {expr}"),
+                },
+                StopReason::EndStepping => "step".into(),
+                StopReason::FunctionFinished => "finish".into(),
+                StopReason::Signal(sig) => format!("signal {sig}"),
+                StopReason::SyscallReturn(name) => format!("return from {name}"),
+                StopReason::Unknown => "stopped".into(),
+            };
+            let frame = &pause.frame;
+            match (&frame.file, frame.line) {
+                (Some(file), Some(line)) => {
+                    let short = file.rsplit(['/', '\\']).next().unwrap_or(file);
+                    format!("paused: {reason} in {} at {short}:{line}", frame.function)
+                }
+                _ => format!(
+                    "paused: {reason} in {} at 0x{:x}",
+                    frame.function, frame.addr
+                ),
+            }
+        }
+        StateEvent::ProgramExited { code, signal } => match (code, signal) {
+            (_, Some(sig)) => format!("exited (signal {sig})"),
+            (Some(code), None) => format!("exited (code {code})"),
+            (None, None) => "exited".into(),
+        },
+        StateEvent::ProgramKilled => "killed".into(),
+        StateEvent::BreakpointAdded { breakpoint: bp } => match (bp.kind, &bp.what) {
+            (BreakpointKind::Watch, Some(what)) => format!("watchpoint {}: {what}", bp.id),
+            (BreakpointKind::Catch, Some(what)) => format!("catchpoint {}: {what}", bp.id),
+            (_, Some(what)) => format!("breakpoint {} at {what}", bp.id),
+            _ => format!("breakpoint {} at {}:{}", bp.id, bp.file, bp.line),
+        },
+        StateEvent::BreakpointRemoved { id } => format!("breakpoint {id} deleted"),
+        StateEvent::StackUpdated { frames } => frames
+            .iter()
+            .map(|f| match (&f.file, f.line) {
+                (Some(file), Some(line)) => format!("#{} {} at {file}:{line}", f.level, f.function),
+                _ => format!("#{} {} at 0x{:x}", f.level, f.function, f.addr),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    Some(text)
+}
+
+// ─── Session ──────────────────────────────────────────────────────────────────
+
+struct Session<'a, W> {
+    state: DebuggerState,
+    event_rx: Receiver<DebuggerEvent>,
+    out: &'a mut W,
+}
+
+impl<W: Write> Session<'_, W> {
+    /// Prints events until GDB is quiet; with `until_stop`, first until the
+    /// program stops or exits.
+    fn pump(&mut self, mut until_stop: bool) -> io::Result<()> {
+        let deadline = Instant::now() + STOP_TIMEOUT;
+        loop {
+            let timeout = if until_stop {
+                deadline.saturating_duration_since(Instant::now())
+            } else {
+                SETTLE
+            };
+            match self.event_rx.recv_timeout(timeout) {
+                Ok(event) => {
+                    if self.handle(event)? {
+                        until_stop = false;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if until_stop {
+                        writeln!(self.out, "timeout: the program didn't stop")?;
+                    }
+                    return Ok(());
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "GDB session ended",
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Prints and applies one event; true if the program stopped.
+    fn handle(&mut self, event: DebuggerEvent) -> io::Result<bool> {
+        match event {
+            DebuggerEvent::State(s) => {
+                // ^running and *running both say so
+                let repeated = matches!(s, StateEvent::ProgramStarted)
+                    && matches!(self.state.program, ProgramState::Running);
+                if !repeated && let Some(text) = describe(&s) {
+                    writeln!(self.out, "{text}")?;
+                }
+                let stopped = matches!(
+                    s,
+                    StateEvent::ProgramPaused { .. }
+                        | StateEvent::ProgramExited { .. }
+                        | StateEvent::ProgramKilled
+                );
+                self.state.apply(s);
+                Ok(stopped)
+            }
+            DebuggerEvent::Ui(UiEvent::GdbError(err) | UiEvent::CommandFailed { msg: err, .. }) => {
+                writeln!(self.out, "error: {err}")?;
+                Ok(false)
+            }
+            DebuggerEvent::Ui(UiEvent::EvaluationResult { expr, value }) => {
+                match value {
+                    Ok(v) => writeln!(self.out, "{expr} = {v}")?,
+                    Err(e) => writeln!(self.out, "{expr}: {e}")?,
+                }
+                Ok(false)
+            }
+            // No terminal: GDB answers its own questions, this just says what
+            DebuggerEvent::Ui(UiEvent::Query { question, .. }) => {
+                writeln!(self.out, "query: {question}")?;
+                Ok(false)
+            }
+            DebuggerEvent::Ui(UiEvent::CommandStalled(Some(command))) => {
+                writeln!(self.out, "waiting: no reply to {command} yet")?;
+                Ok(false)
+            }
+            // A failed write was already printed as the command's error
+            DebuggerEvent::Ui(
+                UiEvent::ConsoleOutput(_)
+                | UiEvent::MiRecord(_)
+                | UiEvent::CommandSent(_)
+                | UiEvent::MemoryWritten { .. }
+                | UiEvent::StopHookOutput { .. }
+                | UiEvent::SymbolsFound { .. }
+                | UiEvent::CommandStalled(None),
+            ) => Ok(false),
+        }
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Frame, PauseState};
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("  # setup", false), None);
+        assert_eq!(parse_command("", false), None);
+        assert_eq!(parse_command("r", false), Some(Command::Run));
+        assert_eq!(parse_command("next", false), Some(Command::Next));
+        assert_eq!(
+            parse_command("b main.c:12", false),
+            Some(Command::InsertBreakpoint(BreakpointSpec::new("main.c:12")))
+        );
+        let Some(Command::InsertBreakpoint(spec)) = parse_command("tbreak main", false) else {
+            panic!("expected a breakpoint");
+        };
+        assert!(spec.temporary);
+        assert!(!spec.pending);
+        let Some(Command::InsertBreakpoint(spec)) = parse_command("tbreak main", true) else {
+            panic!("expected a breakpoint");
+        };
+        assert!(spec.temporary && spec.pending);
+        assert_eq!(
+            parse_command("advance main.c:30", false),
+            Some(Command::Advance("main.c:30".into()))
+        );
+        assert_eq!(
+            parse_command("p x + 1", false),
+            Some(Command::Evaluate("x + 1".into()))
+        );
+        assert_eq!(
+            parse_command("bt", false),
+            Some(Command::RequestStack {
+                low: None,
+                high: None
+            })
+        );
+        assert_eq!(
+            parse_command("info frame", false),
+            Some(Command::Raw("info frame".into()))
+        );
+        // Without an argument `break` is GDB's business
+        assert_eq!(
+            parse_command("break", false),
+            Some(Command::Raw("break".into()))
+        );
+    }
+
+    #[test]
+    fn test_breakpoint_pending_setting() {
+        assert_eq!(
+            breakpoint_pending_setting("set breakpoint pending on"),
+            Some(true)
+        );
+        assert_eq!(
+            breakpoint_pending_setting(" set  breakpoint pending off"),
+            Some(false)
+        );
+        assert_eq!(
+            breakpoint_pending_setting("set breakpoint pending auto"),
+            Some(false)
+        );
+        assert_eq!(breakpoint_pending_setting("set pagination off"), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        let pause = PauseState {
+            thread_id: 1,
+            frame: Frame {
+                level: 0,
+                args: vec![],
+                addr: 0x401136,
+                function: "main".into(),
+                file: Some("/src/main.c".into()),
+                line: Some(3),
+            },
+            stack: vec![],
+            stop_reason: StopReason::BreakpointHit(1),
+        };
+        assert_eq!(
+            describe(&StateEvent::ProgramPaused { pause }).as_deref(),
+            Some("paused: breakpoint 1 in main at main.c:3")
+        );
+        assert_eq!(
+            describe(&StateEvent::ProgramExited {
+                code: Some(0),
+                signal: None
+            })
+            .as_deref(),
+            Some("exited (code 0)")
+        );
+        assert_eq!(describe(&StateEvent::LocalsUpdated { vars: vec![] }), None);
+    }
+
+    #[test]
+    fn test_run_script() {
+        let fake = gdb::FakeGdb::default()
+            .reply(
+                "-break-insert",
+                &[
+                    r#"^done,bkpt={number="1",type="breakpoint",disp="keep",enabled="y",addr="0x401136",func="main",file="main.c",fullname="/src/main.c",line="2"}"#,
+                ],
+            )
+            .reply(
+                "-exec-run",
+                &[
+                    "^running",
+                    r#"*running,thread-id="all""#,
+                    r#"*stopped,reason="breakpoint-hit",disp="keep",bkptno="1",frame={addr="0x401136",func="main",args=[],file="main.c",fullname="/src/main.c",line="2"},thread-id="1",stopped-threads="all""#,
+                ],
+            )
+            .reply(
+                "-stack-list-frames",
+                &[
+                    r#"^done,stack=[frame={level="0",addr="0x401136",func="main",file="main.c",fullname="/src/main.c",line="2"}]"#,
+                ],
+            )
+            .reply(
+                "-exec-continue",
+                &[
+                    "^running",
+                    r#"*running,thread-id="all""#,
+                    r#"*stopped,reason="exited",exit-code="03""#,
+                ],
+            );
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        thread::spawn(move || {
+            gdb::run_loop(Box::new(fake), Some("a.out".into()), true, cmd_rx, event_tx)
+        });
+
+        let script = "break main\nrun\nbt\ncontinue\n";
+        let mut out = vec![];
+        run(script.as_bytes(), &cmd_tx, event_rx, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "loaded a.out
+> break main
+breakpoint 1 at /src/main.c:2
+> run
+running
+paused: breakpoint 1 in main at main.c:2
+> bt
+#0 main at /src/main.c:2
+> continue
+running
+exited (code 3)
+"
+        );
+    }
+}
@@ -2,21 +2,38 @@ use std::sync::mpsc;
 use std::thread;
 
 mod gdb;
+mod headless;
 mod state;
 mod ui;
 
 use state::DebuggerState;
-use ui::{App, command::Command};
+use ui::{App, command::Command, settings::Settings};
 
 fn main() -> eframe::Result<()> {
     let (cmd_tx, cmd_rx) = mpsc::channel::<Command>();
     let (event_tx, event_rx) = mpsc::channel::<state::DebuggerEvent>();
 
-    let executable = std::env::args().nth(1);
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(pos) = args.iter().position(|a| a == "--headless") {
+        args.remove(pos);
+        let mut args = args.into_iter();
+        let executable = args.next();
+        if let Some(path) = executable.as_deref().filter(|p| is_source_file(p)) {
+            eprintln!("gdb_gui: {path} is a source file, pass the compiled binary");
+            std::process::exit(1);
+        }
+        if let Err(e) = headless::main(executable, args.next()) {
+            eprintln!("gdb_gui: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    thread::spawn(move || {
-        gdb::run_loop(executable, cmd_rx, event_tx);
-    });
+    // A source file by mistake: show it and ask for the binary instead
+    let (executable, source) = match args.into_iter().next() {
+        Some(path) if is_source_file(&path) => (None, Some(path)),
+        path => (path, None),
+    };
 
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -28,9 +45,50 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "GDB GUI",
         native_options,
-        Box::new(|_cc| {
+        Box::new(|cc| {
             let state = DebuggerState::new();
-            Ok(Box::new(App::new(state, event_rx, cmd_tx)))
+            let settings = Settings::load(cc.storage);
+            // GDB arranca acá: mi-async se fija al lanzarlo y depende de los settings
+            let mi_async = settings.mi_async;
+            thread::spawn(move || {
+                gdb::run_gdb(executable, mi_async, cmd_rx, event_tx);
+            });
+            let mut app = App::new(state, settings, event_rx, cmd_tx);
+            if let Some(path) = source {
+                app.prompt_executable(path);
+            }
+            Ok(Box::new(app))
         }),
     )
 }
+
+/// Extensions of files GDB can't load as an executable but users pass anyway.
+fn is_source_file(path: &str) -> bool {
+    const SOURCE_EXTS: &[&str] = &[
+        "c", "h", "cc", "cpp", "cxx", "c++", "hh", "hpp", "hxx", "rs", "go", "s", "asm", "f",
+        "f90", "m", "mm", "zig", "d", "swift",
+    ];
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SOURCE_EXTS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_source_file() {
+        assert!(is_source_file("main.c"));
+        assert!(is_source_file("src/lib.rs"));
+        assert!(is_source_file("/home/me/app/Widget.CPP"));
+        assert!(is_source_file("boot.S"));
+        assert!(!is_source_file("./a.out"));
+        assert!(!is_source_file("target/debug/app"));
+        assert!(!is_source_file("app.exe"));
+        // Extensionless binaries named like a language
+        assert!(!is_source_file("rs"));
+        assert!(!is_source_file("build/c"));
+    }
+}
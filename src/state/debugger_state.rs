@@ -1,7 +1,12 @@
+use std::collections::{BTreeMap, HashMap};
+
 // ─── Frame ────────────────────────────────────────────────────────────────────
 
 #[derive(Clone, Debug)]
 pub struct Frame {
+    pub level: u32,
+    /// From `-stack-list-arguments`; empty until fetched.
+    pub args: Vec<Variable>,
     pub addr: u64,
     pub function: String,
     pub file: Option<String>,
@@ -10,12 +15,69 @@ pub struct Frame {
 
 // ─── Breakpoint ───────────────────────────────────────────────────────────────
 
+/// What a breakpoint stops on, from the `type` of GDB's `bkpt` block.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BreakpointKind {
+    /// A source line: `file` and `line` are set
+    #[default]
+    Line,
+    /// Code without line info, like a libc function; `what` says where
+    Address,
+    /// A watched expression, in `what`
+    Watch,
+    /// An event (`exception throw`, `fork`, `syscall`…), in `what`
+    Catch,
+    /// Collects data without stopping; remote targets only
+    Trace,
+}
+
 #[derive(Clone, Debug)]
 pub struct Breakpoint {
     pub id: u32,
+    pub kind: BreakpointKind,
     pub file: String,
     pub line: u32,
     pub enabled: bool,
+    pub temporary: bool,
+    pub condition: Option<String>,
+    pub ignore_count: u32,
+    /// Location not resolved yet (e.g. a shared library not loaded); `file`
+    /// and `line` come from GDB's `pending="..."` text.
+    pub pending: bool,
+    /// Logpoint template (`"i = {i}"`). GDB doesn't know about it; the UI
+    /// keeps it across `=breakpoint-modified` updates.
+    pub log_message: Option<String>,
+    /// The location as the user wrote it (`main.c:42`, `foo`).
+    pub original_location: Option<String>,
+    /// What a breakpoint without `file`/`line` is on: the watched
+    /// expression, the caught event or the address.
+    pub what: Option<String>,
+    /// GDB commands run on each hit (`commands N … end`).
+    pub commands: Vec<String>,
+}
+
+// ─── Inferior ─────────────────────────────────────────────────────────────────
+
+/// A GDB thread group (`i1`, `i2`, ...). `pid` is set while its process runs.
+#[derive(Clone, Debug, Default)]
+pub struct Inferior {
+    pub pid: Option<u32>,
+}
+
+// ─── Thread ───────────────────────────────────────────────────────────────────
+
+/// One entry of `-thread-info`.
+#[derive(Clone, Debug)]
+pub struct Thread {
+    pub id: u32,
+    /// `Thread 0x7ffff7d8a740 (LWP 1234)`
+    pub target_id: String,
+    /// Set by the program (`pthread_setname_np`), when it did.
+    pub name: Option<String>,
+    /// `stopped` / `running`
+    pub state: String,
+    /// Innermost frame; absent while the thread runs.
+    pub frame: Option<Frame>,
 }
 
 // ─── Variable (locals / watch) ────────────────────────────────────────────────
@@ -37,12 +99,53 @@ pub struct Register {
     pub value: String, // hex: "0x00007fff..."
 }
 
+// ─── Memory ───────────────────────────────────────────────────────────────────
+
+/// Bytes from `-data-read-memory-bytes`, starting at `addr`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryBlock {
+    pub addr: u64,
+    pub bytes: Vec<u8>,
+}
+
+/// A function from `-symbol-info-functions`. Debug symbols come with their
+/// file and line, the rest (libc's, stripped code) with an address.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub addr: Option<u64>,
+}
+
+/// One row of `info proc mappings`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryRegion {
+    pub start: u64,
+    pub end: u64,
+    pub offset: u64,
+    /// `r-xp`; empty from GDBs older than 12, which don't print it
+    pub perms: String,
+    /// Backing file, `[stack]`, `[heap]`…; empty for anonymous memory
+    pub path: String,
+}
+
+impl MemoryRegion {
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
 // ─── Disassembly ──────────────────────────────────────────────────────────────
 
 #[derive(Clone, Debug)]
 pub struct AsmLine {
     pub addr: u64,
+    /// `func-name`; None outside any known symbol.
+    pub function: Option<String>,
     pub offset: u32,
+    /// Raw instruction bytes in hex (`48 89 e5`), from disassembly mode 2.
+    pub opcodes: Option<String>,
     pub inst: String,
     pub current: bool,
 }
@@ -52,9 +155,80 @@ pub struct AsmLine {
 #[derive(Clone, Debug)]
 pub enum StopReason {
     BreakpointHit(u32),
+    /// A watchpoint fired; `old` is missing for read watchpoints.
+    WatchpointTrigger {
+        id: u32,
+        expr: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
     EndStepping,
+    /// `-exec-finish` returned to the caller.
+    FunctionFinished,
     Signal(String),
+    /// A `catch syscall` on its way out of the named syscall.
+    SyscallReturn(String),
+    Unknown,
+}
+
+// ─── Language ─────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Language {
+    #[default]
     Unknown,
+    C,
+    Cpp,
+    Rust,
+    Go,
+}
+
+impl Language {
+    pub fn from_path(path: &str) -> Self {
+        let ext = path.rsplit_once('.').map(|(_, e)| e).unwrap_or_default();
+        match ext {
+            "c" | "h" => Language::C,
+            "cc" | "cpp" | "cxx" | "c++" | "hh" | "hpp" | "hxx" => Language::Cpp,
+            "rs" => Language::Rust,
+            "go" => Language::Go,
+            _ => Language::Unknown,
+        }
+    }
+
+    /// GDB's name for it, as in `show language`.
+    pub fn from_gdb(name: &str) -> Self {
+        match name {
+            "c" => Language::C,
+            "c++" => Language::Cpp,
+            "rust" => Language::Rust,
+            "go" => Language::Go,
+            _ => Language::Unknown,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::Unknown => "?",
+            Language::C => "C",
+            Language::Cpp => "C++",
+            Language::Rust => "Rust",
+            Language::Go => "Go",
+        }
+    }
+
+    /// Standard-library containers are unreadable without their printers.
+    pub fn pretty_printing(self) -> bool {
+        matches!(self, Language::Cpp | Language::Rust)
+    }
+
+    /// Name of the implicit method receiver, if the language has one.
+    pub fn receiver(self) -> Option<&'static str> {
+        match self {
+            Language::Cpp => Some("this"),
+            Language::Rust => Some("self"),
+            _ => None,
+        }
+    }
 }
 
 // ─── Pause state ─────────────────────────────────────────────────────────────
@@ -75,7 +249,32 @@ pub enum ProgramState {
     ProgramLoaded,
     Running,
     Paused,
-    Exited { code: Option<i32> },
+    Exited {
+        code: Option<i32>,
+        signal: Option<String>,
+    },
+}
+
+// ─── Watch ───────────────────────────────────────────────────────────────────
+
+/// A watch expression's GDB variable object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchValue {
+    /// The varobj's name (`var3`), for `-var-update` and `-var-delete`
+    pub var: String,
+    pub value: String,
+    pub type_: String,
+    /// False once the frame it was created in is gone
+    pub in_scope: bool,
+}
+
+/// One entry of `-var-update`'s changelist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VarChange {
+    pub var: String,
+    /// Absent when the varobj went out of scope
+    pub value: Option<String>,
+    pub in_scope: bool,
 }
 
 // ─── Persistent state ────────────────────────────────────────────────────────
@@ -84,6 +283,8 @@ pub enum ProgramState {
 pub struct PersistentState {
     pub executable: Option<String>,
     pub breakpoints: Vec<Breakpoint>,
+    /// Watch expressions, in the order added; kept across runs.
+    pub watches: Vec<String>,
 }
 
 // ─── Top-level state ─────────────────────────────────────────────────────────
@@ -91,11 +292,39 @@ pub struct PersistentState {
 #[derive(Clone, Debug)]
 pub struct DebuggerState {
     pub program: ProgramState,
+    /// GDB is still reading the executable's symbols: loaded, but a `run`
+    /// now would fail. Ends at GDB's next prompt.
+    pub symbols_loading: bool,
     pub pause: Option<PauseState>,
     pub locals: Vec<Variable>,
     pub register_names: Vec<String>,
     pub registers: Vec<Register>,
     pub disasm: Vec<AsmLine>,
+    /// Source line of each instruction address, from `-data-disassemble -- 1`.
+    /// Filled per function and kept until another executable is loaded.
+    pub asm_source_lines: HashMap<u64, u32>,
+    /// GDB's source search path, including `$cdir`/`$cwd`.
+    pub source_dirs: Vec<String>,
+    pub inferiors: BTreeMap<u32, Inferior>,
+    pub current_inferior: u32,
+    /// Inferior output from the `@` stream. Survives the exit so the last
+    /// lines stay readable; cleared when another executable is loaded.
+    pub program_output: String,
+    /// `checkpoint` snapshots: id → pid of the forked copy.
+    pub checkpoints: BTreeMap<u32, u32>,
+    /// Last `-thread-info`, and the thread GDB has selected.
+    pub threads: Vec<Thread>,
+    pub current_thread: Option<u32>,
+    /// Varobjs of `persistent.watches`, or why GDB couldn't create one.
+    /// They die with the process.
+    pub watch_values: HashMap<String, Result<WatchValue, String>>,
+    /// Last region read for the Memory view.
+    pub memory: Option<MemoryBlock>,
+    /// The process's address space, as of the last `info proc mappings`.
+    pub mappings: Vec<MemoryRegion>,
+    /// From the current frame's file, or GDB's `show language` when that
+    /// doesn't tell.
+    pub language: Language,
     pub persistent: PersistentState,
 }
 
@@ -103,23 +332,185 @@ pub struct DebuggerState {
 
 #[derive(Clone, Debug)]
 pub enum StateEvent {
-    ProgramLoaded { executable: String },
+    ProgramLoaded {
+        executable: String,
+    },
+    /// GDB's first prompt after a load: the symbols are in.
+    SymbolsLoaded,
     ProgramStarted,
-    ProgramPaused { pause: PauseState },
-    ProgramExited { code: Option<i32> },
-    BreakpointAdded { breakpoint: Breakpoint },
-    BreakpointRemoved { id: u32 },
-    BreakpointToggled { id: u32, enabled: bool },
-    LocalsUpdated { vars: Vec<Variable> },
-    RegisterNamesReceived { names: Vec<String> },
-    RegistersUpdated { registers: Vec<Register> },
-    DisasmUpdated { lines: Vec<AsmLine> },
+    ProgramPaused {
+        pause: PauseState,
+    },
+    ProgramExited {
+        code: Option<i32>,
+        signal: Option<String>,
+    },
+    /// Inferior killed on request; GDB and the executable stay loaded.
+    ProgramKilled,
+    BreakpointAdded {
+        breakpoint: Breakpoint,
+    },
+    BreakpointRemoved {
+        id: u32,
+    },
+    BreakpointToggled {
+        id: u32,
+        enabled: bool,
+    },
+    LogMessageSet {
+        id: u32,
+        message: Option<String>,
+    },
+    WatchAdded {
+        expr: String,
+    },
+    WatchRemoved {
+        expr: String,
+    },
+    /// `-var-create`'s answer for a watch expression.
+    WatchCreated {
+        expr: String,
+        result: Result<WatchValue, String>,
+    },
+    /// Varobjs that changed since the last `-var-update`.
+    WatchesUpdated {
+        changes: Vec<VarChange>,
+    },
+    LocalsUpdated {
+        vars: Vec<Variable>,
+    },
+    /// Frames from `-stack-list-frames`, possibly just a range of levels.
+    StackUpdated {
+        frames: Vec<Frame>,
+    },
+    /// Arguments per frame level, from `-stack-list-arguments`.
+    FrameArgsUpdated {
+        frames: Vec<(u32, Vec<Variable>)>,
+    },
+    RegisterNamesReceived {
+        names: Vec<String>,
+    },
+    RegistersUpdated {
+        registers: Vec<Register>,
+    },
+    DisasmUpdated {
+        lines: Vec<AsmLine>,
+    },
+    /// (instruction address, source line) pairs for one function.
+    AsmSourceLinesUpdated {
+        lines: Vec<(u64, u32)>,
+    },
+    SourceDirsUpdated {
+        dirs: Vec<String>,
+    },
+    InferiorAdded {
+        id: u32,
+    },
+    InferiorStarted {
+        id: u32,
+        pid: u32,
+    },
+    InferiorExited {
+        id: u32,
+    },
+    InferiorRemoved {
+        id: u32,
+    },
+    InferiorSelected {
+        id: u32,
+    },
+    /// Text the inferior wrote, from the `@` stream.
+    ProgramOutput {
+        text: String,
+    },
+    CheckpointCreated {
+        id: u32,
+        pid: u32,
+    },
+    CheckpointDeleted {
+        id: u32,
+    },
+    /// `^connected` to a `target remote` address.
+    RemoteConnected {
+        addr: String,
+    },
+    RemoteConnectFailed {
+        addr: String,
+        msg: String,
+    },
+    /// The remote link dropped; GDB keeps the executable and breakpoints.
+    RemoteDisconnected {
+        reason: String,
+    },
+    ThreadsUpdated {
+        threads: Vec<Thread>,
+        current: Option<u32>,
+    },
+    ThreadCreated {
+        id: u32,
+    },
+    ThreadExited {
+        id: u32,
+    },
+    /// GDB switched to another thread; `frame` is its innermost one.
+    ThreadSelected {
+        id: u32,
+        frame: Option<Frame>,
+    },
+    MemoryRead {
+        block: MemoryBlock,
+    },
+    MappingsRead {
+        regions: Vec<MemoryRegion>,
+    },
+    LanguageDetected {
+        language: Language,
+    },
 }
 
 #[derive(Clone, Debug)]
 pub enum UiEvent {
+    /// Text GDB printed, parsed out of its record.
     ConsoleOutput(String),
+    /// A line exactly as GDB printed it, ahead of the events parsed from it.
+    MiRecord(String),
+    /// The MI text of a command `run_loop` just sent.
+    CommandSent(String),
     GdbError(String),
+    /// `^error` for a token `run_loop` sent, with the MI command behind it.
+    CommandFailed {
+        command: String,
+        msg: String,
+    },
+    /// Result of a `Command::Evaluate`, correlated by token in `run_loop`.
+    EvaluationResult {
+        expr: String,
+        value: Result<String, String>,
+    },
+    /// GDB is waiting on a `(y or n)` question, or on `--Type <RET>` when
+    /// `pagination` is set.
+    Query {
+        question: String,
+        pagination: bool,
+    },
+    /// Result of a `Command::WriteMemory`, correlated by token in `run_loop`.
+    MemoryWritten {
+        addr: u64,
+        result: Result<(), String>,
+    },
+    /// Console output of one of the user's stop commands.
+    StopHookOutput {
+        command: String,
+        output: String,
+    },
+    /// Functions matching a `Command::SearchSymbols` regex.
+    SymbolsFound {
+        regex: String,
+        symbols: Vec<Symbol>,
+    },
+    /// The oldest command GDB hasn't answered in a while, or None once
+    /// nothing is late anymore.
+    CommandStalled(Option<String>),
 }
 
 #[derive(Clone, Debug)]
@@ -134,14 +525,28 @@ impl DebuggerState {
     pub fn new() -> Self {
         Self {
             program: ProgramState::NoProgramLoaded,
+            symbols_loading: false,
             pause: None,
             locals: vec![],
             register_names: vec![],
             registers: vec![],
             disasm: vec![],
+            asm_source_lines: HashMap::new(),
+            source_dirs: vec![],
+            inferiors: BTreeMap::new(),
+            current_inferior: 1,
+            checkpoints: BTreeMap::new(),
+            program_output: String::new(),
+            threads: vec![],
+            current_thread: None,
+            watch_values: HashMap::new(),
+            memory: None,
+            mappings: vec![],
+            language: Language::Unknown,
             persistent: PersistentState {
                 executable: None,
                 breakpoints: vec![],
+                watches: vec![],
             },
         }
     }
@@ -150,39 +555,77 @@ impl DebuggerState {
         match event {
             StateEvent::ProgramLoaded { executable } => {
                 self.program = ProgramState::ProgramLoaded;
+                self.symbols_loading = true;
                 self.persistent.executable = Some(executable);
                 self.pause = None;
+                self.threads = vec![];
                 self.locals = vec![];
                 self.register_names = vec![];
                 self.registers = vec![];
                 self.disasm = vec![];
+                self.asm_source_lines.clear();
+                self.memory = None;
+                self.mappings.clear();
+                self.language = Language::Unknown;
+                self.checkpoints.clear();
+                self.program_output.clear();
+                self.watch_values.clear();
             }
 
+            StateEvent::SymbolsLoaded => self.symbols_loading = false,
+
             StateEvent::ProgramStarted => {
                 self.program = ProgramState::Running;
                 self.pause = None;
                 self.locals = vec![];
-                self.register_names = vec![];
                 self.registers = vec![];
                 self.disasm = vec![];
             }
 
             StateEvent::ProgramPaused { pause } => {
+                let language = pause.frame.file.as_deref().map(Language::from_path);
+                if let Some(language) = language.filter(|l| *l != Language::Unknown) {
+                    self.language = language;
+                }
                 self.program = ProgramState::Paused;
                 self.pause = Some(pause);
             }
 
-            StateEvent::ProgramExited { code } => {
-                self.program = ProgramState::Exited { code };
+            StateEvent::ProgramExited { code, signal } => {
+                self.program = ProgramState::Exited { code, signal };
                 self.pause = None;
+                self.threads = vec![];
                 self.locals = vec![];
-                self.register_names = vec![];
                 self.registers = vec![];
                 self.disasm = vec![];
+                self.mappings.clear();
+                self.watch_values.clear();
             }
 
-            StateEvent::BreakpointAdded { breakpoint } => {
-                self.persistent.breakpoints.push(breakpoint);
+            StateEvent::ProgramKilled => {
+                self.program = ProgramState::ProgramLoaded;
+                self.pause = None;
+                self.threads = vec![];
+                self.locals = vec![];
+                self.registers = vec![];
+                self.disasm = vec![];
+                self.mappings.clear();
+                self.watch_values.clear();
+            }
+
+            StateEvent::BreakpointAdded { mut breakpoint } => {
+                match self
+                    .persistent
+                    .breakpoints
+                    .iter_mut()
+                    .find(|b| b.id == breakpoint.id)
+                {
+                    Some(existing) => {
+                        breakpoint.log_message = existing.log_message.take();
+                        *existing = breakpoint;
+                    }
+                    None => self.persistent.breakpoints.push(breakpoint),
+                }
             }
 
             StateEvent::BreakpointRemoved { id } => {
@@ -195,10 +638,145 @@ impl DebuggerState {
                 }
             }
 
+            StateEvent::LogMessageSet { id, message } => {
+                if let Some(bp) = self.persistent.breakpoints.iter_mut().find(|b| b.id == id) {
+                    bp.log_message = message;
+                }
+            }
+
+            StateEvent::WatchAdded { expr } => {
+                if !self.persistent.watches.contains(&expr) {
+                    self.persistent.watches.push(expr);
+                }
+            }
+            StateEvent::WatchRemoved { expr } => {
+                self.persistent.watches.retain(|w| *w != expr);
+                self.watch_values.remove(&expr);
+            }
+            StateEvent::WatchCreated { expr, result } => {
+                // Removed while GDB was creating it
+                if self.persistent.watches.contains(&expr) {
+                    self.watch_values.insert(expr, result);
+                }
+            }
+            StateEvent::WatchesUpdated { changes } => {
+                for change in changes {
+                    let watch = self.watch_values.values_mut().find_map(|w| match w {
+                        Ok(w) if w.var == change.var => Some(w),
+                        _ => None,
+                    });
+                    if let Some(watch) = watch {
+                        if let Some(value) = change.value {
+                            watch.value = value;
+                        }
+                        watch.in_scope = change.in_scope;
+                    }
+                }
+            }
+
             StateEvent::LocalsUpdated { vars } => self.locals = vars,
+            StateEvent::StackUpdated { frames } => {
+                if let Some(pause) = &mut self.pause {
+                    for frame in frames {
+                        // Frame 0 only moves without a stop when the pc is set by hand
+                        if frame.level == 0 && frame.addr != pause.frame.addr {
+                            pause.frame = frame.clone();
+                        }
+                        match pause.stack.get_mut(frame.level as usize) {
+                            Some(slot) => *slot = frame,
+                            None => pause.stack.push(frame),
+                        }
+                    }
+                }
+            }
             StateEvent::RegisterNamesReceived { names } => self.register_names = names,
             StateEvent::RegistersUpdated { registers } => self.registers = registers,
             StateEvent::DisasmUpdated { lines } => self.disasm = lines,
+            StateEvent::AsmSourceLinesUpdated { lines } => self.asm_source_lines.extend(lines),
+            StateEvent::FrameArgsUpdated { frames } => {
+                if let Some(pause) = &mut self.pause {
+                    for (level, args) in frames {
+                        if let Some(frame) = pause.stack.get_mut(level as usize) {
+                            frame.args = args;
+                        }
+                    }
+                }
+            }
+            StateEvent::SourceDirsUpdated { dirs } => self.source_dirs = dirs,
+
+            StateEvent::InferiorAdded { id } => {
+                self.inferiors.entry(id).or_default();
+            }
+            StateEvent::InferiorStarted { id, pid } => {
+                self.inferiors.entry(id).or_default().pid = Some(pid);
+            }
+            StateEvent::InferiorExited { id } => {
+                if let Some(inf) = self.inferiors.get_mut(&id) {
+                    inf.pid = None;
+                }
+            }
+            StateEvent::InferiorRemoved { id } => {
+                self.inferiors.remove(&id);
+            }
+            StateEvent::InferiorSelected { id } => self.current_inferior = id,
+
+            StateEvent::ProgramOutput { text } => self.program_output.push_str(&text),
+
+            StateEvent::CheckpointCreated { id, pid } => {
+                self.checkpoints.insert(id, pid);
+            }
+            StateEvent::CheckpointDeleted { id } => {
+                self.checkpoints.remove(&id);
+            }
+
+            StateEvent::ThreadsUpdated { threads, current } => {
+                self.threads = threads;
+                self.current_thread = current;
+            }
+            StateEvent::ThreadCreated { id } => {
+                if !self.threads.iter().any(|t| t.id == id) {
+                    self.threads.push(Thread {
+                        id,
+                        target_id: String::new(),
+                        name: None,
+                        state: "running".into(),
+                        frame: None,
+                    });
+                }
+            }
+            StateEvent::ThreadExited { id } => {
+                self.threads.retain(|t| t.id != id);
+                if self.current_thread == Some(id) {
+                    self.current_thread = None;
+                }
+            }
+            StateEvent::ThreadSelected { id, frame } => {
+                self.current_thread = Some(id);
+                // The old thread's stack and locals don't apply anymore
+                if let Some(pause) = &mut self.pause
+                    && let Some(frame) = frame
+                {
+                    pause.frame = frame.clone();
+                    pause.stack = vec![frame];
+                    self.locals = vec![];
+                }
+            }
+
+            StateEvent::MemoryRead { block } => self.memory = Some(block),
+            StateEvent::MappingsRead { regions } => self.mappings = regions,
+
+            StateEvent::LanguageDetected { language } => self.language = language,
+
+            StateEvent::RemoteConnected { .. } | StateEvent::RemoteConnectFailed { .. } => {}
+
+            StateEvent::RemoteDisconnected { .. } => {
+                self.program = ProgramState::ProgramLoaded;
+                self.pause = None;
+                self.locals = vec![];
+                self.registers = vec![];
+                self.disasm = vec![];
+                self.watch_values.clear();
+            }
         }
     }
 
@@ -212,6 +790,11 @@ impl DebuggerState {
         matches!(self.program, ProgramState::Running)
     }
 
+    /// Whether Run can start the program now.
+    pub fn can_run(&self) -> bool {
+        !self.symbols_loading
+    }
+
     pub fn current_file(&self) -> Option<&str> {
         self.pause.as_ref()?.frame.file.as_deref()
     }
@@ -241,3 +824,313 @@ impl Default for DebuggerState {
         Self::new()
     }
 }
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paused_state() -> DebuggerState {
+        let mut state = DebuggerState::new();
+        state.apply(StateEvent::ProgramLoaded {
+            executable: "a.out".into(),
+        });
+        state.apply(StateEvent::ProgramPaused {
+            pause: PauseState {
+                thread_id: 1,
+                frame: Frame {
+                    level: 0,
+                    args: vec![],
+                    addr: 0x1000,
+                    function: "main".into(),
+                    file: Some("main.c".into()),
+                    line: Some(3),
+                },
+                stack: vec![],
+                stop_reason: StopReason::EndStepping,
+            },
+        });
+        state.apply(StateEvent::LocalsUpdated {
+            vars: vec![Variable {
+                name: "i".into(),
+                value: "1".into(),
+                type_: "int".into(),
+            }],
+        });
+        state
+    }
+
+    #[test]
+    fn test_run_waits_for_symbols() {
+        let mut state = DebuggerState::new();
+        assert!(state.can_run());
+        state.apply(StateEvent::ProgramLoaded {
+            executable: "big.elf".into(),
+        });
+        assert!(matches!(state.program, ProgramState::ProgramLoaded));
+        assert!(!state.can_run());
+        state.apply(StateEvent::SymbolsLoaded);
+        assert!(state.can_run());
+
+        // Loading another executable waits again
+        state.apply(StateEvent::ProgramLoaded {
+            executable: "other.elf".into(),
+        });
+        assert!(!state.can_run());
+    }
+
+    #[test]
+    fn test_stack_refresh_moves_frame_zero() {
+        let mut state = paused_state();
+        let frame = |addr, line| Frame {
+            level: 0,
+            args: vec![],
+            addr,
+            function: "main".into(),
+            file: Some("main.c".into()),
+            line: Some(line),
+        };
+        state.apply(StateEvent::StackUpdated {
+            frames: vec![frame(0x1000, 3)],
+        });
+        assert_eq!(state.current_line(), Some(3));
+        // After `set $pc`
+        state.apply(StateEvent::StackUpdated {
+            frames: vec![frame(0x1010, 5)],
+        });
+        assert_eq!(state.current_addr(), Some(0x1010));
+        assert_eq!(state.current_line(), Some(5));
+    }
+
+    #[test]
+    fn test_thread_switch() {
+        let mut state = paused_state();
+        state.apply(StateEvent::ThreadCreated { id: 2 });
+        state.apply(StateEvent::ThreadCreated { id: 2 });
+        assert_eq!(state.threads.len(), 1);
+
+        let worker = Frame {
+            level: 0,
+            args: vec![],
+            addr: 0x2000,
+            function: "worker".into(),
+            file: Some("w.c".into()),
+            line: Some(8),
+        };
+        state.apply(StateEvent::ThreadSelected {
+            id: 2,
+            frame: Some(worker),
+        });
+        assert_eq!(state.current_thread, Some(2));
+        let pause = state.pause.as_ref().unwrap();
+        assert_eq!(pause.frame.function, "worker");
+        assert_eq!(pause.stack.len(), 1);
+        // The thread that stopped stays the one that stopped
+        assert_eq!(pause.thread_id, 1);
+        assert!(state.locals.is_empty());
+
+        state.apply(StateEvent::ThreadExited { id: 2 });
+        assert!(state.threads.is_empty());
+        assert_eq!(state.current_thread, None);
+    }
+
+    #[test]
+    fn test_watch_values() {
+        let mut state = paused_state();
+        let watch = |var: &str, value: &str| WatchValue {
+            var: var.into(),
+            value: value.into(),
+            type_: "int".into(),
+            in_scope: true,
+        };
+        state.apply(StateEvent::WatchAdded { expr: "n".into() });
+        state.apply(StateEvent::WatchAdded { expr: "n".into() });
+        state.apply(StateEvent::WatchAdded { expr: "p".into() });
+        assert_eq!(state.persistent.watches, ["n", "p"]);
+
+        state.apply(StateEvent::WatchCreated {
+            expr: "n".into(),
+            result: Ok(watch("var1", "1")),
+        });
+        state.apply(StateEvent::WatchCreated {
+            expr: "p".into(),
+            result: Err("No symbol \"p\" in current context.".into()),
+        });
+        // Removed before GDB answered
+        state.apply(StateEvent::WatchCreated {
+            expr: "q".into(),
+            result: Ok(watch("var2", "0")),
+        });
+        assert_eq!(state.watch_values.len(), 2);
+
+        state.apply(StateEvent::WatchesUpdated {
+            changes: vec![VarChange {
+                var: "var1".into(),
+                value: Some("2".into()),
+                in_scope: true,
+            }],
+        });
+        assert_eq!(state.watch_values["n"], Ok(watch("var1", "2")));
+        state.apply(StateEvent::WatchesUpdated {
+            changes: vec![VarChange {
+                var: "var1".into(),
+                value: None,
+                in_scope: false,
+            }],
+        });
+        assert!(matches!(&state.watch_values["n"], Ok(w) if !w.in_scope && w.value == "2"));
+
+        state.apply(StateEvent::WatchRemoved { expr: "p".into() });
+        assert_eq!(state.persistent.watches, ["n"]);
+        assert!(!state.watch_values.contains_key("p"));
+
+        // The expressions outlive the process, their varobjs don't
+        state.apply(StateEvent::ProgramKilled);
+        assert!(state.watch_values.is_empty());
+        assert_eq!(state.persistent.watches, ["n"]);
+    }
+
+    #[test]
+    fn test_kill_returns_to_loaded() {
+        let mut state = paused_state();
+        state.apply(StateEvent::ProgramKilled);
+        assert!(matches!(state.program, ProgramState::ProgramLoaded));
+        assert!(state.pause.is_none());
+        assert!(state.locals.is_empty());
+        assert_eq!(state.persistent.executable.as_deref(), Some("a.out"));
+    }
+
+    #[test]
+    fn test_resume_clears_machine_views() {
+        let mut state = paused_state();
+        state.apply(StateEvent::RegistersUpdated {
+            registers: vec![Register {
+                number: 0,
+                name: "rax".into(),
+                value: "0x2a".into(),
+            }],
+        });
+        state.apply(StateEvent::DisasmUpdated {
+            lines: vec![AsmLine {
+                addr: 0x401126,
+                function: Some("main".into()),
+                offset: 0,
+                opcodes: None,
+                inst: "push   %rbp".into(),
+                current: true,
+            }],
+        });
+        assert_eq!(state.registers.len(), 1);
+        assert_eq!(state.disasm.len(), 1);
+
+        state.apply(StateEvent::ProgramStarted);
+        assert!(state.registers.is_empty());
+        assert!(state.disasm.is_empty());
+
+        let mut state = paused_state();
+        state.apply(StateEvent::RegistersUpdated {
+            registers: vec![Register {
+                number: 0,
+                name: "rax".into(),
+                value: "0x0".into(),
+            }],
+        });
+        state.apply(StateEvent::ProgramExited {
+            code: Some(0),
+            signal: None,
+        });
+        assert!(state.registers.is_empty());
+    }
+
+    #[test]
+    fn test_register_names_survive_runs() {
+        let mut state = paused_state();
+        state.apply(StateEvent::RegisterNamesReceived {
+            names: vec!["rax".into(), "rbx".into()],
+        });
+        state.apply(StateEvent::ProgramExited {
+            code: Some(0),
+            signal: None,
+        });
+        state.apply(StateEvent::ProgramStarted);
+        let pause = paused_state().pause.unwrap();
+        state.apply(StateEvent::ProgramPaused { pause });
+        // Only a new program has other names
+        assert_eq!(state.register_names, ["rax", "rbx"]);
+
+        state.apply(StateEvent::ProgramLoaded {
+            executable: "b.out".into(),
+        });
+        assert!(state.register_names.is_empty());
+    }
+
+    #[test]
+    fn test_language_defaults() {
+        let state = paused_state();
+        assert_eq!(state.language, Language::C);
+
+        let cases = [
+            ("main.c", Language::C, false, None),
+            ("widget.cpp", Language::Cpp, true, Some("this")),
+            ("src/lib.rs", Language::Rust, true, Some("self")),
+            ("cmd/main.go", Language::Go, false, None),
+            ("start.S", Language::Unknown, false, None),
+        ];
+        for (path, language, pretty, receiver) in cases {
+            assert_eq!(Language::from_path(path), language, "{path}");
+            assert_eq!(language.pretty_printing(), pretty, "{path}");
+            assert_eq!(language.receiver(), receiver, "{path}");
+        }
+        assert_eq!(Language::from_gdb("c++"), Language::Cpp);
+        assert_eq!(Language::from_gdb("asm"), Language::Unknown);
+    }
+
+    #[test]
+    fn test_program_output_survives_exit() {
+        let mut state = paused_state();
+        state.apply(StateEvent::ProgramOutput {
+            text: "done: 42\n".into(),
+        });
+        state.apply(StateEvent::ProgramExited {
+            code: Some(0),
+            signal: None,
+        });
+        // Output arriving after *stopped still lands in the same buffer
+        state.apply(StateEvent::ProgramOutput {
+            text: "bye\n".into(),
+        });
+        assert!(state.pause.is_none());
+        assert!(state.locals.is_empty());
+        assert_eq!(state.program_output, "done: 42\nbye\n");
+    }
+
+    #[test]
+    fn test_stack_pages_merge() {
+        let frame = |level: u32, function: &str| Frame {
+            level,
+            args: vec![],
+            addr: 0,
+            function: function.into(),
+            file: None,
+            line: None,
+        };
+        let mut state = paused_state();
+        state.apply(StateEvent::StackUpdated {
+            frames: vec![frame(0, "leaf"), frame(1, "mid")],
+        });
+        state.apply(StateEvent::StackUpdated {
+            frames: vec![frame(2, "main")],
+        });
+        state.apply(StateEvent::StackUpdated { frames: vec![] });
+
+        let names: Vec<_> = state
+            .pause
+            .unwrap()
+            .stack
+            .iter()
+            .map(|f| f.function.clone())
+            .collect();
+        assert_eq!(names, vec!["leaf", "mid", "main"]);
+    }
+}
@@ -3,19 +3,27 @@ mod debugger_state;
 pub use debugger_state::{
     AsmLine,
     Breakpoint,
+    BreakpointKind,
     // Events
     DebuggerEvent,
     // Core state
     DebuggerState,
     // Types
     Frame,
+    Language,
+    MemoryBlock,
+    MemoryRegion,
     PauseState,
 
     ProgramState,
     Register,
     StateEvent,
     StopReason,
+    Symbol,
+    Thread,
 
     UiEvent,
+    VarChange,
     Variable,
+    WatchValue,
 };
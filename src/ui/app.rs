@@ -2,10 +2,47 @@ use eframe::egui::{
     self, Align, Color32, FontId, Frame, Key, Layout, Margin, RichText, ScrollArea, Sense, Stroke,
     TextEdit, Vec2,
 };
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::{Receiver, Sender};
+use std::time::Instant;
 
-use super::command::Command;
-use crate::state::{DebuggerEvent, DebuggerState, UiEvent};
+use super::bookmarks;
+use super::changes::{FRAME_EXPR, Snapshot, diff};
+use super::command::{BreakpointSpec, Command, ForkMode, WatchKind};
+use super::condition::{CondEdit, Op, candidate_names};
+use super::crash_report::{CrashReport, is_fatal};
+use super::disasm_export::format_disasm;
+use super::editor;
+use super::errno::{self, ERRNO_EXPR, SyscallResult};
+use super::errors::ErrorList;
+use super::focus::FocusRegion;
+use super::frame_eval::FrameEval;
+use super::hit_log::{HitEntry, VisitCounts};
+use super::insn_doc::{Arch, describe_insn};
+use super::launch::{LaunchConfig, LaunchConfigs};
+use super::layout::{CentralView, LayoutPreset};
+use super::logpoint::PendingLog;
+use super::memory::{MAX_WATCH_LEN, READ_LEN, ROW, ascii, changed_bytes, parse_hex_bytes};
+use super::mi_log::MiLog;
+use super::profile::StepProfile;
+use super::reconnect::{LinkState, MAX_ATTEMPTS, RemoteLink};
+use super::registers::{MAX_PINNED, RegCategory, categorize, toggle_pin};
+use super::search::{SearchHit, SearchQuery, parse_extensions, sort_hits, spawn_search};
+use super::settings::Settings;
+use super::smart_step::SmartStep;
+use super::source_watch::SourceWatch;
+use super::state_report;
+use super::step_until::{self, Outcome, StepUntil};
+use super::stop_hook::{resumes, stop_commands};
+use super::symbols::{self, MAX_SYMBOLS, SymbolSearch};
+use super::syntax::{self, Token};
+use super::value_diff::{Compare, ValueTree, diff_values, top_level_fields};
+use super::verbosity::{ConsoleLog, LineKind, Verbosity};
+use crate::gdb::{STALL_TIMEOUT, command_to_console, command_to_mi};
+use crate::state::{
+    AsmLine, BreakpointKind, DebuggerEvent, DebuggerState, Language, MemoryRegion, PauseState,
+    ProgramState, StateEvent, StopReason, Thread, UiEvent, Variable,
+};
 
 // ─── Palette ──────────────────────────────────────────────────────────────────
 
@@ -28,9 +65,32 @@ const TXT_CYAN: Color32 = Color32::from_rgb(0x7e, 0xc8, 0xe3);
 const TXT_YELLOW: Color32 = Color32::from_rgb(0xe8, 0xc9, 0x7d);
 const TXT_HL: Color32 = Color32::from_rgb(0xd4, 0xf0, 0xd4);
 
+// Frames fetched per -stack-list-frames request
+const STACK_PAGE: u32 = 32;
+
+// Breakpoints from a single rbreak before warning the user
+const RBREAK_WARN: usize = 100;
+
+// Breadcrumb segments shown before the middle is elided
+const BREADCRUMB_MAX: usize = 8;
+
+// Inline values look this far back for the start of the current function
+const INLINE_WINDOW: u32 = 40;
+
+// Find in Files stops after this many hits
+const FIND_MAX_RESULTS: usize = 500;
+
 // ─── UI-only tab state ────────────────────────────────────────────────────────
 
 #[derive(Default, PartialEq, Clone, Copy)]
+enum ConsoleTab {
+    #[default]
+    Gdb,
+    Output,
+    Stop,
+}
+
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
 enum WatchTab {
     #[default]
     Watch,
@@ -38,6 +98,39 @@ enum WatchTab {
     Data,
 }
 
+/// Per-tab data that hasn't been fetched since the last stop. Only the visible
+/// tab is requested on pause; the rest is fetched when its tab is opened.
+#[derive(Default)]
+struct StaleViews {
+    locals: bool,
+    registers: bool,
+    disasm: bool,
+}
+
+impl StaleViews {
+    fn all() -> Self {
+        Self {
+            locals: true,
+            registers: true,
+            disasm: true,
+        }
+    }
+
+    /// Requests needed to show `tab`; marks them as fetched.
+    fn requests_for(&mut self, tab: WatchTab) -> Vec<Command> {
+        let (flag, cmd) = match tab {
+            WatchTab::Watch => (&mut self.locals, Command::RequestLocals),
+            WatchTab::Registers => (&mut self.registers, Command::RequestRegisters),
+            WatchTab::Data => (&mut self.disasm, Command::RequestDisasm),
+        };
+        if std::mem::take(flag) {
+            vec![cmd]
+        } else {
+            vec![]
+        }
+    }
+}
+
 // ─── Source line para renderizado ─────────────────────────────────────────────
 
 struct SourceLine {
@@ -45,51 +138,413 @@ struct SourceLine {
     text: String,
 }
 
+/// Which `try_load_source` candidate a file was read from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SourceMatch {
+    Exact,
+    Substituted,
+    FileName,
+    SrcDir,
+    SourceDir,
+}
+
+impl SourceMatch {
+    /// Matched by bare file name in a guessed place: another file with the
+    /// same name would be just as good a match.
+    fn is_fallback(self) -> bool {
+        matches!(self, SourceMatch::FileName | SourceMatch::SrcDir)
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            SourceMatch::Exact => "path reported by GDB",
+            SourceMatch::Substituted => "path substitution",
+            SourceMatch::FileName => "file name in the current directory",
+            SourceMatch::SrcDir => "file name under src/",
+            SourceMatch::SourceDir => "file name in a source directory",
+        }
+    }
+}
+
+// ─── Gutter marker ────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq)]
+enum BpMarker {
+    Break,
+    Log,
+    Pending,
+}
+
 // ─── App ──────────────────────────────────────────────────────────────────────
 
 pub struct App {
     pub state: DebuggerState,
+    settings: Settings,
     event_rx: Receiver<DebuggerEvent>,
     cmd_tx: Sender<Command>,
 
     // UI state
     console_input: String,
-    console_log: Vec<String>,
+    console_log: ConsoleLog,
+    console_tab: ConsoleTab,
+    // Output of the stop commands after the last stop
+    stop_output: String,
+    new_stop_cmd: String,
+    // Commands typed at the (gdb) prompt, oldest first
+    console_history: Vec<String>,
+    // CLI equivalent of every user action, copyable as a reproduction script
+    command_log: Vec<String>,
+    // Breakpoint/watchpoint stops, oldest first; times are relative to `started`
+    hit_log: Vec<HitEntry>,
+    visits: VisitCounts,
+    started: Instant,
+    // Previous and current stop, for the Changes panel
+    changes_prev: Snapshot,
+    changes_cur: Snapshot,
+    // Auto-step profiling: time per source line, shown as a gutter heat map
+    profile: StepProfile,
+    // Snapshot being gathered after a fatal signal; one per run
+    crash_report: Option<CrashReport>,
+    crash_captured: bool,
     watch_tab: WatchTab,
+    locals_filter: String,
+    // this/self first and pre-expanded: the `*this` expression and its value
+    receiver_first: bool,
+    receiver_expr: Option<String>,
+    receiver_value: Option<Result<String, String>>,
+    // Frame chosen in the Stack/breadcrumb; GDB goes back to 0 on every stop
+    selected_frame: u32,
+    /// List the arrow keys drive, once one of its rows was clicked
+    nav: Option<NavPanel>,
+    stack_cursor: usize,
+    tree_cursor: usize,
+    /// Expanded nodes of the evaluated value, by path (`expr.field[2]`)
+    tree_open: HashSet<String>,
+    /// Watches with a `-var-create` not answered yet
+    watch_creating: HashSet<String>,
+    // "Eval in all frames": expression being typed and the running/finished sweep
+    frame_eval_input: String,
+    // One-off eval: the last expression (from the Eval row or a console `print`)
+    // can be re-sent with ↻ / Ctrl+E while stepping
+    eval_input: String,
+    // Watch expression being typed; the list itself is in `state.persistent`
+    watch_input: String,
+    // "View as type" form: address or expression, and the type it points to
+    cast_expr: String,
+    cast_type: String,
+    last_eval: Option<String>,
+    last_eval_result: Option<Result<String, String>>,
+    last_eval_pending: bool,
+    frame_eval: Option<FrameEval>,
+    // Functions whose asm → source line table was already requested
+    asm_line_funcs: HashSet<String>,
+    stale: StaleViews,
+    // -enable-pretty-printing sent for the loaded program
+    pretty_printing: bool,
 
     // Collapsible sections
     open_bp: bool,
     open_cmd: bool,
+    // New custom command button being typed: (label, command)
+    new_cmd: (String, String),
+    open_cmd_log: bool,
+    // Raw MI records, kept while the section is open
+    open_mi_log: bool,
+    mi_log: MiLog,
+    mi_unhandled_only: bool,
+    open_hit_log: bool,
+    open_changes: bool,
+    errors: ErrorList,
+    /// errno at the last stop, when the setting is on
+    errno: Option<Result<String, String>>,
+    /// The syscall's return value, at a syscall return with the setting on
+    syscall_ret: Option<SyscallResult>,
+    smart_step: SmartStep,
+    step_until: StepUntil,
+    step_until_input: String,
+    compare: Compare,
+    /// Prompt GDB printed: (question, is pagination)
+    query: Option<(String, bool)>,
+    /// MI text of a command GDB hasn't answered for a while
+    stalled: Option<String>,
+    open_errors: bool,
+    open_launch: bool,
+    open_mappings: bool,
+    open_bookmarks: bool,
+    open_profile: bool,
+    open_step_until: bool,
+    open_compare: bool,
     open_struct: bool,
     open_stack: bool,
     open_files: bool,
     open_thread: bool,
+    open_find: bool,
+    open_symbols: bool,
+    open_checkpoints: bool,
+
+    // Find in Files: results stream in from a background search while `find_rx` is set
+    find_query: String,
+    find_exts: String,
+    find_hits: Vec<SearchHit>,
+    find_rx: Option<Receiver<SearchHit>>,
+    symbols: SymbolSearch,
+
+    // Source tabs opened by hand; active_tab None = follow execution
+    open_tabs: Vec<String>,
+    active_tab: Option<usize>,
+    scroll_to_line: Option<u32>,
+    // Source row under the pointer last frame, and the last bookmark jumped to
+    hovered_line: Option<u32>,
+    bookmark_cursor: Option<u32>,
+
+    // Stack paging: more frames may exist past the last page / a page is in flight
+    stack_more: bool,
+    stack_loading: bool,
 
     source_lines: Vec<SourceLine>,
     source_file: Option<String>,
+    // Where `source_file` was actually read from, and how it was found
+    source_resolved: Option<(std::path::PathBuf, SourceMatch)>,
+    // Files read for stack-frame previews (None = not found, don't retry)
+    source_cache: HashMap<String, Option<Vec<String>>>,
+    // Where each cached tab was read from, for the watcher
+    tab_resolved: HashMap<String, std::path::PathBuf>,
+    source_watch: SourceWatch,
+    // Shown files changed on disk since loaded, waiting for Reload
+    source_changed: HashSet<String>,
+
+    // Logpoints
+    pending_log: Option<PendingLog>,
+    log_edit: Option<(u32, String)>,
+    // Breakpoint command list being edited: (id, one command per line)
+    cmds_edit: Option<(u32, String)>,
+    // Breakpoint condition being edited, with its clause builder
+    cond_edit: Option<CondEdit>,
+    // An ambiguous `file:line` breakpoint: (its spec, line, full paths it could mean)
+    bp_choices: Option<(BreakpointSpec, u32, Vec<String>)>,
+    // Breakpoint inserts sent, by MI command, to retry one GDB found ambiguous
+    bp_inserts: HashMap<String, BreakpointSpec>,
+
+    // Register editing: (name, text) being edited, (name, value) awaiting confirmation
+    reg_edit: Option<(String, String)>,
+    reg_confirm: Option<(String, String)>,
+    /// Memory view: address expression, byte being edited, write to confirm
+    mem_addr: String,
+    mem_edit: Option<(u64, String)>,
+    mem_confirm: Option<(u64, Vec<u8>)>,
+    /// Welcome screen fields: executable, process id, core file
+    welcome_exe: String,
+    welcome_pid: String,
+    welcome_core: String,
+    /// Region re-read on every stop, open or not: (address, length)
+    mem_watch: Option<(u64, u32)>,
+    mem_watch_len: u32,
+    /// Bytes of the shown block that differ from the previous read
+    mem_changed: Vec<bool>,
+    open_memory: bool,
+
+    // Breakpoint script import/export; `bp_pending` inserts every breakpoint with -f
+    bp_script_path: String,
+    bp_pending: bool,
+    // rbreak: regex being typed, and the breakpoint count before the last one ran
+    rbreak_input: String,
+    rbreak_base: Option<usize>,
+
+    source_dir_input: String,
+    // New substitute-path rule being typed: (from, to)
+    subst_input: (String, String),
+    // Started with a source file instead of a binary: the path typed so far
+    exe_prompt: Option<String>,
+    // gdbserver link: address being typed, and the live connection
+    remote_input: String,
+    remote: RemoteLink,
+
+    // Instruction steps since the last Run/Restart; `insn_step_pending` until the stop arrives
+    step_count: u64,
+    insn_step_pending: bool,
+
+    // Run: stop at the entry point first (`entry_symbol` empty = first instruction)
+    break_at_entry: bool,
+    entry_symbol: String,
+    // Launch config the last Run applied, and GDB's working directory before any
+    launch_applied: Option<LaunchConfig>,
+    start_dir: String,
+
+    // Data tab: Set PC address being typed, and the one waiting for confirmation
+    pc_input: String,
+    pc_confirm: Option<u64>,
+
+    // Data tab: address/symbol to disassemble; follow_pc re-disassembles at $pc on every stop
+    disasm_addr: String,
+    disasm_follow_pc: bool,
+
+    // Data tab: a pinned range, disassembled once and kept while stepping in it
+    focus: Option<FocusRegion>,
+    focus_input: (String, String),
+    focus_disasm: Vec<AsmLine>,
+    focus_pending: bool,
+
+    // Panel sizes of a preset just picked, forced for one frame: (right width, console height)
+    panel_resize: Option<(f32, f32)>,
 }
 
 impl App {
     pub fn new(
         state: DebuggerState,
+        settings: Settings,
         event_rx: Receiver<DebuggerEvent>,
         cmd_tx: Sender<Command>,
     ) -> Self {
+        // GDB keeps these across `file`/`run`, so once per session is enough
+        for cmd in settings.gdb_commands() {
+            let _ = cmd_tx.send(cmd);
+        }
+        let (source_watch, watch_error) = SourceWatch::new();
+        let mut console_log = ConsoleLog::default();
+        if let Some(e) = watch_error {
+            console_log.push(format!(
+                "[UI] ⚠ Source files won't be watched for changes: {e}"
+            ));
+        }
         Self {
             state,
+            settings,
             event_rx,
             cmd_tx,
             console_input: String::new(),
-            console_log: Vec::new(),
+            console_log,
+            console_tab: ConsoleTab::Gdb,
+            stop_output: String::new(),
+            new_stop_cmd: String::new(),
+            console_history: Vec::new(),
+            command_log: Vec::new(),
+            hit_log: Vec::new(),
+            visits: VisitCounts::default(),
+            started: Instant::now(),
+            changes_prev: Snapshot::default(),
+            changes_cur: Snapshot::default(),
+            profile: StepProfile::default(),
+            crash_report: None,
+            crash_captured: false,
             watch_tab: WatchTab::Watch,
+            locals_filter: String::new(),
+            receiver_first: true,
+            receiver_expr: None,
+            receiver_value: None,
+            selected_frame: 0,
+            nav: None,
+            stack_cursor: 0,
+            tree_cursor: 0,
+            tree_open: HashSet::new(),
+            watch_creating: HashSet::new(),
+            frame_eval_input: String::new(),
+            eval_input: String::new(),
+            watch_input: String::new(),
+            cast_expr: String::new(),
+            cast_type: String::new(),
+            last_eval: None,
+            last_eval_result: None,
+            last_eval_pending: false,
+            frame_eval: None,
+            asm_line_funcs: HashSet::new(),
+            stale: StaleViews::default(),
+            pretty_printing: false,
             open_bp: true,
             open_cmd: false,
+            new_cmd: (String::new(), String::new()),
+            open_cmd_log: false,
+            open_mi_log: false,
+            mi_log: MiLog::default(),
+            mi_unhandled_only: false,
+            open_hit_log: false,
+            open_changes: false,
+            errors: ErrorList::default(),
+            errno: None,
+            syscall_ret: None,
+            smart_step: SmartStep::default(),
+            step_until: StepUntil::default(),
+            step_until_input: String::new(),
+            compare: Compare::default(),
+            query: None,
+            stalled: None,
+            open_errors: false,
+            open_launch: false,
+            open_mappings: false,
+            open_bookmarks: false,
+            open_profile: false,
+            open_step_until: false,
+            open_compare: false,
             open_struct: false,
             open_stack: true,
             open_files: false,
             open_thread: false,
+            open_find: false,
+            open_symbols: false,
+            open_checkpoints: false,
+            find_query: String::new(),
+            find_exts: "c,h,cc,cpp,hpp,rs,s".into(),
+            find_hits: Vec::new(),
+            symbols: SymbolSearch::default(),
+            find_rx: None,
+            open_tabs: Vec::new(),
+            active_tab: None,
+            scroll_to_line: None,
+            hovered_line: None,
+            bookmark_cursor: None,
+            stack_more: false,
+            stack_loading: false,
             source_lines: Vec::new(),
             source_file: None,
+            source_resolved: None,
+            source_cache: HashMap::new(),
+            tab_resolved: HashMap::new(),
+            source_watch,
+            source_changed: HashSet::new(),
+            pending_log: None,
+            log_edit: None,
+            cmds_edit: None,
+            cond_edit: None,
+            bp_choices: None,
+            bp_inserts: HashMap::new(),
+            reg_edit: None,
+            reg_confirm: None,
+            mem_addr: String::new(),
+            mem_edit: None,
+            mem_confirm: None,
+            welcome_exe: String::new(),
+            welcome_pid: String::new(),
+            welcome_core: String::new(),
+            mem_watch: None,
+            mem_watch_len: READ_LEN,
+            mem_changed: vec![],
+            open_memory: false,
+            bp_script_path: "breakpoints.gdb".into(),
+            bp_pending: false,
+            rbreak_input: String::new(),
+            rbreak_base: None,
+            source_dir_input: String::new(),
+            subst_input: (String::new(), String::new()),
+            remote_input: String::new(),
+            exe_prompt: None,
+            remote: RemoteLink::default(),
+            step_count: 0,
+            insn_step_pending: false,
+            break_at_entry: false,
+            entry_symbol: String::new(),
+            launch_applied: None,
+            // GDB inherits ours
+            start_dir: std::env::current_dir()
+                .map(|d| d.display().to_string())
+                .unwrap_or_default(),
+            pc_input: String::new(),
+            pc_confirm: None,
+            disasm_addr: String::new(),
+            disasm_follow_pc: true,
+            focus: None,
+            focus_input: (String::new(), String::new()),
+            focus_disasm: vec![],
+            focus_pending: false,
+            panel_resize: None,
         }
     }
 
@@ -97,428 +552,4731 @@ impl App {
         let _ = self.cmd_tx.send(cmd);
     }
 
-    fn load_source_if_needed(&mut self) {
-        let target_file = match self.state.current_file() {
-            Some(f) => f.to_owned(),
-            None => {
-                self.source_lines.clear();
-                self.source_file = None;
-                return;
+    /// Sends a user action, recording its CLI form in the command log.
+    fn act(&mut self, cmd: Command) {
+        match &cmd {
+            Command::Run | Command::StartInstruction | Command::Restart => {
+                self.step_count = 0;
+                self.crash_captured = false;
+                self.changes_prev = Snapshot::default();
+                self.changes_cur = Snapshot::default();
+                self.errors.clear();
+                self.smart_step.cancel();
+                self.step_until.cancel();
+                for launch in self.launch_commands() {
+                    self.act(launch);
+                }
             }
-        };
+            Command::StepInstruction | Command::NextInstruction => self.insn_step_pending = true,
+            Command::InsertBreakpoint(spec) => {
+                self.bp_inserts.insert(command_to_mi(&cmd), spec.clone());
+            }
+            _ => {}
+        }
+        if let Some(console) = command_to_console(&cmd) {
+            self.command_log.push(console);
+        }
+        self.send(cmd);
+    }
 
-        if self.source_file.as_deref() == Some(&target_file) {
-            return;
+    /// The active launch config of the loaded executable, as commands.
+    fn launch_commands(&mut self) -> Vec<Command> {
+        let config = self
+            .state
+            .persistent
+            .executable
+            .as_ref()
+            .and_then(|exe| self.settings.launch.get(exe))
+            .and_then(LaunchConfigs::active)
+            .cloned();
+        // Nothing to apply, and nothing applied before to undo
+        if config.is_none() && self.launch_applied.is_none() {
+            return vec![];
         }
+        let config = config.unwrap_or_default();
+        let cmds = config.commands(self.launch_applied.as_ref(), &self.start_dir);
+        self.launch_applied = Some(config);
+        cmds
+    }
 
-        self.console_log
-            .push(format!("[DEBUG] GDB says file is: {:?}", target_file));
-        self.console_log.push(format!(
-            "[DEBUG] Current dir: {:?}",
-            std::env::current_dir()
-        ));
+    /// Arguments, environment, working directory and redirections of the
+    /// loaded executable, applied right before each Run.
+    fn launch_panel(&mut self, ui: &mut egui::Ui) {
+        let Some(exe) = self.state.persistent.executable.clone() else {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.label(m("No executable loaded", 11.0, TXT_DIM).italics());
+            });
+            return;
+        };
+        let table = self.settings.launch.entry(exe).or_default();
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let current = table.active().map_or("(defaults)", |c| c.name.as_str());
+            egui::ComboBox::from_id_salt("launch_pick")
+                .selected_text(m(current, 11.0, TXT))
+                .show_ui(ui, |ui| {
+                    for (i, config) in table.configs.iter().enumerate() {
+                        ui.selectable_value(&mut table.active, i, &config.name);
+                    }
+                });
+            if ui
+                .small_button("+")
+                .on_hover_text("New configuration")
+                .clicked()
+            {
+                let name = format!("config {}", table.configs.len() + 1);
+                table.configs.push(LaunchConfig::named(&name));
+                table.active = table.configs.len() - 1;
+            }
+            if table.active().is_some()
+                && ui
+                    .small_button("✕")
+                    .on_hover_text("Delete this configuration")
+                    .clicked()
+            {
+                table.remove_active();
+            }
+        });
+        let Some(config) = table.configs.get_mut(table.active) else {
+            return;
+        };
 
-        let content = self.try_load_source(&target_file);
+        egui::Grid::new("launch_grid")
+            .num_columns(2)
+            .spacing([6.0, 2.0])
+            .show(ui, |ui| {
+                let fields = [
+                    ("name", &mut config.name, ""),
+                    ("args", &mut config.args, "--flag value"),
+                    ("cwd", &mut config.cwd, "GDB's"),
+                    ("stdin", &mut config.stdin, "terminal"),
+                    ("stdout", &mut config.stdout, "terminal"),
+                ];
+                for (label, text, hint) in fields {
+                    ui.horizontal(|ui| {
+                        ui.add_space(8.0);
+                        ui.label(m(label, 11.0, TXT_DIM));
+                    });
+                    ui.add(
+                        TextEdit::singleline(text)
+                            .hint_text(hint)
+                            .font(FontId::monospace(11.0))
+                            .desired_width(180.0),
+                    );
+                    ui.end_row();
+                }
+            });
 
-        match content {
-            Some(text) => {
-                self.source_lines = text
-                    .lines()
-                    .enumerate()
-                    .map(|(i, line)| SourceLine {
-                        number: (i + 1) as u32,
-                        text: line.to_owned(),
-                    })
-                    .collect();
-                self.source_file = Some(target_file.clone());
-                self.console_log.push(format!(
-                    "[UI] ✓ Loaded {} ({} lines)",
-                    target_file,
-                    self.source_lines.len()
-                ));
-            }
-            None => {
-                self.console_log
-                    .push(format!("[UI] ✗ Could not find source file: {target_file}"));
-                self.console_log.push("[UI] Tried:".into());
-                self.console_log.push(format!("  1. {target_file}"));
-                if let Some(filename) = std::path::Path::new(&target_file).file_name() {
-                    self.console_log
-                        .push(format!("  2. {}", filename.to_string_lossy()));
-                    self.console_log
-                        .push(format!("  3. src/{}", filename.to_string_lossy()));
+        let mut remove = None;
+        for (i, (name, value)) in config.env.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.add(
+                    TextEdit::singleline(name)
+                        .hint_text("NAME")
+                        .font(FontId::monospace(11.0))
+                        .desired_width(90.0),
+                );
+                ui.label(m("=", 11.0, TXT_DIM));
+                ui.add(
+                    TextEdit::singleline(value)
+                        .font(FontId::monospace(11.0))
+                        .desired_width(110.0),
+                );
+                if ui.small_button("✕").clicked() {
+                    remove = Some(i);
                 }
-                self.source_lines.clear();
-                self.source_file = None;
-            }
+            });
         }
+        if let Some(i) = remove {
+            config.env.remove(i);
+        }
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if ui.small_button("+ env var").clicked() {
+                config.env.push((String::new(), String::new()));
+            }
+        });
     }
 
-    fn try_load_source(&self, path: &str) -> Option<String> {
-        // 1. Intentar path tal cual (absoluto o relativo desde CWD)
-        if let Ok(content) = std::fs::read_to_string(path) {
-            return Some(content);
-        }
+    /// Asks for the next `STACK_PAGE` frames starting at level `low`.
+    fn request_stack_page(&mut self, low: u32) {
+        self.stack_loading = true;
+        let (low, high) = (Some(low), Some(low + STACK_PAGE - 1));
+        self.send(Command::RequestStack { low, high });
+        self.send(Command::RequestFrameArguments { low, high });
+    }
 
-        if let Some(filename) = std::path::Path::new(path).file_name() {
-            if let Ok(content) = std::fs::read_to_string(filename) {
-                return Some(content);
-            }
+    fn request_visible(&mut self) {
+        if !self.state.is_paused() {
+            return;
         }
-
-        if let Some(filename) = std::path::Path::new(path).file_name() {
-            let src_path = format!("src/{}", filename.to_string_lossy());
-            if let Ok(content) = std::fs::read_to_string(&src_path) {
-                return Some(content);
+        for cmd in self.stale.requests_for(self.watch_tab) {
+            self.send(cmd);
+        }
+        if self.settings.layout.central() != CentralView::Source || self.asm_fallback() {
+            for cmd in self.stale.requests_for(WatchTab::Data) {
+                self.send(cmd);
             }
         }
-
-        None
     }
-}
 
-// ─── eframe::App ──────────────────────────────────────────────────────────────
+    /// Stopped where there's no source, e.g. at the entry point after
+    /// `starti`: the source view shows the disassembly instead.
+    fn asm_fallback(&self) -> bool {
+        self.active_tab.is_none()
+            && self
+                .state
+                .pause
+                .as_ref()
+                .is_some_and(|p| p.frame.file.is_none())
+    }
 
-impl eframe::App for App {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        apply_theme(ctx);
+    /// Sizes the panels, picks the tab and opens the sections `preset` is about.
+    fn apply_preset(&mut self, preset: LayoutPreset) {
+        self.settings.layout = preset;
+        self.panel_resize = Some(preset.panel_sizes());
+        let asm = preset != LayoutPreset::Source;
+        self.watch_tab = if preset == LayoutPreset::Assembly {
+            WatchTab::Registers
+        } else {
+            WatchTab::Watch
+        };
+        self.open_bp = true;
+        self.open_stack = true;
+        self.open_files = !asm;
+        self.open_memory = preset == LayoutPreset::Assembly;
+        self.open_mappings = false;
+        self.request_visible();
+    }
 
-        while let Ok(event) = self.event_rx.try_recv() {
-            match event {
-                DebuggerEvent::State(s) => {
-                    let was_paused = matches!(s, crate::state::StateEvent::ProgramPaused { .. });
-                    let was_loaded = matches!(s, crate::state::StateEvent::ProgramLoaded { .. });
-                    self.state.apply(s);
-                    self.load_source_if_needed();
-                    if was_loaded {
-                        self.send(Command::RequestRegisterNames);
-                    }
-                    if was_paused {
-                        self.send(Command::RequestLocals);
-                        self.send(Command::RequestRegisters);
-                        self.send(Command::RequestDisasm);
+    fn layout_picker(&mut self, ui: &mut egui::Ui) {
+        let mut picked = None;
+        egui::ComboBox::from_id_salt("layout_preset")
+            .selected_text(m(self.settings.layout.label(), 11.0, TXT_MUTED))
+            .show_ui(ui, |ui| {
+                for preset in LayoutPreset::ALL {
+                    if ui
+                        .selectable_label(preset == self.settings.layout, preset.label())
+                        .clicked()
+                    {
+                        picked = Some(preset);
                     }
                 }
-                DebuggerEvent::Ui(UiEvent::ConsoleOutput(text)) => {
-                    self.console_log.push(text);
+            })
+            .response
+            .on_hover_text("Layout preset");
+        if let Some(preset) = picked {
+            self.apply_preset(preset);
+        }
+    }
+
+    fn disasm_listing(&self, ui: &mut egui::Ui) {
+        let lines = self.shown_disasm();
+        if lines.is_empty() {
+            ui.label(m("Not paused", 11.0, TXT_DIM).italics());
+            return;
+        }
+        let arch = Arch::from_register_names(&self.state.register_names);
+        let pc = self.state.current_addr();
+        let mut prev_line = None;
+        for asm in lines {
+            let current = asm.current || pc == Some(asm.addr);
+            let col = if current { TXT_HL } else { TXT };
+            // Source line, only where a new line's block starts
+            let src_line = self.state.asm_source_lines.get(&asm.addr);
+            let gutter = match src_line {
+                Some(l) if src_line != prev_line => format!("{l:>5}"),
+                _ => " ".repeat(5),
+            };
+            prev_line = src_line;
+            ui.horizontal(|ui| {
+                ui.label(m(&gutter, 11.0, TXT_DIM));
+                if current {
+                    ui.label(m("▶", 11.0, ACCENT));
+                } else {
+                    ui.add_space(14.0);
                 }
-                DebuggerEvent::Ui(UiEvent::GdbError(err)) => {
-                    self.console_log.push(format!("[ERROR] {err}"));
+                ui.label(m(&format!("0x{:x}", asm.addr), 11.0, TXT_DIM));
+                ui.add_space(6.0);
+                let (mnemonic, operands) = asm
+                    .inst
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((&asm.inst, ""));
+                let resp = ui.label(m(mnemonic, 11.0, col));
+                if let Some(doc) = arch.and_then(|a| describe_insn(a, mnemonic)) {
+                    resp.on_hover_text(doc);
                 }
-            }
+                ui.label(m(operands.trim(), 11.0, col));
+            });
         }
+    }
 
-        ctx.request_repaint();
+    /// Template of the logpoint that caused this stop, if any.
+    fn logpoint_hit(&self, event: &StateEvent) -> Option<(u32, String)> {
+        let StateEvent::ProgramPaused { pause } = event else {
+            return None;
+        };
+        let StopReason::BreakpointHit(id) = pause.stop_reason else {
+            return None;
+        };
+        let bp = self
+            .state
+            .persistent
+            .breakpoints
+            .iter()
+            .find(|b| b.id == id)?;
+        Some((id, bp.log_message.clone()?))
+    }
 
-        // ── TOP BAR ───────────────────────────────────────────────────────────
-        egui::TopBottomPanel::top("top_bar")
-            .frame(flat(BG_TOPBAR).inner_margin(Margin {
-                left: 8,
-                right: 8,
-                top: 4,
-                bottom: 4,
-            }))
-            .show(ctx, |ui| {
-                ui.horizontal(|ui| {
-                    ui.label(m("GDB GUI", 13.0, ACCENT).strong());
-                    ui.add(egui::Separator::default().vertical());
+    fn start_logpoint(&mut self, id: u32, template: &str) {
+        let log = PendingLog::new(id, template);
+        let exprs = log.expressions();
+        if exprs.is_empty() {
+            self.finish_logpoint(log);
+            return;
+        }
+        for expr in exprs {
+            self.send(Command::Evaluate(expr));
+        }
+        self.pending_log = Some(log);
+    }
 
-                    if tbtn(ui, "Run", true).clicked() {
-                        self.send(Command::Run);
-                    }
-                    if tbtn(ui, "Continue", false).clicked() {
-                        self.send(Command::Continue);
-                    }
-                    if tbtn(ui, "Step", false).clicked() {
-                        self.send(Command::Step);
-                    }
-                    if tbtn(ui, "Next", false).clicked() {
-                        self.send(Command::Next);
-                    }
-                    if tbtn(ui, "Finish", false).clicked() {
-                        self.send(Command::Finish);
-                    }
-                    if tbtn(ui, "Restart", false).clicked() {
-                        self.send(Command::Restart);
-                    }
+    /// Hands an `-data-evaluate-expression` answer to whoever asked for it.
+    /// The logpoint goes first: the inferior stays stopped until it's done.
+    fn on_evaluation_result(&mut self, expr: String, value: Result<String, String>) {
+        if let Some(log) = &mut self.pending_log
+            && log.fill(&expr, value.clone())
+        {
+            if log.is_complete()
+                && let Some(log) = self.pending_log.take()
+            {
+                self.finish_logpoint(log);
+            }
+        } else if expr == ERRNO_EXPR {
+            self.errno = Some(value);
+        } else if expr == FRAME_EXPR && self.changes_cur.frame.is_none() {
+            self.changes_cur.frame = value.ok();
+        } else if let Some(ret) = &mut self.syscall_ret
+            && ret.value.is_none()
+            && ret.register == expr
+        {
+            ret.value = Some(value);
+        } else if self.step_until.awaits(&expr) {
+            let next = self.step_until.on_result(value);
+            self.step_until_next(next);
+        } else if self.compare.awaits(&expr) {
+            self.compare.fill(&expr, value);
+        } else if let Some(eval) = &mut self.frame_eval
+            && eval.awaits(&expr)
+        {
+            for cmd in eval.on_result(value) {
+                self.send(cmd);
+            }
+        } else if self.receiver_expr.as_deref() == Some(&expr) && self.receiver_value.is_none() {
+            self.receiver_value = Some(value);
+        } else if self.last_eval_pending && self.last_eval.as_deref() == Some(&expr) {
+            self.last_eval_pending = false;
+            self.last_eval_result = Some(value);
+        }
+    }
 
-                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                        let (r, _) = ui.allocate_exact_size(Vec2::splat(12.0), Sense::hover());
-                        let color = if self.state.is_running() {
-                            ACCENT
-                        } else if self.state.is_paused() {
-                            TXT_YELLOW
-                        } else {
-                            TXT_DIM
-                        };
-                        ui.painter().rect_filled(r, 2.0, color);
-                        ui.add_space(6.0);
+    fn finish_logpoint(&mut self, log: PendingLog) {
+        self.console_log
+            .push(format!("[log #{}] {}", log.bp_id, log.message()));
+        self.send(Command::Continue);
+    }
 
-                        let status = match &self.state.program {
-                            crate::state::ProgramState::NoProgramLoaded => "No program loaded",
-                            crate::state::ProgramState::ProgramLoaded => "Loaded",
-                            crate::state::ProgramState::Running => "Running",
-                            crate::state::ProgramState::Paused => "Paused",
-                            crate::state::ProgramState::Exited { .. } => "Exited",
-                        };
+    fn logpoint_editor(&mut self, ui: &mut egui::Ui) {
+        let Some((id, text)) = &mut self.log_edit else {
+            return;
+        };
+        let id = *id;
+        let mut submit = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&format!("#{id} log:"), 11.0, TXT_DIM));
+            let resp = ui.add(
+                TextEdit::singleline(text)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("i = {i}, p = {p->x}")
+                    .desired_width(ui.available_width() - 8.0),
+            );
+            if resp.lost_focus() {
+                // Enter guarda, Escape (o perder el foco) cancela
+                submit = Some(ui.input(|i| i.key_pressed(Key::Enter)));
+            }
+        });
+        match submit {
+            Some(true) => {
+                let message = self
+                    .log_edit
+                    .take()
+                    .map(|(_, t)| t.trim().to_owned())
+                    .filter(|t| !t.is_empty());
+                self.state.apply(StateEvent::LogMessageSet { id, message });
+            }
+            Some(false) => self.log_edit = None,
+            None => {}
+        }
+    }
 
-                        let location = if let (Some(file), Some(func)) =
-                            (self.state.current_file(), self.state.current_function())
-                        {
-                            format!("{file} — {func}")
-                        } else {
-                            status.to_owned()
-                        };
+    fn breakpoint_commands_editor(&mut self, ui: &mut egui::Ui) {
+        let Some((id, text)) = &mut self.cmds_edit else {
+            return;
+        };
+        let id = *id;
+        let mut submit = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&format!("#{id} commands, one per line:"), 11.0, TXT_DIM));
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.add(
+                TextEdit::multiline(text)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("silent\nprint i\ncontinue")
+                    .desired_rows(3)
+                    .desired_width(ui.available_width() - 8.0),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if ui.small_button("Save").clicked() {
+                submit = Some(true);
+            }
+            if ui.small_button("Cancel").clicked() {
+                submit = Some(false);
+            }
+        });
+        match submit {
+            Some(true) => {
+                let commands = self
+                    .cmds_edit
+                    .take()
+                    .map(|(_, t)| {
+                        t.lines()
+                            .map(str::trim)
+                            .filter(|l| !l.is_empty())
+                            .map(str::to_owned)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                // GDB answers with =breakpoint-modified carrying the new script
+                self.act(Command::SetBreakpointCommands { id, commands });
+            }
+            Some(false) => self.cmds_edit = None,
+            None => {}
+        }
+    }
 
-                        ui.label(m(&location, 11.0, TXT_MUTED));
+    fn breakpoint_condition_editor(&mut self, ui: &mut egui::Ui) {
+        // Arguments and locals of the selected frame: the breakpoint's own
+        // function when paused on it
+        let names = {
+            let frame = self.state.pause.as_ref().and_then(|p| {
+                p.stack
+                    .iter()
+                    .find(|f| f.level == self.selected_frame)
+                    .or((self.selected_frame == 0).then_some(&p.frame))
+            });
+            candidate_names(frame, &self.state.locals)
+        };
+        let Some(edit) = &mut self.cond_edit else {
+            return;
+        };
+        let mut submit = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&format!("#{} condition:", edit.id), 11.0, TXT_DIM));
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut edit.text)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("i > 3 && buf != 0")
+                    .desired_width(ui.available_width() - 8.0),
+            );
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                submit = Some(true);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if names.is_empty() {
+                ui.add(
+                    TextEdit::singleline(&mut edit.clause.name)
+                        .font(FontId::monospace(11.0))
+                        .hint_text("name")
+                        .desired_width(70.0),
+                );
+            } else {
+                egui::ComboBox::from_id_salt("cond_name")
+                    .width(70.0)
+                    .selected_text(m(&edit.clause.name, 11.0, TXT))
+                    .show_ui(ui, |ui| {
+                        for name in &names {
+                            ui.selectable_value(&mut edit.clause.name, name.clone(), name);
+                        }
                     });
+            }
+            egui::ComboBox::from_id_salt("cond_op")
+                .width(36.0)
+                .selected_text(m(edit.clause.op.as_str(), 11.0, TXT))
+                .show_ui(ui, |ui| {
+                    for op in Op::ALL {
+                        ui.selectable_value(&mut edit.clause.op, op, op.as_str());
+                    }
                 });
-            });
-
-        // ── CONSOLE (bottom) ──────────────────────────────────────────────────
-        egui::TopBottomPanel::bottom("console")
-            .resizable(true)
-            .min_height(50.0)
-            .default_height(180.0)
-            .frame(flat(BG_CONSOLE))
-            .show(ctx, |ui| {
-                // Header fijo arriba
-                Frame::new()
-                    .fill(BG_TOPBAR)
-                    .inner_margin(Margin {
-                        left: 8,
-                        right: 8,
-                        top: 3,
-                        bottom: 3,
-                    })
-                    .show(ui, |ui| {
-                        ui.label(m("Console", 11.0, TXT_MUTED));
+            ui.add(
+                TextEdit::singleline(&mut edit.clause.value)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("value")
+                    .desired_width(ui.available_width() - 40.0),
+            );
+            if ui
+                .add_enabled(edit.clause.expr().is_some(), egui::Button::new("+").small())
+                .on_hover_text("AND this comparison onto the condition")
+                .clicked()
+            {
+                edit.add_clause();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if ui.small_button("Save").clicked() {
+                submit = Some(true);
+            }
+            if ui.small_button("Cancel").clicked() {
+                submit = Some(false);
+            }
+        });
+        match submit {
+            Some(true) => {
+                if let Some(edit) = self.cond_edit.take() {
+                    // GDB answers with =breakpoint-modified carrying `cond`
+                    self.act(Command::SetBreakpointCondition {
+                        id: edit.id,
+                        condition: edit.condition(),
                     });
-                hl(ui);
+                }
+            }
+            Some(false) => self.cond_edit = None,
+            None => {}
+        }
+    }
 
-                ui.with_layout(Layout::bottom_up(Align::LEFT), |ui| {
-                    // Input line
-                    Frame::new()
-                        .fill(BG_CONSOLE)
-                        .inner_margin(Margin {
-                            left: 8,
-                            right: 8,
-                            top: 3,
-                            bottom: 3,
-                        })
-                        .show(ui, |ui| {
-                            ui.horizontal(|ui| {
-                                ui.label(m("(gdb)", 12.0, ACCENT));
-                                ui.add_space(4.0);
-                                let resp = ui.add(
-                                    TextEdit::singleline(&mut self.console_input)
-                                        .font(FontId::monospace(12.0))
-                                        .desired_width(ui.available_width())
-                                        .frame(false)
-                                        .text_color(Color32::from_rgb(0xe0, 0xe0, 0xe0)),
-                                );
-                                if resp.lost_focus() && ctx.input(|i| i.key_pressed(Key::Enter)) {
-                                    let raw = self.console_input.trim().to_owned();
-                                    if !raw.is_empty() {
-                                        self.send(Command::Raw(raw));
-                                        self.console_input.clear();
-                                    }
-                                    resp.request_focus();
-                                }
-                            });
-                        });
+    fn submit_register_edit(&mut self) {
+        let Some((name, text)) = self.reg_edit.take() else {
+            return;
+        };
+        match parse_register_value(&text) {
+            Some(value) if needs_confirmation(&name) => self.reg_confirm = Some((name, value)),
+            Some(value) => self.write_register(name, value),
+            None => self
+                .console_log
+                .push(format!("[UI] ✗ Invalid value for ${name}: {text:?}")),
+        }
+    }
 
-                    hl(ui);
+    fn write_register(&mut self, name: String, value: String) {
+        self.act(Command::SetRegister { name, value });
+        self.send(Command::RequestRegisters);
+    }
 
-                    ScrollArea::vertical()
-                        .id_salt("con_log")
-                        .stick_to_bottom(true)
-                        .show(ui, |ui| {
-                            ui.add_space(2.0);
-                            for line in &self.console_log {
-                                ui.horizontal(|ui| {
-                                    ui.add_space(6.0);
-                                    ui.label(m(line, 11.0, TXT));
-                                });
-                            }
-                            ui.add_space(2.0);
-                        });
-                });
-            });
+    fn register_confirm(&mut self, ui: &mut egui::Ui) {
+        let Some((name, value)) = &self.reg_confirm else {
+            return;
+        };
+        let mut answer = None;
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&format!("Write {value} to ${name}?"), 11.0, RED));
+            if ui.button("Write").clicked() {
+                answer = Some(true);
+            }
+            if ui.button("Cancel").clicked() {
+                answer = Some(false);
+            }
+        });
+        if let Some(ok) = answer
+            && let Some((name, value)) = self.reg_confirm.take()
+            && ok
+        {
+            self.write_register(name, value);
+        }
+    }
 
-        // ── RIGHT PANEL ───────────────────────────────────────────────────────
-        egui::SidePanel::right("right_panel")
-            .resizable(true)
-            .min_width(180.0)
-            .default_width(280.0)
-            .frame(flat(BG_PANEL))
-            .show(ctx, |ui| {
-                // Upper collapsible sections
-                ScrollArea::vertical()
-                    .id_salt("right_upper")
-                    .max_height(ui.available_height() * 0.52)
-                    .show(ui, |ui| {
-                        ui.set_min_width(ui.available_width());
+    fn read_memory(&mut self) {
+        if let Some((addr, len)) = self.mem_watch {
+            self.send(Command::ReadMemory {
+                addr: format!("0x{addr:x}"),
+                len,
+            });
+            return;
+        }
+        let addr = self.mem_addr.trim();
+        if !addr.is_empty() {
+            self.send(Command::ReadMemory {
+                addr: addr.to_owned(),
+                len: READ_LEN,
+            });
+        }
+    }
 
-                        // BREAKPOINTS ──────────────────────────────────────────
-                        sec_hdr(ui, "Breakpoints", &mut self.open_bp);
-                        if self.open_bp {
-                            egui::Grid::new("bp_grid")
-                                .num_columns(3)
-                                .spacing([8.0, 2.0])
-                                .show(ui, |ui| {
-                                    for h in ["File", "Line", ""] {
-                                        ui.label(m(h, 11.0, TXT_DIM));
-                                    }
-                                    ui.end_row();
+    /// Re-reads the block on display. The address field may have been edited
+    /// since, or hold an expression like `$sp` that now points elsewhere.
+    fn refresh_memory(&mut self) {
+        if self.mem_watch.is_some() {
+            self.read_memory();
+        } else if let Some(block) = &self.state.memory {
+            self.send(Command::ReadMemory {
+                addr: format!("0x{:x}", block.addr),
+                len: READ_LEN,
+            });
+        }
+    }
 
-                                    for bp in &self.state.persistent.breakpoints {
-                                        // Nombre corto del archivo
-                                        let short_file = bp
-                                            .file
-                                            .split('/')
-                                            .last()
-                                            .or_else(|| bp.file.split('\\').last())
-                                            .unwrap_or(&bp.file);
+    fn submit_memory_edit(&mut self) {
+        let Some((addr, text)) = self.mem_edit.take() else {
+            return;
+        };
+        match parse_hex_bytes(&text) {
+            Some(bytes) => self.mem_confirm = Some((addr, bytes)),
+            None => self
+                .console_log
+                .push(format!("[UI] ✗ Not hex bytes: {text:?}")),
+        }
+    }
 
-                                        ui.label(m(short_file, 12.0, TXT_CYAN));
-                                        ui.label(m(&bp.line.to_string(), 12.0, TXT_YELLOW));
-                                        if ui
-                                            .add(
-                                                egui::Button::new(m("×", 12.0, RED))
-                                                    .fill(Color32::TRANSPARENT)
-                                                    .stroke(Stroke::NONE),
-                                            )
-                                            .clicked()
-                                        {
-                                            self.send(Command::RemoveBreakpoint(bp.id));
-                                        }
-                                        ui.end_row();
-                                    }
-                                });
-                            ui.add_space(4.0);
-                        }
-                        hl(ui);
+    /// Hex dump of the last read. Double-click a byte to type over it and the
+    /// ones after; every write asks first.
+    fn memory_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.mem_addr)
+                    .hint_text("&buf, $sp, 0x…")
+                    .font(FontId::monospace(11.0))
+                    .desired_width(140.0),
+            );
+            let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if (ui.small_button("Read").clicked() || enter) && self.state.pause.is_some() {
+                self.mem_watch = None;
+                self.read_memory();
+            }
+        });
+        let Some(block) = &self.state.memory else {
+            return;
+        };
+        let mut watch = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            match self.mem_watch {
+                Some((addr, len)) => {
+                    ui.label(m(
+                        &format!("● watching 0x{addr:x}, {len} bytes"),
+                        11.0,
+                        TXT_YELLOW,
+                    ))
+                    .on_hover_text("Re-read on every stop; changed bytes are highlighted");
+                    if ui.small_button("Unwatch").clicked() {
+                        watch = Some(None);
+                    }
+                }
+                None => {
+                    ui.add(
+                        egui::DragValue::new(&mut self.mem_watch_len)
+                            .range(1..=MAX_WATCH_LEN)
+                            .suffix(" bytes"),
+                    );
+                    if ui
+                        .small_button("Watch")
+                        .on_hover_text(
+                            "Re-read this region on every stop and highlight the bytes \
+                             that changed, without using a hardware watchpoint",
+                        )
+                        .clicked()
+                    {
+                        watch = Some(Some((block.addr, self.mem_watch_len)));
+                    }
+                }
+            }
+        });
+        if let Some(watch) = watch {
+            self.mem_watch = watch;
+            if watch.is_some() {
+                self.read_memory();
+            }
+        }
+        let Some(block) = &self.state.memory else {
+            return;
+        };
 
-                        // COMMANDS ──────────────────────────────────────────────
-                        sec_hdr(ui, "Commands", &mut self.open_cmd);
-                        if self.open_cmd {
-                            for cmd_str in
-                                &["info locals", "bt full", "info registers", "info threads"]
-                            {
-                                if ui
+        let mut start_edit = None;
+        let mut submit = None;
+        egui::Grid::new("memory_grid")
+            .num_columns(ROW + 2)
+            .spacing([4.0, 1.0])
+            .show(ui, |ui| {
+                for (i, row) in block.bytes.chunks(ROW).enumerate() {
+                    let row_addr = block.addr + (i * ROW) as u64;
+                    ui.label(m(&format!("{row_addr:012x}"), 10.0, TXT_DIM));
+                    for (j, byte) in row.iter().enumerate() {
+                        let addr = row_addr + j as u64;
+                        match &mut self.mem_edit {
+                            Some((editing, text)) if *editing == addr => {
+                                let resp = ui.add(
+                                    TextEdit::singleline(text)
+                                        .font(FontId::monospace(10.5))
+                                        .desired_width(60.0),
+                                );
+                                resp.request_focus();
+                                if resp.lost_focus() {
+                                    submit = Some(ui.input(|i| i.key_pressed(Key::Enter)));
+                                }
+                            }
+                            _ => {
+                                let changed = self.mem_changed.get(i * ROW + j) == Some(&true);
+                                let color = if changed { TXT_YELLOW } else { TXT };
+                                let resp = ui
                                     .add(
-                                        egui::Button::new(m(cmd_str, 11.0, TXT_CYAN))
-                                            .fill(Color32::TRANSPARENT)
-                                            .stroke(Stroke::NONE)
-                                            .min_size(Vec2::new(ui.available_width(), 18.0)),
+                                        egui::Label::new(m(&format!("{byte:02x}"), 10.5, color))
+                                            .sense(Sense::click()),
                                     )
-                                    .clicked()
-                                {
-                                    self.send(Command::Raw(cmd_str.to_string()));
+                                    .on_hover_text(format!("0x{addr:x}"));
+                                if resp.double_clicked() {
+                                    start_edit = Some((addr, format!("{byte:02x}")));
                                 }
                             }
-                            ui.add_space(4.0);
                         }
-                        hl(ui);
+                    }
+                    ui.label(m(&ascii(row), 10.5, TXT_MUTED));
+                    ui.end_row();
+                }
+            });
+        if start_edit.is_some() {
+            self.mem_edit = start_edit;
+            self.mem_confirm = None;
+        }
+        match submit {
+            Some(true) => self.submit_memory_edit(),
+            Some(false) => self.mem_edit = None,
+            None => {}
+        }
+        self.memory_confirm(ui);
+    }
 
-                        // STRUCT ────────────────────────────────────────────────
-                        sec_hdr(ui, "Struct", &mut self.open_struct);
-                        if self.open_struct {
-                            ui.horizontal(|ui| {
-                                ui.add_space(8.0);
-                                ui.label(
-                                    RichText::new("No struct selected")
-                                        .color(TXT_DIM)
-                                        .font(FontId::monospace(11.0))
-                                        .italics(),
-                                );
-                            });
-                            ui.add_space(4.0);
-                        }
-                        hl(ui);
+    /// `info proc mappings`; a click opens the Memory view at the region.
+    fn mappings_panel(&mut self, ui: &mut egui::Ui) {
+        if !cfg!(target_os = "linux") {
+            ui.label(m("Needs a Linux process", 11.0, TXT_DIM).italics());
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if ui
+                .add_enabled(self.state.is_paused(), egui::Button::new("Refresh").small())
+                .on_disabled_hover_text("Pause the program first")
+                .clicked()
+            {
+                self.send(Command::RequestMappings);
+            }
+        });
+        if self.state.mappings.is_empty() {
+            ui.label(m("No regions read", 11.0, TXT_DIM).italics());
+            return;
+        }
+        let mut open = None;
+        ScrollArea::vertical()
+            .id_salt("mappings")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for region in &self.state.mappings {
+                    let text = format!(
+                        "0x{:x} {:>7} {:<4} {}",
+                        region.start,
+                        size_label(region.size()),
+                        region.perms,
+                        short_name(&region.path)
+                    );
+                    let label = egui::Label::new(m(&text, 11.0, TXT_MUTED)).sense(Sense::click());
+                    let hover = format!(
+                        "0x{:x}–0x{:x}, offset 0x{:x}\n{}",
+                        region.start, region.end, region.offset, region.path
+                    );
+                    if ui.add(label).on_hover_text(hover).clicked() {
+                        open = Some(region.start);
+                    }
+                }
+            });
+        if let Some(start) = open {
+            self.mem_addr = format!("0x{start:x}");
+            self.mem_watch = None;
+            self.open_memory = true;
+            self.read_memory();
+        }
+    }
 
-                        // STACK ─────────────────────────────────────────────────
-                        sec_hdr(ui, "Stack", &mut self.open_stack);
-                        if self.open_stack {
-                            if let Some(pause) = &self.state.pause {
-                                egui::Grid::new("stack_grid")
-                                    .num_columns(3)
-                                    .spacing([6.0, 2.0])
-                                    .show(ui, |ui| {
-                                        for h in ["#", "Function", "Location"] {
-                                            ui.label(m(h, 11.0, TXT_DIM));
-                                        }
-                                        ui.end_row();
+    fn memory_confirm(&mut self, ui: &mut egui::Ui) {
+        let Some((addr, bytes)) = &self.mem_confirm else {
+            return;
+        };
+        let mut answer = None;
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let text = format!("Overwrite {} byte(s) at 0x{addr:x}?", bytes.len());
+            ui.label(m(&text, 11.0, RED));
+            if ui.button("Write").clicked() {
+                answer = Some(true);
+            }
+            if ui.button("Cancel").clicked() {
+                answer = Some(false);
+            }
+        });
+        if let Some(ok) = answer
+            && let Some((addr, bytes)) = self.mem_confirm.take()
+            && ok
+        {
+            self.act(Command::WriteMemory { addr, bytes });
+        }
+    }
 
-                                        for (idx, frame) in pause.stack.iter().enumerate() {
-                                            let active = idx == 0;
+    fn breakpoint_script_row(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.add(
+                TextEdit::singleline(&mut self.bp_script_path)
+                    .font(FontId::monospace(11.0))
+                    .desired_width(ui.available_width() - 110.0),
+            );
+            if ui.small_button("Import").clicked() {
+                self.import_breakpoints();
+            }
+            if ui.small_button("Export").clicked() {
+                self.export_breakpoints();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.checkbox(
+                &mut self.bp_pending,
+                m("Set pending breakpoints", 11.0, TXT_MUTED),
+            )
+            .on_hover_text("Keep breakpoints in code that isn't loaded yet (-break-insert -f)");
+        });
+    }
 
-                                            let (stripe, _) = ui.allocate_exact_size(
-                                                Vec2::new(2.0, 14.0),
-                                                Sense::hover(),
-                                            );
-                                            if active {
-                                                ui.painter().rect_filled(stripe, 0.0, BLUE);
-                                            }
+    fn export_breakpoints(&mut self) {
+        let script = crate::gdb::export_breakpoints(&self.state.persistent.breakpoints);
+        let msg = match std::fs::write(&self.bp_script_path, script) {
+            Ok(()) => format!(
+                "[UI] ✓ Exported {} breakpoints to {}",
+                self.state.persistent.breakpoints.len(),
+                self.bp_script_path
+            ),
+            Err(e) => format!("[UI] ✗ Could not write {}: {e}", self.bp_script_path),
+        };
+        self.console_log.push(msg);
+    }
 
-                                            let fn_col = if active { BLUE } else { TXT_CYAN };
-                                            ui.label(m(&idx.to_string(), 11.0, TXT_DIM));
-                                            ui.label(m(&frame.function, 11.0, fn_col));
+    fn import_breakpoints(&mut self) {
+        let script = match std::fs::read_to_string(&self.bp_script_path) {
+            Ok(s) => s,
+            Err(e) => {
+                self.console_log.push(format!(
+                    "[UI] ✗ Could not read {}: {e}",
+                    self.bp_script_path
+                ));
+                return;
+            }
+        };
+        let (specs, unknown) = crate::gdb::parse_breakpoint_script(&script);
+        for line in &unknown {
+            self.console_log.push(format!("[UI] skipped: {line}"));
+        }
+        self.console_log.push(format!(
+            "[UI] ✓ Importing {} breakpoints from {}",
+            specs.len(),
+            self.bp_script_path
+        ));
+        for spec in specs {
+            self.act(spec.insert(self.bp_pending));
+        }
+    }
 
-                                            let loc = if let (Some(file), Some(line)) =
-                                                (&frame.file, frame.line)
-                                            {
-                                                let short = file
-                                                    .split('/')
-                                                    .last()
-                                                    .or_else(|| file.split('\\').last())
-                                                    .unwrap_or(file);
-                                                format!("{short}:{line}")
-                                            } else {
-                                                format!("0x{:x}", frame.addr)
-                                            };
-                                            ui.label(m(&loc, 11.0, TXT_MUTED));
-                                            ui.end_row();
-                                        }
-                                    });
-                            } else {
-                                ui.label(m("Not paused", 11.0, TXT_DIM).italics());
-                            }
-                            ui.add_space(4.0);
-                        }
-                        hl(ui);
+    /// Shows `this`/`self` one level deep: struct values are used as-is,
+    /// pointers and references are dereferenced with an `Evaluate`.
+    fn expand_receiver(&mut self) {
+        self.receiver_expr = None;
+        self.receiver_value = None;
+        if !self.receiver_first {
+            return;
+        }
+        let receiver = receiver_index(&self.state.locals, self.state.language);
+        let Some(var) = receiver.map(|i| &self.state.locals[i]) else {
+            return;
+        };
+        if var.value.trim_start().starts_with('{') {
+            self.receiver_value = Some(Ok(var.value.clone()));
+        } else {
+            let expr = format!("*{}", var.name);
+            self.receiver_expr = Some(expr.clone());
+            self.send(Command::Evaluate(expr));
+        }
+    }
 
-                        // FILES ─────────────────────────────────────────────────
-                        sec_hdr(ui, "Files", &mut self.open_files);
-                        if self.open_files {
-                            if let Some(exe) = &self.state.persistent.executable {
-                                ui.horizontal(|ui| {
-                                    ui.add_space(8.0);
-                                    ui.label(m(&format!("📄 {exe}"), 11.0, TXT_CYAN));
-                                });
-                            }
-                            ui.add_space(4.0);
-                        }
-                        hl(ui);
+    /// User-defined buttons below the built-in commands, plus a row to add more.
+    fn custom_commands(&mut self, ui: &mut egui::Ui) {
+        let mut run = None;
+        let mut remove = None;
+        for (i, (label, cmd)) in self.settings.custom_commands.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let w = ui.available_width() - 24.0;
+                if ui
+                    .add(
+                        egui::Button::new(m(label, 11.0, TXT_YELLOW))
+                            .fill(Color32::TRANSPARENT)
+                            .stroke(Stroke::NONE)
+                            .min_size(Vec2::new(w, 18.0)),
+                    )
+                    .on_hover_text(cmd)
+                    .clicked()
+                {
+                    run = Some(cmd.clone());
+                }
+                if ui.small_button("×").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(cmd) = run {
+            self.act(Command::Raw(cmd));
+        }
+        if let Some(i) = remove {
+            self.settings.custom_commands.remove(i);
+        }
 
-                        // THREAD ────────────────────────────────────────────────
-                        sec_hdr(ui, "Thread", &mut self.open_thread);
-                        if self.open_thread {
-                            if let Some(pause) = &self.state.pause {
-                                ui.horizontal(|ui| {
+        ui.horizontal(|ui| {
+            let w = (ui.available_width() - 40.0) / 2.0;
+            ui.add(
+                TextEdit::singleline(&mut self.new_cmd.0)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("label")
+                    .desired_width(w),
+            );
+            ui.add(
+                TextEdit::singleline(&mut self.new_cmd.1)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("command")
+                    .desired_width(w),
+            );
+            let (label, cmd) = (self.new_cmd.0.trim(), self.new_cmd.1.trim());
+            if ui
+                .add_enabled(!cmd.is_empty(), egui::Button::new("+").small())
+                .clicked()
+            {
+                let label = if label.is_empty() { cmd } else { label };
+                self.settings
+                    .custom_commands
+                    .push((label.to_owned(), cmd.to_owned()));
+                self.new_cmd = (String::new(), String::new());
+            }
+        });
+    }
+
+    /// Commands sent after every stop; their output goes to the "On stop" tab.
+    fn stop_commands_editor(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m("On every stop", 11.0, TXT_DIM));
+        });
+        let mut remove = None;
+        for (i, cmd) in self.settings.stop_commands.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                let col = if resumes(cmd) { TXT_MUTED } else { TXT_CYAN };
+                let label = ui.label(m(cmd, 11.0, col));
+                if resumes(cmd) {
+                    label.on_hover_text("Skipped: it would resume the program");
+                }
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.small_button("×").clicked() {
+                        remove = Some(i);
+                    }
+                });
+            });
+        }
+        if let Some(i) = remove {
+            self.settings.stop_commands.remove(i);
+        }
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.new_stop_cmd)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("info registers eflags")
+                    .desired_width(ui.available_width() - 30.0),
+            );
+            let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            let cmd = self.new_stop_cmd.trim();
+            let add = ui
+                .add_enabled(!cmd.is_empty(), egui::Button::new("+").small())
+                .clicked();
+            if (add || enter) && !cmd.is_empty() {
+                self.settings.stop_commands.push(cmd.to_owned());
+                self.new_stop_cmd.clear();
+            }
+        });
+    }
+
+    /// Only shown once GDB has more than one inferior.
+    fn inferior_selector(&mut self, ui: &mut egui::Ui) {
+        if self.state.inferiors.len() < 2 {
+            return;
+        }
+        let label = |id: u32, pid: Option<u32>| match pid {
+            Some(pid) => format!("Inferior {id} (pid {pid})"),
+            None => format!("Inferior {id}"),
+        };
+        let current = self.state.current_inferior;
+        let current_pid = self.state.inferiors.get(&current).and_then(|i| i.pid);
+        let mut selected = None;
+        ui.add(egui::Separator::default().vertical());
+        egui::ComboBox::from_id_salt("inferior")
+            .selected_text(m(&label(current, current_pid), 11.0, TXT))
+            .show_ui(ui, |ui| {
+                for (&id, inf) in &self.state.inferiors {
+                    if ui
+                        .selectable_label(id == current, label(id, inf.pid))
+                        .clicked()
+                        && id != current
+                    {
+                        selected = Some(id);
+                    }
+                }
+            });
+        if let Some(id) = selected {
+            self.act(Command::SelectInferior(id));
+        }
+    }
+
+    /// The files an ambiguous breakpoint location could mean, to pick from.
+    fn breakpoint_choices(&mut self, ui: &mut egui::Ui) {
+        let Some((_, line, choices)) = &self.bp_choices else {
+            return;
+        };
+        let line = *line;
+        let mut picked = None;
+        let mut dismiss = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&format!("Line {line} of which file?"), 11.0, TXT_YELLOW));
+            dismiss = ui.small_button("×").clicked();
+        });
+        for path in choices {
+            ui.horizontal(|ui| {
+                ui.add_space(16.0);
+                if ui
+                    .add(egui::Label::new(m(path, 11.0, TXT_CYAN)).sense(Sense::click()))
+                    .on_hover_text("Break on this file's line")
+                    .clicked()
+                {
+                    picked = Some(path.clone());
+                }
+            });
+        }
+        if let Some(path) = picked {
+            self.pick_breakpoint_file(&path);
+        } else if dismiss {
+            self.bp_choices = None;
+        }
+    }
+
+    /// Inserts the ambiguous breakpoint again at `path`, as it was asked for.
+    fn pick_breakpoint_file(&mut self, path: &str) {
+        if let Some((mut spec, line, _)) = self.bp_choices.take() {
+            spec.location = format!("{path}:{line}");
+            self.act(spec.insert(self.bp_pending));
+        }
+    }
+
+    fn rbreak_row(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.rbreak_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("function regex")
+                    .desired_width(ui.available_width() - 60.0),
+            );
+            submit = ui
+                .small_button("rbreak")
+                .on_hover_text("Break on every function matching the regex")
+                .clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        });
+        let regex = self.rbreak_input.trim().to_owned();
+        if submit && !regex.is_empty() {
+            self.rbreak_base = Some(self.state.persistent.breakpoints.len());
+            self.act(Command::AddRegexBreakpoints(regex));
+        }
+    }
+
+    /// Warns once if the last `rbreak` created more than `RBREAK_WARN` breakpoints.
+    fn check_rbreak_count(&mut self) {
+        let Some(base) = self.rbreak_base else {
+            return;
+        };
+        let created = self.state.persistent.breakpoints.len().saturating_sub(base);
+        if created > RBREAK_WARN {
+            self.rbreak_base = None;
+            self.console_log.push(format!(
+                "[UI] ⚠ rbreak matched more than {RBREAK_WARN} functions; \
+                 consider a narrower regex or deleting the extra breakpoints"
+            ));
+        }
+    }
+
+    /// The current `$pc`, and Set PC: moves it without running anything.
+    fn pc_row(&mut self, ui: &mut egui::Ui) {
+        let mut set = false;
+        ui.horizontal(|ui| {
+            let pc = match self.state.current_addr() {
+                Some(pc) => format!("pc 0x{pc:x}"),
+                None => "pc —".into(),
+            };
+            ui.label(m(&pc, 13.0, ACCENT).strong());
+            let resp = ui.add_enabled(
+                self.state.is_paused(),
+                TextEdit::singleline(&mut self.pc_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("new pc")
+                    .desired_width(ui.available_width() - 60.0),
+            );
+            set = ui
+                .add_enabled(self.state.is_paused(), egui::Button::new("Set PC").small())
+                .on_hover_text("Continue from another address; nothing in between runs")
+                .clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        });
+        if set {
+            let text = self.pc_input.trim();
+            let hex = text
+                .strip_prefix("0x")
+                .or_else(|| text.strip_prefix("0X"))
+                .unwrap_or(text);
+            let checked = u64::from_str_radix(hex, 16)
+                .map_err(|_| format!("{text:?} isn't a hex address"))
+                .and_then(|addr| check_pc(addr, &self.state.mappings).map(|()| addr));
+            match checked {
+                Ok(addr) => self.pc_confirm = Some(addr),
+                Err(e) => self.console_log.push(format!("[UI] ✗ Can't set pc: {e}")),
+            }
+        }
+
+        let Some(addr) = self.pc_confirm else {
+            return;
+        };
+        let mut answer = None;
+        ui.horizontal(|ui| {
+            ui.label(m(&format!("Move pc to 0x{addr:x}?"), 11.0, RED));
+            if ui.button("Set").clicked() {
+                answer = Some(true);
+            }
+            if ui.button("Cancel").clicked() {
+                answer = Some(false);
+            }
+        });
+        match answer {
+            Some(true) => self.set_pc(addr),
+            Some(false) => self.pc_confirm = None,
+            None => {}
+        }
+    }
+
+    fn set_pc(&mut self, addr: u64) {
+        self.pc_confirm = None;
+        self.act(Command::SetRegister {
+            name: "pc".into(),
+            value: format!("0x{addr:x}"),
+        });
+        // No stop follows: refetch what depends on where we are
+        self.selected_frame = 0;
+        self.stale = StaleViews::all();
+        self.stale.disasm = self.disasm_follow_pc && self.focus.is_none();
+        self.request_visible();
+        self.request_stack_page(0);
+    }
+
+    fn disasm_jump_row(&mut self, ui: &mut egui::Ui) {
+        let mut jump = false;
+        let mut export = false;
+        ui.horizontal(|ui| {
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.disasm_addr)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("address / symbol")
+                    .desired_width(ui.available_width() - 190.0),
+            );
+            jump = ui.small_button("Go").clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+            if ui
+                .checkbox(&mut self.disasm_follow_pc, m("Follow PC", 11.0, TXT_MUTED))
+                .changed()
+                && self.disasm_follow_pc
+            {
+                self.stale.disasm = true;
+                self.request_visible();
+            }
+            export = ui
+                .add_enabled(
+                    !self.shown_disasm().is_empty(),
+                    egui::Button::new("Export").small(),
+                )
+                .on_hover_text("Save the disassembly shown to a text file")
+                .clicked();
+        });
+        let start = self.disasm_addr.trim().to_owned();
+        if jump && !start.is_empty() {
+            self.act(Command::DisassembleAt { start });
+        }
+        if export {
+            self.export_disasm();
+        }
+    }
+
+    /// The disassembly on screen: the focus region's if one is pinned.
+    fn shown_disasm(&self) -> &[AsmLine] {
+        if self.focus.is_some() {
+            &self.focus_disasm
+        } else {
+            &self.state.disasm
+        }
+    }
+
+    fn export_disasm(&mut self) {
+        let lines = self.shown_disasm();
+        let Some(first) = lines.first() else {
+            return;
+        };
+        let path = format!("disasm-0x{:x}.txt", first.addr);
+        let count = lines.len();
+        let text = format_disasm(lines, &self.state.asm_source_lines);
+        let msg = match std::fs::write(&path, text) {
+            Ok(()) => format!("[UI] ✓ Exported {count} instructions to {path}"),
+            Err(e) => format!("[UI] ✗ Could not write {path}: {e}"),
+        };
+        self.console_log.push(msg);
+    }
+
+    fn focus_row(&mut self, ui: &mut egui::Ui) {
+        let mut focus = false;
+        let mut clear = false;
+        ui.horizontal(|ui| {
+            let width = (ui.available_width() - 110.0) / 2.0;
+            for (text, hint) in [
+                (&mut self.focus_input.0, "focus start"),
+                (&mut self.focus_input.1, "end / +len"),
+            ] {
+                let resp = ui.add(
+                    TextEdit::singleline(text)
+                        .font(FontId::monospace(11.0))
+                        .hint_text(hint)
+                        .desired_width(width),
+                );
+                focus |= resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            }
+            focus |= ui
+                .small_button("Focus")
+                .on_hover_text("Keep this range disassembled while stepping through it")
+                .clicked();
+            clear = ui
+                .add_enabled(self.focus.is_some(), egui::Button::new("Clear").small())
+                .clicked();
+        });
+        if focus {
+            self.set_focus();
+        } else if clear {
+            self.clear_focus();
+        }
+
+        if let Some(region) = self.focus
+            && let Some(pc) = self.state.current_addr()
+            && !region.contains(pc)
+        {
+            ui.label(m(
+                &format!("⚠ left focus region (pc 0x{pc:x})"),
+                11.0,
+                TXT_YELLOW,
+            ));
+        }
+    }
+
+    fn set_focus(&mut self) {
+        let Some(region) = FocusRegion::parse(&self.focus_input.0, &self.focus_input.1) else {
+            self.console_log.push(format!(
+                "[UI] ✗ Bad focus region: {}..{} (hex addresses, end after start)",
+                self.focus_input.0.trim(),
+                self.focus_input.1.trim()
+            ));
+            return;
+        };
+        self.focus = Some(region);
+        self.focus_disasm.clear();
+        self.focus_pending = true;
+        self.act(Command::DisassembleRange {
+            start: region.start,
+            end: region.end,
+        });
+    }
+
+    /// Back to the plain disassembly view.
+    fn clear_focus(&mut self) {
+        self.focus = None;
+        self.focus_disasm.clear();
+        self.focus_pending = false;
+        if self.state.is_paused() {
+            self.stale.disasm = true;
+            self.request_visible();
+        }
+    }
+
+    /// Asks for the source lines of every function in the disassembly we
+    /// haven't seen yet; one mode-1 query per function.
+    fn request_asm_source_lines(&mut self) {
+        let mut new = vec![];
+        for asm in &self.state.disasm {
+            if let Some(func) = &asm.function
+                && self.asm_line_funcs.insert(func.clone())
+            {
+                new.push(asm.addr);
+            }
+        }
+        for addr in new {
+            self.send(Command::RequestAsmSourceLines { addr });
+        }
+    }
+
+    /// Sends `expr` as the new last evaluation; its result replaces the old one.
+    fn evaluate(&mut self, expr: String) {
+        self.last_eval = Some(expr.clone());
+        self.last_eval_result = None;
+        self.last_eval_pending = true;
+        self.act(Command::Evaluate(expr));
+    }
+
+    fn reevaluate(&mut self) {
+        if let Some(expr) = self.last_eval.clone() {
+            self.evaluate(expr);
+        }
+    }
+
+    fn add_watch(&mut self) {
+        let expr = self.watch_input.trim().to_owned();
+        if expr.is_empty() {
+            return;
+        }
+        self.watch_input.clear();
+        if self.state.persistent.watches.contains(&expr) {
+            return;
+        }
+        self.state
+            .apply(StateEvent::WatchAdded { expr: expr.clone() });
+        // Otherwise created at the next stop
+        if self.state.pause.is_some() {
+            self.send_watch_requests(vec![Command::CreateVarObject(expr)]);
+        }
+    }
+
+    fn remove_watch(&mut self, expr: String) {
+        // With a create in flight the old varobj is already deleted, and the
+        // new one is once its answer comes in
+        if !self.watch_creating.contains(&expr)
+            && let Some(Ok(watch)) = self.state.watch_values.get(&expr)
+        {
+            self.send(Command::DeleteVarObject(watch.var.clone()));
+        }
+        self.state.apply(StateEvent::WatchRemoved { expr });
+    }
+
+    /// Sends `watch_requests`' commands, noting the creates in flight.
+    fn send_watch_requests(&mut self, cmds: Vec<Command>) {
+        for cmd in cmds {
+            if let Command::CreateVarObject(expr) = &cmd {
+                self.watch_creating.insert(expr.clone());
+            }
+            self.send(cmd);
+        }
+    }
+
+    /// Watch expressions, re-read at every stop, with the input adding them.
+    fn watches_panel(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.watch_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("add watch")
+                    .desired_width(ui.available_width() - 30.0),
+            );
+            submit = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            submit |= ui
+                .add(egui::Button::new("+").small())
+                .on_hover_text("Watch this expression")
+                .clicked();
+        });
+        if submit {
+            self.add_watch();
+        }
+
+        let mut remove = None;
+        for expr in &self.state.persistent.watches {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                if ui
+                    .add(egui::Button::new("×").small())
+                    .on_hover_text("Remove watch")
+                    .clicked()
+                {
+                    remove = Some(expr.clone());
+                }
+                match self.state.watch_values.get(expr) {
+                    Some(Ok(watch)) if watch.in_scope => {
+                        ui.label(m(&format!("{expr} = {}", watch.value), 11.0, TXT))
+                            .on_hover_text(&watch.type_);
+                    }
+                    Some(Ok(_)) => {
+                        ui.label(m(&format!("{expr} = <out of scope>"), 11.0, TXT_DIM));
+                    }
+                    Some(Err(msg)) => {
+                        ui.label(m(&format!("{expr} ="), 11.0, TXT));
+                        ui.label(m("<error>", 11.0, RED)).on_hover_text(msg);
+                    }
+                    None if self.state.pause.is_some() => {
+                        ui.label(m(&format!("{expr} = …"), 11.0, TXT_DIM));
+                    }
+                    None => {
+                        ui.label(m(expr, 11.0, TXT_DIM));
+                    }
+                }
+            });
+        }
+        if let Some(expr) = remove {
+            self.remove_watch(expr);
+        }
+    }
+
+    /// One-off expression plus a re-evaluate button for the last one.
+    fn eval_row(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        let mut again = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.eval_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("evaluate")
+                    .desired_width(ui.available_width() - 30.0),
+            );
+            submit = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            again |= ui
+                .add_enabled(self.last_eval.is_some(), egui::Button::new("↻").small())
+                .on_hover_text("Re-evaluate the last expression (Ctrl+E)")
+                .clicked();
+        });
+        let expr = self.eval_input.trim().to_owned();
+        if submit && !expr.is_empty() {
+            self.evaluate(expr);
+        } else if again {
+            self.reevaluate();
+        }
+
+        self.cast_row(ui);
+
+        if let Some(expr) = &self.last_eval {
+            let (text, col) = match &self.last_eval_result {
+                // Structs and arrays open field by field
+                Some(Ok(v)) if v.trim_start().starts_with('{') => {
+                    let tree = ValueTree::parse(v);
+                    let rows = tree_rows(expr, &tree, &self.tree_open);
+                    let mut toggle = None;
+                    for (i, row) in rows.iter().enumerate() {
+                        let cursor = self.nav == Some(NavPanel::Tree) && i == self.tree_cursor;
+                        let text = match (row.expandable, self.tree_open.contains(&row.path)) {
+                            (true, true) => format!("▾ {}", row.name),
+                            (true, false) => format!("▸ {}", row.name),
+                            (false, _) => format!("{} = {}", row.name, row.value),
+                        };
+                        let col = match (cursor, row.expandable) {
+                            (true, _) => ACCENT,
+                            (false, true) => TXT_CYAN,
+                            (false, false) => TXT,
+                        };
+                        ui.horizontal(|ui| {
+                            ui.add_space(8.0 + 12.0 * row.depth as f32);
+                            let label = egui::Label::new(m(&text, 11.0, col)).sense(Sense::click());
+                            if ui.add(label).clicked() {
+                                toggle = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = toggle {
+                        self.nav = Some(NavPanel::Tree);
+                        self.tree_cursor = i;
+                        self.toggle_tree_row(&rows[i].path.clone(), rows[i].expandable);
+                    }
+                    return;
+                }
+                Some(Ok(v)) => (format!("{expr} = {v}"), TXT),
+                Some(Err(e)) => (format!("{expr}: {e}"), RED),
+                None if self.last_eval_pending => (format!("{expr} = …"), TXT_DIM),
+                None => (format!("{expr}  (↻ to evaluate)"), TXT_DIM),
+            };
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.label(m(&text, 11.0, col));
+            });
+        }
+    }
+
+    /// Address or expression viewed as a given type: `*(struct foo *)(addr)`.
+    fn cast_row(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let width = (ui.available_width() - 70.0) / 2.0;
+            let expr = ui.add(
+                TextEdit::singleline(&mut self.cast_expr)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("address")
+                    .desired_width(width),
+            );
+            ui.label(m("as", 11.0, TXT_DIM));
+            let ty = ui.add(
+                TextEdit::singleline(&mut self.cast_type)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("struct foo")
+                    .desired_width(width),
+            );
+            let enter =
+                (expr.lost_focus() || ty.lost_focus()) && ui.input(|i| i.key_pressed(Key::Enter));
+            submit = ui
+                .small_button("View")
+                .on_hover_text("Read the memory at the address as this type")
+                .clicked()
+                || enter;
+        });
+        if !submit {
+            return;
+        }
+        match cast_expression(&self.cast_expr, &self.cast_type) {
+            Some(expr) => self.evaluate(expr),
+            None => self.console_log.push(format!(
+                "[UI] ✗ Can't view {:?} as {:?}",
+                self.cast_expr.trim(),
+                self.cast_type.trim()
+            )),
+        }
+    }
+
+    fn toggle_tree_row(&mut self, path: &str, expandable: bool) {
+        if expandable && !self.tree_open.remove(path) {
+            self.tree_open.insert(path.to_owned());
+        }
+    }
+
+    /// ↑/↓ move through the Stack or the value tree, whichever was clicked
+    /// last; Enter selects the frame or opens the node, ←/→ close and open.
+    /// Text fields keep their arrow keys.
+    fn keyboard_nav(&mut self, ctx: &egui::Context) {
+        let Some(panel) = self.nav else {
+            return;
+        };
+        if ctx.memory(|mem| mem.focused().is_some()) {
+            return;
+        }
+        let key = |key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, key));
+        let delta = if key(Key::ArrowUp) {
+            -1
+        } else if key(Key::ArrowDown) {
+            1
+        } else {
+            0
+        };
+        let (enter, left, right) = (key(Key::Enter), key(Key::ArrowLeft), key(Key::ArrowRight));
+        if key(Key::Escape) {
+            self.nav = None;
+            return;
+        }
+        match panel {
+            NavPanel::Stack => {
+                let Some(pause) = &self.state.pause else {
+                    return;
+                };
+                self.stack_cursor = move_cursor(self.stack_cursor, delta, pause.stack.len());
+                let level = pause.stack.get(self.stack_cursor).map(|f| f.level);
+                if enter && let Some(level) = level {
+                    self.select_frame(level);
+                }
+            }
+            NavPanel::Tree => {
+                let (Some(expr), Some(Ok(value))) = (&self.last_eval, &self.last_eval_result)
+                else {
+                    return;
+                };
+                let tree = ValueTree::parse(value);
+                let rows = tree_rows(expr, &tree, &self.tree_open);
+                self.tree_cursor = move_cursor(self.tree_cursor, delta, rows.len());
+                let Some(row) = rows.get(self.tree_cursor) else {
+                    return;
+                };
+                let open = self.tree_open.contains(&row.path);
+                if row.expandable && (enter || (left && open) || (right && !open)) {
+                    let path = row.path.clone();
+                    self.toggle_tree_row(&path, true);
+                }
+            }
+        }
+    }
+
+    /// Makes `level` GDB's selected frame: locals and registers follow it and
+    /// its source opens in a tab.
+    fn select_frame(&mut self, level: u32) {
+        let Some(frame) = self
+            .state
+            .pause
+            .as_ref()
+            .and_then(|p| p.stack.iter().find(|f| f.level == level))
+        else {
+            return;
+        };
+        let location = frame.file.clone().zip(frame.line);
+        self.act(Command::SelectFrame(level));
+        self.selected_frame = level;
+        self.stale = StaleViews::all();
+        self.request_visible();
+        match location {
+            Some((file, line)) if level != 0 => self.open_tab(file, line),
+            _ => self.active_tab = None,
+        }
+    }
+
+    /// `main › process › parse › scan`, outermost first; click to select a frame.
+    fn breadcrumb_bar(&mut self, ui: &mut egui::Ui) {
+        let Some(pause) = &self.state.pause else {
+            return;
+        };
+        let mut select = None;
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 4.0;
+            for (i, crumb) in breadcrumb(&pause.stack, self.stack_more, BREADCRUMB_MAX)
+                .into_iter()
+                .enumerate()
+            {
+                if i > 0 {
+                    ui.label(m("›", 11.0, TXT_DIM));
+                }
+                let Some(frame) = crumb else {
+                    ui.label(m("…", 11.0, TXT_DIM));
+                    continue;
+                };
+                let (name, col) = if frame.function == "??" {
+                    (format!("0x{:x}", frame.addr), TXT_DIM)
+                } else if frame.level == self.selected_frame {
+                    (frame.function.clone(), BLUE)
+                } else {
+                    (frame.function.clone(), TXT_CYAN)
+                };
+                let resp = ui
+                    .add(egui::Label::new(m(&name, 11.0, col)).sense(Sense::click()))
+                    .on_hover_text(format!("#{} {}", frame.level, frame_call(frame)));
+                if resp.clicked() {
+                    select = Some(frame.level);
+                }
+            }
+        });
+        if let Some(level) = select {
+            self.select_frame(level);
+        }
+    }
+
+    /// Evaluates an expression in every loaded frame and lists frame → value.
+    fn frame_eval_panel(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.frame_eval_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("eval in all frames")
+                    .desired_width(ui.available_width() - 50.0),
+            );
+            submit = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            submit |= ui.small_button("Eval").clicked();
+        });
+        let expr = self.frame_eval_input.trim().to_owned();
+        if submit && !expr.is_empty() && self.frame_eval.as_ref().is_none_or(|e| e.is_complete()) {
+            let levels = self
+                .state
+                .pause
+                .iter()
+                .flat_map(|p| &p.stack)
+                .map(|f| f.level)
+                .collect();
+            let eval = FrameEval::new(expr, levels, self.selected_frame);
+            for cmd in eval.start() {
+                self.send(cmd);
+            }
+            self.frame_eval = Some(eval);
+        }
+
+        let Some(eval) = &self.frame_eval else {
+            return;
+        };
+        let stack = self
+            .state
+            .pause
+            .as_ref()
+            .map(|p| p.stack.as_slice())
+            .unwrap_or(&[]);
+        egui::Grid::new("frame_eval_grid")
+            .num_columns(3)
+            .spacing([6.0, 2.0])
+            .show(ui, |ui| {
+                for (level, value) in &eval.results {
+                    let function = stack
+                        .iter()
+                        .find(|f| f.level == *level)
+                        .map(|f| f.function.as_str())
+                        .unwrap_or("??");
+                    ui.label(m(&level.to_string(), 11.0, TXT_DIM));
+                    ui.label(m(function, 11.0, TXT_CYAN));
+                    match value {
+                        Ok(v) => ui.label(m(&truncate_chars(v, FRAME_CALL_MAX), 11.0, TXT)),
+                        Err(e) => ui.label(m(&truncate_chars(e, FRAME_CALL_MAX), 11.0, TXT_DIM)),
+                    }
+                    .on_hover_text(value.as_ref().unwrap_or_else(|e| e));
+                    ui.end_row();
+                }
+            });
+        if !eval.is_complete() {
+            ui.label(m(&format!("evaluating `{}`…", eval.expr), 11.0, TXT_DIM));
+        }
+    }
+
+    /// Snapshots taken with `checkpoint`, each restorable with `restart N`.
+    fn checkpoints_panel(&mut self, ui: &mut egui::Ui) {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if ui
+                .add_enabled(self.state.is_paused(), egui::Button::new("Save snapshot"))
+                .on_disabled_hover_text("Pause the program first")
+                .clicked()
+            {
+                action = Some(Command::CreateCheckpoint);
+            }
+        });
+        if self.state.checkpoints.is_empty() {
+            ui.label(m("No checkpoints", 11.0, TXT_DIM).italics());
+        }
+        for (&id, &pid) in &self.state.checkpoints {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.label(m(&format!("#{id}"), 11.0, TXT_CYAN));
+                ui.label(m(&format!("pid {pid}"), 11.0, TXT_MUTED));
+                if ui.small_button("Restore").clicked() {
+                    action = Some(Command::RestartCheckpoint(id));
+                }
+                if ui.small_button("×").clicked() {
+                    action = Some(Command::DeleteCheckpoint(id));
+                }
+            });
+        }
+        if let Some(cmd) = action {
+            let restored = matches!(cmd, Command::RestartCheckpoint(_));
+            self.act(cmd);
+            if restored {
+                // restart no emite *stopped: refrescar a mano
+                self.stale = StaleViews::all();
+                self.request_visible();
+                self.request_stack_page(0);
+            }
+        }
+    }
+
+    /// One auto-step stop: book the time of the line we left and step again.
+    /// Views aren't refreshed in between, that would dominate the timing.
+    fn profile_step(&mut self) {
+        let Some(pause) = &self.state.pause else {
+            return;
+        };
+        let location = pause.frame.file.as_deref().zip(pause.frame.line);
+        self.profile.record(location, Instant::now());
+        if matches!(pause.stop_reason, StopReason::Signal(_)) {
+            self.stop_profile();
+        } else {
+            self.send(Command::Step);
+        }
+    }
+
+    /// One step-until stop: check the condition before stepping again.
+    fn step_until_stop(&mut self) {
+        let Some(pause) = &self.state.pause else {
+            return;
+        };
+        let next = self.step_until.on_stop(pause);
+        self.step_until_next(next);
+    }
+
+    fn step_until_next(&mut self, next: Result<Command, Outcome>) {
+        let outcome = match next {
+            Ok(cmd) => return self.send(cmd),
+            Err(outcome) => outcome,
+        };
+        let expr = &self.step_until.expr;
+        let msg = match outcome {
+            Outcome::Hit(steps) => format!("[UI] ✓ `{expr}` became true after {steps} steps"),
+            Outcome::Limit => format!(
+                "[UI] ⚠ `{expr}` still false after {} steps",
+                step_until::MAX_STEPS
+            ),
+            Outcome::Error(err) => format!("[UI] ✗ Step until `{expr}`: {err}"),
+            Outcome::Interrupted => format!("[UI] ⚠ Step until `{expr}` interrupted"),
+        };
+        self.console_log.push(msg);
+        self.on_stop();
+    }
+
+    fn step_until_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if self.step_until.is_active() {
+                ui.spinner();
+                ui.label(m(
+                    &format!(
+                        "{}  step {}/{}",
+                        self.step_until.expr,
+                        self.step_until.steps(),
+                        step_until::MAX_STEPS
+                    ),
+                    11.0,
+                    TXT_MUTED,
+                ));
+                if ui.small_button("Cancel").clicked() {
+                    // The step in flight stops like any other and refreshes the views
+                    self.step_until.cancel();
+                    if self.state.is_paused() {
+                        self.on_stop();
+                    }
+                }
+                return;
+            }
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.step_until_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("i == 3")
+                    .desired_width(ui.available_width() - 60.0),
+            );
+            let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            let expr = self.step_until_input.trim().to_owned();
+            let ready = self.state.is_paused() && !expr.is_empty();
+            let start = ui
+                .add_enabled(ready, egui::Button::new("Start").small())
+                .on_hover_text("Step until the expression is true, checking after each step")
+                .on_disabled_hover_text("Pause the program and enter a condition")
+                .clicked();
+            if ready && (start || enter) {
+                let cmd = self.step_until.start(expr);
+                self.act(cmd);
+            }
+        });
+    }
+
+    /// Two expressions, or one before and after with the left side pinned,
+    /// as a tree with the differing fields opened.
+    fn compare_panel(&mut self, ui: &mut egui::Ui) {
+        let mut evaluate = false;
+        for (i, hint) in ["left: *before", "right: *after"].into_iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                let resp = ui.add(
+                    TextEdit::singleline(&mut self.compare.exprs[i])
+                        .font(FontId::monospace(11.0))
+                        .hint_text(hint)
+                        .desired_width(ui.available_width() - 60.0),
+                );
+                evaluate |= resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                if i == 0 {
+                    ui.checkbox(&mut self.compare.pinned, m("Pin", 11.0, TXT_MUTED))
+                        .on_hover_text("Keep this value across stops to compare before and after");
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            evaluate |= ui
+                .add_enabled(self.state.is_paused(), egui::Button::new("Compare").small())
+                .on_disabled_hover_text("Pause the program first")
+                .clicked();
+        });
+        if evaluate && self.state.is_paused() {
+            for cmd in self.compare.requests() {
+                self.send(cmd);
+            }
+        }
+
+        let [left, right] = &self.compare.values;
+        let (left, right) = match (left, right) {
+            (Some(Ok(l)), Some(Ok(r))) => (l, r),
+            (Some(Err(e)), _) | (_, Some(Err(e))) => {
+                ui.horizontal(|ui| {
+                    ui.add_space(8.0);
+                    ui.label(m(&format!("<{e}>"), 11.0, RED));
+                });
+                return;
+            }
+            _ => return,
+        };
+        egui::Grid::new("compare_grid")
+            .num_columns(3)
+            .spacing([8.0, 2.0])
+            .show(ui, |ui| {
+                for row in diff_values(left, right) {
+                    let col = if row.changed { TXT_YELLOW } else { TXT_MUTED };
+                    let name = if row.depth == 0 { "value" } else { &row.name };
+                    ui.label(m(
+                        &format!("{}{name}", "  ".repeat(row.depth)),
+                        11.0,
+                        TXT_CYAN,
+                    ));
+                    ui.label(m(row.left.as_deref().unwrap_or("—"), 11.0, col));
+                    ui.label(m(row.right.as_deref().unwrap_or("—"), 11.0, col));
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn stop_profile(&mut self) {
+        self.profile.stop();
+        self.stale = StaleViews::all();
+        self.request_visible();
+        self.request_stack_page(0);
+    }
+
+    /// On the first fatal signal of a run, asks for everything the report
+    /// needs. Sent before the view refresh so these replies come in first.
+    fn start_crash_report(&mut self) {
+        if !self.settings.crash_reports || self.crash_captured {
+            return;
+        }
+        let Some(pause) = &self.state.pause else {
+            return;
+        };
+        let StopReason::Signal(sig) = &pause.stop_reason else {
+            return;
+        };
+        if !is_fatal(sig) {
+            return;
+        }
+        self.crash_captured = true;
+        self.crash_report = Some(CrashReport::new(
+            sig.clone(),
+            self.state.persistent.executable.clone(),
+        ));
+        for cmd in CrashReport::requests() {
+            self.send(cmd);
+        }
+    }
+
+    fn save_crash_report(&mut self) {
+        let Some(report) = self.crash_report.take() else {
+            return;
+        };
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let path = format!("crash-{}-{stamp}.txt", report.signal);
+        let msg = match std::fs::write(&path, report.render()) {
+            Ok(()) => format!("[UI] ✓ {} crash report saved to {path}", report.signal),
+            Err(e) => format!("[UI] ✗ Could not write {path}: {e}"),
+        };
+        self.console_log.push(msg);
+    }
+
+    /// Warns that GDB has gone quiet on a command, with a way out.
+    fn stall_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(command) = &self.stalled else {
+            return;
+        };
+        let mut interrupt = false;
+        ui.horizontal(|ui| {
+            ui.label(m(
+                &format!(
+                    "⚠ No reply to {command} for {}s: the target may be unresponsive or GDB busy",
+                    STALL_TIMEOUT.as_secs()
+                ),
+                11.0,
+                TXT_YELLOW,
+            ));
+            interrupt = ui.small_button("Interrupt").clicked();
+        });
+        if interrupt {
+            self.act(Command::Interrupt);
+        }
+    }
+
+    fn save_state_report(&mut self) {
+        let source = self
+            .state
+            .current_file()
+            .and_then(|file| self.source_cache.get(file))
+            .and_then(|lines| lines.as_deref());
+        let report = state_report::render(&self.state, source);
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let path = format!("gdb-report-{stamp}.md");
+        let msg = match std::fs::write(&path, report) {
+            Ok(()) => format!("[UI] ✓ State report saved to {path}"),
+            Err(e) => format!("[UI] ✗ Could not write {path}: {e}"),
+        };
+        self.console_log.push(msg);
+    }
+
+    /// New stop: the current snapshot becomes the one to compare against.
+    /// With the Changes panel open, locals and registers are fetched even if
+    /// their tab isn't showing, and the frame base to tell recursive calls
+    /// apart.
+    fn start_snapshot(&mut self) {
+        let Some(pause) = &self.state.pause else {
+            return;
+        };
+        let watch = match &pause.stop_reason {
+            StopReason::WatchpointTrigger { expr, old, new, .. } => {
+                Some((expr.clone(), old.clone(), new.clone()))
+            }
+            _ => None,
+        };
+        let next = Snapshot {
+            function: pause.frame.function.clone(),
+            watch,
+            ..Snapshot::default()
+        };
+        self.changes_prev = std::mem::replace(&mut self.changes_cur, next);
+        if self.open_changes {
+            self.send(Command::Evaluate(FRAME_EXPR.into()));
+            for tab in [WatchTab::Watch, WatchTab::Registers] {
+                for cmd in self.stale.requests_for(tab) {
+                    self.send(cmd);
+                }
+            }
+        }
+    }
+
+    /// First frame-0 locals and registers of the stop go into the snapshot,
+    /// and the first read of the Memory view's watched region.
+    fn record_snapshot(&mut self, event: &StateEvent) {
+        let cur = &mut self.changes_cur;
+        match event {
+            StateEvent::LocalsUpdated { vars }
+                if cur.locals.is_none() && self.selected_frame == 0 =>
+            {
+                cur.locals = Some(
+                    vars.iter()
+                        .map(|v| (v.name.clone(), v.value.clone()))
+                        .collect(),
+                );
+            }
+            StateEvent::RegistersUpdated { registers }
+                if cur.registers.is_none() && self.selected_frame == 0 =>
+            {
+                let names = &self.state.register_names;
+                cur.registers = Some(
+                    registers
+                        .iter()
+                        .filter_map(|r| {
+                            let name = names.get(r.number as usize)?;
+                            (!name.is_empty()).then(|| (name.clone(), r.value.clone()))
+                        })
+                        .collect(),
+                );
+            }
+            StateEvent::MemoryRead { block }
+                if cur.region.is_none()
+                    && self.mem_watch.is_some_and(|(addr, _)| addr == block.addr) =>
+            {
+                cur.region = Some((block.addr, block.bytes.clone()));
+            }
+            _ => {}
+        }
+    }
+
+    fn changes_panel(&mut self, ui: &mut egui::Ui) {
+        let changes = diff(&self.changes_prev, &self.changes_cur);
+        if changes.is_empty() {
+            ui.label(m("No changes since the last stop", 11.0, TXT_DIM).italics());
+            return;
+        }
+        ScrollArea::vertical()
+            .id_salt("changes")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for c in &changes {
+                    ui.horizontal(|ui| {
+                        ui.add_space(8.0);
+                        ui.label(m(c.kind.tag(), 10.0, TXT_DIM));
+                        ui.label(m(&c.name, 11.0, TXT_CYAN));
+                        let old = c.old.as_deref().unwrap_or("—");
+                        let new = c.new.as_deref().unwrap_or("—");
+                        ui.label(m(&format!("{old} → {new}"), 11.0, TXT_YELLOW));
+                    });
+                }
+            });
+    }
+
+    /// Another thread's stack is showing: refetch what hangs off it.
+    fn on_thread_switch(&mut self) {
+        self.selected_frame = 0;
+        self.stack_cursor = 0;
+        self.active_tab = None;
+        self.stale = StaleViews::all();
+        self.request_visible();
+        self.request_stack_page(0);
+    }
+
+    /// One row per thread: name, what stopped it and where it is. Until
+    /// `-thread-info` answers, just the thread that reported the stop.
+    fn threads_panel(&mut self, ui: &mut egui::Ui) {
+        let Some(pause) = &self.state.pause else {
+            return;
+        };
+        let dot = |ui: &mut egui::Ui, color| {
+            let (r, _) = ui.allocate_exact_size(Vec2::splat(8.0), Sense::hover());
+            ui.painter().circle_filled(r.center(), 4.0, color);
+            ui.add_space(4.0);
+        };
+        if self.state.threads.is_empty() {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                dot(ui, ACCENT);
+                ui.label(m(&format!("Thread {}", pause.thread_id), 11.0, TXT_MUTED));
+            });
+            return;
+        }
+        let mut select = None;
+        for thread in &self.state.threads {
+            let stopped_it = thread.id == pause.thread_id;
+            let current = self.state.current_thread == Some(thread.id);
+            let color = match &pause.stop_reason {
+                StopReason::Signal(_) if stopped_it => RED,
+                _ if current => ACCENT,
+                _ => TXT_DIM,
+            };
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                dot(ui, color);
+                let resp = ui
+                    .add(
+                        egui::Label::new(m(
+                            &thread_title(thread),
+                            11.0,
+                            if current { TXT } else { TXT_MUTED },
+                        ))
+                        .sense(Sense::click()),
+                    )
+                    .on_hover_text(&thread.target_id);
+                // Running threads have no stack to show
+                if resp.clicked() && !current && thread.frame.is_some() {
+                    select = Some(thread.id);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.add_space(24.0);
+                ui.label(m(&thread_detail(thread, pause), 10.0, TXT_DIM));
+            });
+        }
+        if let Some(id) = select {
+            // The stack and locals follow once GDB confirms the switch
+            self.act(Command::SelectThread(id));
+        }
+    }
+
+    /// Feeds the reconnect state machine and logs what happened to the link.
+    fn track_remote(&mut self, event: &StateEvent) {
+        let msg = match event {
+            StateEvent::RemoteConnected { addr } => {
+                self.remote.on_connected();
+                format!("[UI] ✓ Connected to {addr}")
+            }
+            StateEvent::RemoteConnectFailed { addr, msg } => {
+                self.remote.on_failed(Instant::now());
+                match self.remote.state {
+                    LinkState::GaveUp => {
+                        format!("[UI] ✗ Gave up on {addr} after {MAX_ATTEMPTS} attempts: {msg}")
+                    }
+                    _ => format!("[UI] ✗ Could not connect to {addr}: {msg}"),
+                }
+            }
+            StateEvent::RemoteDisconnected { reason } => {
+                self.remote.on_disconnected(Instant::now());
+                if self.remote.is_reconnecting() {
+                    format!("[UI] ⚠ Remote link lost ({reason}), reconnecting")
+                } else {
+                    format!("[UI] ⚠ Remote link lost ({reason})")
+                }
+            }
+            _ => return,
+        };
+        self.console_log.push(msg);
+    }
+
+    fn remote_panel(&mut self, ui: &mut egui::Ui) {
+        let mut connect = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let busy = matches!(
+                self.remote.state,
+                LinkState::Connecting { .. } | LinkState::Waiting { .. }
+            );
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.remote_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("host:port")
+                    .desired_width(ui.available_width() - 70.0),
+            );
+            connect = ui
+                .add_enabled(!busy, egui::Button::new("Connect").small())
+                .on_hover_text("target remote, e.g. to a gdbserver")
+                .clicked()
+                || (!busy && resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        });
+        let addr = self.remote_input.trim().to_owned();
+        if connect && !addr.is_empty() {
+            let cmd = self.remote.connect(&addr);
+            self.act(cmd);
+        }
+    }
+
+    /// Status bar indicator while a dropped link is being retried.
+    fn reconnect_status(&mut self, ui: &mut egui::Ui) {
+        let text = match self.remote.state {
+            LinkState::Waiting { attempt, at } => format!(
+                "⟳ reconnecting {attempt}/{MAX_ATTEMPTS} in {}s",
+                at.saturating_duration_since(Instant::now()).as_secs() + 1
+            ),
+            LinkState::Connecting { attempt } if attempt > 0 => {
+                format!("⟳ reconnecting {attempt}/{MAX_ATTEMPTS}")
+            }
+            LinkState::GaveUp => "✗ remote lost".into(),
+            _ => return,
+        };
+        ui.add(egui::Separator::default().vertical());
+        let addr = self.remote.addr.as_deref().unwrap_or_default();
+        ui.label(m(&text, 11.0, TXT_YELLOW)).on_hover_text(addr);
+        if self.remote.is_reconnecting() && ui.small_button("Cancel").clicked() {
+            self.remote.cancel();
+        }
+    }
+
+    /// A prompt GDB printed. Over MI it doesn't read an answer: anything
+    /// typed would run as a new command, so this only tells what happened
+    /// and offers to turn the prompts off.
+    fn query_dialog(&mut self, ctx: &egui::Context) {
+        let Some((question, pagination)) = &self.query else {
+            return;
+        };
+        let (fix, setting) = if *pagination {
+            ("Turn pagination off", Command::SetPagination(false))
+        } else {
+            ("Turn confirmations off", Command::SetConfirm(false))
+        };
+        let (mut close, mut turn_off) = (false, false);
+        egui::Window::new("GDB asked")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(m(question, 12.0, TXT));
+                ui.add_space(4.0);
+                ui.label(m(
+                    "GDB takes the default answer itself when not on a terminal",
+                    11.0,
+                    TXT_MUTED,
+                ));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    close = ui.button("OK").clicked();
+                    turn_off = ui.button(fix).clicked();
+                });
+            });
+        if turn_off {
+            self.act(setting);
+        }
+        if close || turn_off {
+            self.query = None;
+        }
+    }
+
+    /// Strip of pinned registers above the source; yellow for the ones that
+    /// changed since the previous stop.
+    fn pinned_registers(&self, ui: &mut egui::Ui) {
+        if self.settings.pinned_registers.is_empty() || self.state.registers.is_empty() {
+            return;
+        }
+        let names = &self.state.register_names;
+        let prev = self.changes_prev.registers.as_deref().unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            for pin in &self.settings.pinned_registers {
+                let Some(reg) = self
+                    .state
+                    .registers
+                    .iter()
+                    .find(|r| names.get(r.number as usize) == Some(pin))
+                else {
+                    continue;
+                };
+                let changed = prev.iter().any(|(n, v)| n == pin && *v != reg.value);
+                ui.label(m(pin, 10.5, TXT_DIM));
+                ui.label(m(&reg.value, 10.5, if changed { TXT_YELLOW } else { TXT }));
+                ui.add_space(10.0);
+            }
+        });
+        hl(ui);
+    }
+
+    /// Once per loaded program, as soon as it turns out to be C++ or
+    /// Rust: their std containers are unreadable without the printers.
+    fn enable_pretty_printing(&mut self) {
+        let language = self.state.language;
+        if language.pretty_printing() && !self.pretty_printing {
+            self.pretty_printing = true;
+            self.send(Command::EnablePrettyPrinting);
+            self.console_log.push(format!(
+                "[UI] ✓ {} program: pretty-printing on",
+                language.label()
+            ));
+        }
+    }
+
+    /// Refreshes every view for a stop the user gets to see.
+    fn on_stop(&mut self) {
+        self.start_crash_report();
+        if std::mem::take(&mut self.insn_step_pending) {
+            self.step_count += 1;
+        }
+        self.rbreak_base = None;
+        self.frame_eval = None;
+        self.selected_frame = 0;
+        self.stack_cursor = 0;
+        self.stale = StaleViews::all();
+        // A focus region keeps its disassembly; only the highlight moves
+        self.stale.disasm = self.disasm_follow_pc && self.focus.is_none();
+        self.request_visible();
+        self.request_stack_page(0);
+        self.start_snapshot();
+        self.send(Command::RequestThreads);
+        self.errno = None;
+        self.syscall_ret = None;
+        if self.settings.show_errno {
+            self.send(Command::Evaluate(ERRNO_EXPR.into()));
+            if let Some(pause) = &self.state.pause
+                && let StopReason::SyscallReturn(name) = &pause.stop_reason
+                && let Some(reg) = errno::syscall_return_register(&self.state.register_names)
+            {
+                self.send(Command::Evaluate(reg.clone()));
+                self.syscall_ret = Some(SyscallResult {
+                    name: name.clone(),
+                    register: reg,
+                    value: None,
+                });
+            }
+        }
+        if self.state.language == Language::Unknown {
+            self.send(Command::RequestLanguage);
+        }
+        if self.open_memory || self.mem_watch.is_some() {
+            self.refresh_memory();
+        }
+        if self.open_compare {
+            for cmd in self.compare.requests() {
+                self.send(cmd);
+            }
+        }
+        self.send_watch_requests(watch_requests(&self.state, &self.watch_creating, true));
+        if !self.settings.pinned_registers.is_empty() {
+            for cmd in self.stale.requests_for(WatchTab::Registers) {
+                self.send(cmd);
+            }
+        }
+        // Inline values need locals whichever tab is showing
+        if self.settings.inline_values {
+            for cmd in self.stale.requests_for(WatchTab::Watch) {
+                self.send(cmd);
+            }
+        }
+        // After the views' own requests, so they never wait on these
+        let hooks = stop_commands(&self.settings.stop_commands);
+        if !hooks.is_empty() {
+            self.stop_output.clear();
+        }
+        for cmd in hooks {
+            self.send(cmd);
+        }
+    }
+
+    fn on_gdb_error(&mut self, err: String, command: Option<String>) {
+        self.console_log.push(format!("[ERROR] {err}"));
+        if hw_watchpoints_exhausted(&err) {
+            self.console_log.push(
+                "[UI] ⚠ Out of hardware watchpoints (x86 has 4 debug registers): \
+                 delete one, or `set can-use-hw-watchpoints 0` for slow software ones"
+                    .into(),
+            );
+        }
+        if let Some(command) = &command
+            && ambiguous_location(&err)
+            && let Some(spec) = self.bp_inserts.remove(command)
+            && let Some((file, line)) = break_location(&spec.location)
+        {
+            let known = self
+                .open_tabs
+                .iter()
+                .chain(&self.source_file)
+                .map(String::as_str)
+                .chain(
+                    self.state
+                        .persistent
+                        .breakpoints
+                        .iter()
+                        .map(|b| b.file.as_str()),
+                )
+                .chain(
+                    self.state
+                        .pause
+                        .iter()
+                        .flat_map(|p| &p.stack)
+                        .filter_map(|f| f.file.as_deref()),
+                );
+            let choices = same_basename(file, known);
+            if choices.is_empty() {
+                self.console_log.push(format!(
+                    "[UI] ⚠ {file}:{line} matches more than one file: give its full path"
+                ));
+            } else {
+                self.open_bp = true;
+                self.bp_choices = Some((spec.clone(), line, choices));
+            }
+        }
+        // A smart step can't finish out of the outermost frame: show it
+        if self.smart_step.is_active() && command.as_deref() == Some("-exec-finish") {
+            self.smart_step.cancel();
+            self.on_stop();
+        }
+        self.errors.push(&err, command.as_deref());
+    }
+
+    /// errno as of this stop, by name.
+    fn errno_status(&self, ui: &mut egui::Ui) {
+        if !self.state.is_paused() {
+            return;
+        }
+        let (text, hover) = match &self.errno {
+            Some(Ok(value)) => match errno::describe(value) {
+                Some(text) => (text, "errno at this stop".to_owned()),
+                None => return,
+            },
+            Some(Err(msg)) => ("?".to_owned(), format!("errno unavailable: {msg}")),
+            None => return,
+        };
+        ui.add(egui::Separator::default().vertical());
+        ui.label(m(&format!("errno {text}"), 11.0, TXT_MUTED))
+            .on_hover_text(hover);
+    }
+
+    /// What the syscall a `catch syscall` stopped on returned.
+    fn syscall_status(&self, ui: &mut egui::Ui) {
+        let Some(ret) = &self.syscall_ret else {
+            return;
+        };
+        let Some(Ok(value)) = &ret.value else {
+            return;
+        };
+        let Some(text) = errno::describe_syscall(value) else {
+            return;
+        };
+        ui.add(egui::Separator::default().vertical());
+        ui.label(m(&format!("{} → {text}", ret.name), 11.0, TXT_MUTED))
+            .on_hover_text(format!(
+                "Return value of {}, from {}",
+                ret.name, ret.register
+            ));
+    }
+
+    /// Status bar badge; opens the Errors section.
+    fn errors_status(&mut self, ui: &mut egui::Ui) {
+        let total = self.errors.total();
+        if total == 0 {
+            return;
+        }
+        ui.add(egui::Separator::default().vertical());
+        let badge = egui::Button::new(m(&format!("✗ {total}"), 11.0, RED)).frame(false);
+        if ui
+            .add(badge)
+            .on_hover_text("GDB errors since Run")
+            .clicked()
+        {
+            self.open_errors = true;
+        }
+    }
+
+    /// Distinct errors since Run. Clicking the command puts it back in the
+    /// console input to fix and resend.
+    fn errors_panel(&mut self, ui: &mut egui::Ui) {
+        if self.errors.entries.is_empty() {
+            ui.label(m("No errors since Run", 11.0, TXT_DIM).italics());
+            return;
+        }
+        let mut reshow = None;
+        ScrollArea::vertical()
+            .id_salt("errors")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for e in &self.errors.entries {
+                    ui.horizontal(|ui| {
+                        ui.add_space(8.0);
+                        if e.count > 1 {
+                            ui.label(m(&format!("×{}", e.count), 10.0, TXT_DIM));
+                        }
+                        ui.label(m(&e.msg, 11.0, RED));
+                    });
+                    if let Some(cmd) = &e.command {
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
+                            let link = egui::Button::new(m(cmd, 10.0, TXT_MUTED)).frame(false);
+                            if ui.add(link).on_hover_text("Edit in console").clicked() {
+                                reshow = Some(cmd.clone());
+                            }
+                        });
+                    }
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if ui.small_button("Clear").clicked() {
+                self.errors.clear();
+            }
+        });
+        if let Some(cmd) = reshow {
+            self.console_input = cmd;
+        }
+    }
+
+    /// The file the source view shows: the active tab, else the one
+    /// execution is in.
+    fn viewed_file(&self) -> Option<String> {
+        match self.active_tab {
+            Some(i) => self.open_tabs.get(i).cloned(),
+            None => self.source_file.clone(),
+        }
+    }
+
+    fn copy_location_text(&self) -> Option<String> {
+        Some(copy_location_text(
+            self.state.current_file(),
+            self.state.current_line(),
+            self.state.current_addr()?,
+        ))
+    }
+
+    /// Ctrl+L: the current location as `file:line`, ready for a breakpoint.
+    fn copy_location(&mut self, ctx: &egui::Context) {
+        let Some(here) = self.copy_location_text() else {
+            return;
+        };
+        ctx.copy_text(here.clone());
+        self.console_log.push(format!("[UI] ✓ Copied {here}"));
+    }
+
+    /// `file:line` in the editor from the settings. The file GDB names is
+    /// swapped for the one found on disk.
+    fn open_in_editor(&mut self, file: &str, line: u32) {
+        let file = match (&self.source_file, &self.source_resolved) {
+            (Some(gdb_path), Some((path, _))) if gdb_path == file => path.display().to_string(),
+            _ => file.to_owned(),
+        };
+        match editor::open(&self.settings.editor_command, &file, line) {
+            Ok(()) => self
+                .console_log
+                .push(format!("[UI] ✓ Opened {file}:{line} in the editor")),
+            Err(e) => self
+                .console_log
+                .push(format!("[UI] ✗ Could not start the editor: {e}")),
+        }
+    }
+
+    /// Ctrl+K: bookmark the line under the pointer, or the current one.
+    fn toggle_bookmark(&mut self) {
+        let Some(file) = self.viewed_file() else {
+            return;
+        };
+        let Some(line) = self.hovered_line.or(self.state.current_line()) else {
+            return;
+        };
+        bookmarks::toggle(&mut self.settings.bookmarks, &file, line);
+        self.bookmark_cursor = Some(line);
+    }
+
+    /// F2 / Shift+F2: the next or previous bookmark of the viewed file.
+    fn jump_bookmark(&mut self, forward: bool) {
+        let Some(file) = self.viewed_file() else {
+            return;
+        };
+        let Some(lines) = self.settings.bookmarks.get(&file) else {
+            return;
+        };
+        let from = self
+            .bookmark_cursor
+            .or(self.state.current_line())
+            .unwrap_or(0);
+        let target = if forward {
+            bookmarks::next(lines, from)
+        } else {
+            bookmarks::prev(lines, from)
+        };
+        if let Some(line) = target {
+            self.bookmark_cursor = Some(line);
+            self.scroll_to_line = Some(line);
+        }
+    }
+
+    fn bookmarks_panel(&mut self, ui: &mut egui::Ui) {
+        if self.settings.bookmarks.is_empty() {
+            ui.label(m("Ctrl+K bookmarks a line", 11.0, TXT_DIM).italics());
+            return;
+        }
+        let mut files: Vec<_> = self.settings.bookmarks.iter().collect();
+        files.sort();
+        let mut open = None;
+        let mut remove = None;
+        for (file, lines) in files {
+            for &line in lines {
+                ui.horizontal(|ui| {
+                    ui.add_space(8.0);
+                    let text = format!("{}:{line}", short_name(file));
+                    if ui
+                        .add(egui::Label::new(m(&text, 11.0, TXT_MUTED)).sense(Sense::click()))
+                        .on_hover_text(file.as_str())
+                        .clicked()
+                    {
+                        open = Some((file.clone(), line));
+                    }
+                    if ui.small_button("✕").clicked() {
+                        remove = Some((file.clone(), line));
+                    }
+                });
+            }
+        }
+        if let Some((file, line)) = remove {
+            bookmarks::toggle(&mut self.settings.bookmarks, &file, line);
+        }
+        if let Some((file, line)) = open {
+            self.bookmark_cursor = Some(line);
+            self.open_tab(file, line);
+        }
+    }
+
+    fn profile_panel(&mut self, ui: &mut egui::Ui) {
+        let mut open = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            if self.profile.running {
+                if ui.small_button("Stop").clicked() {
+                    // El step en vuelo para como cualquier otro y refresca las vistas
+                    self.profile.stop();
+                }
+            } else if ui
+                .add_enabled(self.state.is_paused(), egui::Button::new("Start").small())
+                .on_hover_text("Step repeatedly, timing each source line")
+                .on_disabled_hover_text("Pause the program first")
+                .clicked()
+            {
+                self.profile.start();
+                self.profile_step();
+            }
+            if ui.small_button("Reset").clicked() {
+                self.profile.reset();
+            }
+        });
+        if self.profile.is_empty() {
+            ui.label(m("No samples", 11.0, TXT_DIM).italics());
+        }
+        for (file, line, time, steps) in self.profile.hottest(10) {
+            let text = format!(
+                "{}:{line}  {:.1} ms  ×{steps}",
+                short_name(file),
+                time.as_secs_f64() * 1000.0
+            );
+            if ui
+                .add(egui::Label::new(m(&text, 11.0, TXT_MUTED)).sense(Sense::click()))
+                .clicked()
+            {
+                open = Some((file.to_owned(), line));
+            }
+        }
+        if let Some((file, line)) = open {
+            self.open_tab(file, line);
+        }
+    }
+
+    /// Raw MI records with what the parser made of them; dropped ones stand out.
+    fn mi_log_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.checkbox(&mut self.mi_unhandled_only, "Unhandled only");
+            if ui.small_button("Clear").clicked() {
+                self.mi_log.clear();
+            }
+        });
+        if self.mi_log.records.is_empty() {
+            ui.label(m("Recording while open", 11.0, TXT_DIM).italics());
+            return;
+        }
+        ScrollArea::vertical()
+            .id_salt("mi_log")
+            .max_height(240.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for record in &self.mi_log.records {
+                    match &record.event {
+                        Some(_) if self.mi_unhandled_only => {}
+                        Some(event) => {
+                            ui.label(m(&record.line, 11.0, TXT_MUTED))
+                                .on_hover_text(event);
+                        }
+                        None => {
+                            ui.label(m(&record.line, 11.0, TXT_YELLOW))
+                                .on_hover_text("unhandled: the parser produced no event");
+                        }
+                    }
+                }
+            });
+    }
+
+    /// Chronological breakpoint/watchpoint stops; click one to open its source.
+    fn hit_log_panel(&mut self, ui: &mut egui::Ui) {
+        if self.hit_log.is_empty() {
+            ui.label(m("No hits yet", 11.0, TXT_DIM).italics());
+            return;
+        }
+        let mut open = None;
+        ScrollArea::vertical()
+            .id_salt("hit_log")
+            .max_height(200.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for hit in &self.hit_log {
+                    let mut text = format!(
+                        "{} #{} {} [t{}]",
+                        hit.timestamp(),
+                        hit.id,
+                        hit.location(),
+                        hit.thread_id
+                    );
+                    if let Some(value) = &hit.value {
+                        text.push_str("  ");
+                        text.push_str(value);
+                    }
+                    let color = if hit.value.is_some() {
+                        TXT_YELLOW
+                    } else {
+                        TXT_MUTED
+                    };
+                    let resp = ui
+                        .add(egui::Label::new(m(&text, 11.0, color)).sense(Sense::click()))
+                        .on_hover_text(&hit.function);
+                    if resp.clicked()
+                        && let (Some(file), Some(line)) = (&hit.file, hit.line)
+                    {
+                        open = Some((file.clone(), line));
+                    }
+                }
+            });
+        ui.horizontal(|ui| {
+            if ui.small_button("Clear").clicked() {
+                self.hit_log.clear();
+            }
+            if ui
+                .small_button("Reset hit counts")
+                .on_hover_text("Zero the per-breakpoint counts shown in Breakpoints")
+                .clicked()
+            {
+                self.visits.clear();
+            }
+        });
+        if let Some((file, line)) = open {
+            self.open_tab(file, line);
+        }
+    }
+
+    /// Directories to search: GDB's source path, with `$cwd` resolved and
+    /// `$cdir` (per-file) dropped. Falls back to the working directory.
+    fn search_roots(&self) -> Vec<std::path::PathBuf> {
+        let mut roots: Vec<std::path::PathBuf> = vec![];
+        for dir in &self.state.source_dirs {
+            let path = match dir.as_str() {
+                "$cwd" => std::path::PathBuf::from("."),
+                d if d.starts_with('$') => continue,
+                d => std::path::PathBuf::from(d),
+            };
+            if !roots.contains(&path) {
+                roots.push(path);
+            }
+        }
+        if roots.is_empty() {
+            roots.push(".".into());
+        }
+        roots
+    }
+
+    fn start_find(&mut self) {
+        let needle = self.find_query.trim().to_owned();
+        self.find_hits.clear();
+        self.find_rx = None; // cancela la búsqueda anterior
+        if needle.is_empty() {
+            return;
+        }
+        let query = SearchQuery {
+            needle,
+            extensions: parse_extensions(&self.find_exts),
+            max_results: FIND_MAX_RESULTS,
+        };
+        self.find_rx = Some(spawn_search(self.search_roots(), query));
+    }
+
+    fn poll_find(&mut self) {
+        let Some(rx) = &self.find_rx else {
+            return;
+        };
+        let mut got = false;
+        loop {
+            match rx.try_recv() {
+                Ok(hit) => {
+                    self.find_hits.push(hit);
+                    got = true;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.find_rx = None;
+                    break;
+                }
+            }
+        }
+        if got {
+            sort_hits(&mut self.find_hits);
+        }
+    }
+
+    fn find_panel(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.find_query)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("search")
+                    .desired_width(ui.available_width() - 90.0),
+            );
+            submit = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            ui.add(
+                TextEdit::singleline(&mut self.find_exts)
+                    .font(FontId::monospace(11.0))
+                    .desired_width(80.0),
+            )
+            .on_hover_text("File extensions, comma-separated (empty = all)");
+        });
+        if submit {
+            self.start_find();
+        }
+
+        let status = match (&self.find_rx, self.find_hits.len()) {
+            (Some(_), n) => format!("searching… {n}"),
+            (None, n) if n >= FIND_MAX_RESULTS => format!("{n} results (capped)"),
+            (None, n) => format!("{n} results"),
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&status, 11.0, TXT_DIM));
+        });
+
+        let mut open = None;
+        ScrollArea::vertical()
+            .id_salt("find_results")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for hit in &self.find_hits {
+                    let file = short_name(&hit.path);
+                    let text = format!("{file}:{}  {}", hit.line, hit.snippet);
+                    let resp = ui
+                        .add(
+                            egui::Label::new(m(&truncate_chars(&text, 60), 11.0, TXT_MUTED))
+                                .sense(Sense::click()),
+                        )
+                        .on_hover_text(&hit.path);
+                    if resp.clicked() {
+                        open = Some((hit.path.clone(), hit.line));
+                    }
+                }
+            });
+        if let Some((path, line)) = open {
+            self.open_tab(path, line);
+        }
+    }
+
+    /// Functions by regex, queried as the user types; break on one or
+    /// disassemble it.
+    fn symbols_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.symbols.input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("function regex")
+                    .desired_width(ui.available_width() - 8.0),
+            );
+            if resp.changed() {
+                self.symbols.edited(Instant::now());
+            }
+        });
+        let n = self.symbols.results.len();
+        let status = if self.symbols.is_searching() {
+            "searching…".to_owned()
+        } else if self.symbols.truncated {
+            format!("first {MAX_SYMBOLS} functions: narrow the regex")
+        } else {
+            format!("{n} functions")
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m(&status, 11.0, TXT_DIM));
+        });
+
+        let mut open = None;
+        let mut action = None;
+        ScrollArea::vertical()
+            .id_salt("symbol_results")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for sym in &self.symbols.results {
+                    ui.horizontal(|ui| {
+                        ui.add_space(8.0);
+                        let where_ = match (&sym.file, sym.line, sym.addr) {
+                            (Some(file), Some(line), _) => format!("{}:{line}", short_name(file)),
+                            (_, _, Some(addr)) => format!("0x{addr:x}"),
+                            _ => String::new(),
+                        };
+                        let resp = ui
+                            .add(
+                                egui::Label::new(m(&truncate_chars(&sym.name, 32), 11.0, TXT_CYAN))
+                                    .sense(Sense::click()),
+                            )
+                            .on_hover_text(sym.file.as_deref().unwrap_or(&sym.name));
+                        if resp.clicked()
+                            && let (Some(file), Some(line)) = (&sym.file, sym.line)
+                        {
+                            open = Some((file.clone(), line));
+                        }
+                        ui.label(m(&where_, 11.0, TXT_DIM));
+                        if ui
+                            .small_button("●")
+                            .on_hover_text("Set a breakpoint on this function")
+                            .clicked()
+                        {
+                            action = Some(
+                                BreakpointSpec::new(symbols::location(sym)).insert(self.bp_pending),
+                            );
+                        }
+                        if ui
+                            .small_button("asm")
+                            .on_hover_text("Disassemble it in the Data tab")
+                            .clicked()
+                        {
+                            action = Some(Command::DisassembleAt {
+                                start: sym.name.clone(),
+                            });
+                        }
+                    });
+                }
+            });
+        if let Some((path, line)) = open {
+            self.open_tab(path, line);
+        }
+        match action {
+            Some(Command::DisassembleAt { start }) => {
+                self.watch_tab = WatchTab::Data;
+                self.disasm_addr = start.clone();
+                self.act(Command::DisassembleAt { start });
+            }
+            Some(cmd) => self.act(cmd),
+            None => {}
+        }
+    }
+
+    /// The blank first run: nothing loaded, attached or connected, and no
+    /// source open.
+    fn shows_welcome(&self) -> bool {
+        matches!(self.state.program, ProgramState::NoProgramLoaded)
+            && self.state.pause.is_none()
+            && self.open_tabs.is_empty()
+            && self.remote.state == LinkState::Idle
+    }
+
+    /// Ways to get started, in place of an empty source view.
+    fn welcome(&mut self, ui: &mut egui::Ui) {
+        let mut cmds = vec![];
+        let mut connect = None;
+        ui.add_space(ui.available_height() * 0.15);
+        ui.vertical_centered(|ui| {
+            ui.label(m("GDB GUI", 22.0, ACCENT).strong());
+            ui.label(m("No program loaded", 12.0, TXT_DIM));
+            ui.add_space(16.0);
+        });
+        let row = |ui: &mut egui::Ui, label: &str, input: &mut String, hint: &str, button: &str| {
+            ui.label(m(label, 12.0, TXT_MUTED));
+            let resp = ui.add(
+                TextEdit::singleline(input)
+                    .font(FontId::monospace(12.0))
+                    .hint_text(hint)
+                    .desired_width(320.0),
+            );
+            let go = ui.button(button).clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+            ui.end_row();
+            go.then(|| input.trim().to_owned())
+                .filter(|s| !s.is_empty())
+        };
+        ui.vertical_centered(|ui| {
+            egui::Grid::new("welcome")
+                .num_columns(3)
+                .spacing([8.0, 8.0])
+                .show(ui, |ui| {
+                    if let Some(path) =
+                        row(ui, "Executable", &mut self.welcome_exe, "./a.out", "Open")
+                    {
+                        cmds.push(Command::LoadExecutable(path));
+                    }
+                    if let Some(pid) = row(ui, "Process", &mut self.welcome_pid, "pid", "Attach") {
+                        match pid.parse() {
+                            Ok(pid) => cmds.push(Command::Attach(pid)),
+                            Err(_) => self
+                                .console_log
+                                .push(format!("[UI] ✗ Not a process id: {pid}")),
+                        }
+                    }
+                    if let Some(addr) = row(
+                        ui,
+                        "Remote target",
+                        &mut self.remote_input,
+                        "host:port",
+                        "Connect",
+                    ) {
+                        connect = Some(addr);
+                    }
+                    if let Some(core) = row(ui, "Core file", &mut self.welcome_core, "core", "Load")
+                    {
+                        // The executable field, if filled, says whose core it is
+                        let exe = self.welcome_exe.trim();
+                        if !exe.is_empty() {
+                            cmds.push(Command::LoadExecutable(exe.to_owned()));
+                        }
+                        cmds.push(Command::LoadCore(core));
+                    }
+                });
+            if !self.settings.recent_executables.is_empty() {
+                ui.add_space(16.0);
+                ui.label(m("Recent", 12.0, TXT_DIM));
+                for path in &self.settings.recent_executables {
+                    let label = egui::Label::new(m(path, 12.0, TXT_CYAN)).sense(Sense::click());
+                    if ui.add(label).on_hover_text("Load").clicked() {
+                        cmds.push(Command::LoadExecutable(path.clone()));
+                    }
+                }
+            }
+        });
+        for cmd in cmds {
+            self.act(cmd);
+        }
+        if let Some(addr) = connect {
+            let cmd = self.remote.connect(&addr);
+            self.act(cmd);
+        }
+    }
+
+    /// Shows `source` and asks for the executable it was compiled into.
+    pub fn prompt_executable(&mut self, source: String) {
+        self.console_log.push(format!(
+            "[UI] ⚠ {source} is a source file, not an executable; load the compiled binary"
+        ));
+        self.open_tab(source, 1);
+        self.exe_prompt = Some(String::new());
+    }
+
+    fn executable_prompt(&mut self, ui: &mut egui::Ui) {
+        let Some(input) = &mut self.exe_prompt else {
+            return;
+        };
+        let mut load = false;
+        ui.horizontal(|ui| {
+            ui.label(m("⚠ No executable loaded. Binary:", 11.0, TXT_YELLOW));
+            let resp = ui.add(
+                TextEdit::singleline(input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("./a.out")
+                    .desired_width(260.0),
+            );
+            load = ui.small_button("Load").clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        });
+        let path = input.trim().to_owned();
+        // Stays up until GDB reports the program loaded, so a typo can be fixed
+        if load && !path.is_empty() {
+            self.act(Command::LoadExecutable(path));
+        }
+    }
+
+    fn open_tab(&mut self, path: String, line: u32) {
+        let idx = match self.open_tabs.iter().position(|p| *p == path) {
+            Some(i) => i,
+            None => {
+                self.open_tabs.push(path.clone());
+                self.open_tabs.len() - 1
+            }
+        };
+        self.cache_source(path);
+        self.active_tab = Some(idx);
+        self.scroll_to_line = Some(line);
+    }
+
+    /// "▶ exec" plus one tab per opened file; shown once a file was opened.
+    fn source_tabs(&mut self, ui: &mut egui::Ui) {
+        if self.open_tabs.is_empty() {
+            return;
+        }
+        let mut close = None;
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_label(self.active_tab.is_none(), m("▶ exec", 11.0, ACCENT))
+                .clicked()
+            {
+                self.active_tab = None;
+            }
+            for (i, path) in self.open_tabs.iter().enumerate() {
+                if ui
+                    .selectable_label(
+                        self.active_tab == Some(i),
+                        m(short_name(path), 11.0, TXT_CYAN),
+                    )
+                    .on_hover_text(path)
+                    .clicked()
+                {
+                    self.active_tab = Some(i);
+                }
+                if ui.small_button("×").clicked() {
+                    close = Some(i);
+                }
+            }
+        });
+        if let Some(i) = close {
+            self.open_tabs.remove(i);
+            self.active_tab = match self.active_tab {
+                Some(a) if a == i => None,
+                Some(a) if a > i => Some(a - 1),
+                a => a,
+            };
+        }
+        hl(ui);
+    }
+
+    /// Resolved path of the execution-view file, flagged when it was only
+    /// matched by name and may not be the file GDB means.
+    fn source_path_header(&mut self, ui: &mut egui::Ui) {
+        let (Some(gdb_path), Some((path, how))) = (&self.source_file, &self.source_resolved) else {
+            return;
+        };
+        let mut edit = false;
+        ui.horizontal(|ui| {
+            ui.label(m(&path.display().to_string(), 11.0, TXT_MUTED))
+                .on_hover_text(format!("GDB: {gdb_path}\nFound by {}", how.describe()));
+            if how.is_fallback() {
+                ui.label(m("⚠ guessed", 11.0, TXT_YELLOW)).on_hover_text(
+                    "Found by file name, not GDB's path: a different file with the same \
+                     name may be showing. Add a source directory or path substitution.",
+                );
+            }
+            edit = ui
+                .small_button("✎")
+                .on_hover_text("Open the current line in the editor")
+                .clicked();
+        });
+        hl(ui);
+        if edit {
+            let file = gdb_path.clone();
+            self.open_in_editor(&file, self.state.current_line().unwrap_or(1));
+        }
+    }
+
+    /// mi-async on/off. Set at spawn; changing it needs no live inferior.
+    fn async_setting(&mut self, ui: &mut egui::Ui) {
+        let before = self.settings.mi_async;
+        let idle = !matches!(
+            self.state.program,
+            ProgramState::Running | ProgramState::Paused
+        );
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.add_enabled(
+                idle,
+                egui::Checkbox::new(&mut self.settings.mi_async, m("mi-async", 11.0, TXT_DIM)),
+            )
+            .on_hover_text("Async mode: GDB keeps reading commands while the target runs")
+            .on_disabled_hover_text("Kill the program to change it");
+        });
+        if self.settings.mi_async != before {
+            self.act(Command::SetMiAsync(self.settings.mi_async));
+        }
+    }
+
+    /// Whether the first fatal signal writes a crash report. UI-side only,
+    /// nothing is sent to GDB.
+    fn crash_report_setting(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.checkbox(
+                &mut self.settings.crash_reports,
+                m("crash reports", 11.0, TXT_DIM),
+            )
+            .on_hover_text(
+                "On the first fatal signal, save backtrace, locals and registers to crash-*.txt",
+            );
+        });
+    }
+
+    /// follow-fork-mode / detach-on-fork dropdowns; applied as soon as they change.
+    fn fork_settings(&mut self, ui: &mut egui::Ui) {
+        let (mode, detach) = (self.settings.follow_fork, self.settings.detach_on_fork);
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m("Follow fork", 11.0, TXT_DIM));
+            egui::ComboBox::from_id_salt("follow_fork")
+                .selected_text(m(self.settings.follow_fork.as_str(), 11.0, TXT))
+                .show_ui(ui, |ui| {
+                    for mode in [ForkMode::Parent, ForkMode::Child] {
+                        ui.selectable_value(&mut self.settings.follow_fork, mode, mode.as_str());
+                    }
+                });
+            ui.label(m("Detach", 11.0, TXT_DIM));
+            egui::ComboBox::from_id_salt("detach_on_fork")
+                .selected_text(m(
+                    if self.settings.detach_on_fork {
+                        "on"
+                    } else {
+                        "off"
+                    },
+                    11.0,
+                    TXT,
+                ))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.settings.detach_on_fork, true, "on");
+                    ui.selectable_value(&mut self.settings.detach_on_fork, false, "off");
+                });
+        });
+        if self.settings.follow_fork != mode {
+            self.act(Command::SetFollowForkMode(self.settings.follow_fork));
+        }
+        if self.settings.detach_on_fork != detach {
+            self.act(Command::SetDetachOnFork(self.settings.detach_on_fork));
+        }
+    }
+
+    /// GDB's source path, plus a field to append directories to it.
+    fn source_dirs_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m("Source directories", 11.0, TXT_DIM));
+            if ui
+                .small_button("⟳")
+                .on_hover_text("show directories")
+                .clicked()
+            {
+                self.send(Command::RequestSourceDirs);
+            }
+        });
+        for dir in &self.state.source_dirs {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.label(m(dir, 11.0, TXT_MUTED));
+            });
+        }
+
+        let mut add = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.source_dir_input)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("/path/to/src")
+                    .desired_width(ui.available_width() - 50.0),
+            );
+            add = ui.small_button("Add").clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        });
+        let dir = self.source_dir_input.trim().to_owned();
+        if add && !dir.is_empty() {
+            self.source_dir_input.clear();
+            // La respuesta trae el source-path nuevo; el próximo evento reintenta la carga
+            self.act(Command::AddSourceDir(dir));
+        }
+    }
+
+    /// `set substitute-path` rules, kept in the settings and replayed at startup.
+    fn substitute_paths_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(m("Path substitutions", 11.0, TXT_DIM));
+        });
+        let mut remove = None;
+        for (i, (from, to)) in self.settings.substitute_paths.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                ui.label(m(&format!("{from} → {to}"), 11.0, TXT_MUTED));
+                if ui.small_button("×").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+
+        let mut add = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let w = (ui.available_width() - 60.0) / 2.0;
+            ui.add(
+                TextEdit::singleline(&mut self.subst_input.0)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("/build/path")
+                    .desired_width(w),
+            );
+            let resp = ui.add(
+                TextEdit::singleline(&mut self.subst_input.1)
+                    .font(FontId::monospace(11.0))
+                    .hint_text("/local/path")
+                    .desired_width(w),
+            );
+            add = ui.small_button("Add").clicked()
+                || (resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        });
+
+        let rule = (
+            self.subst_input.0.trim().to_owned(),
+            self.subst_input.1.trim().to_owned(),
+        );
+        if add && !rule.0.is_empty() && !rule.1.is_empty() {
+            self.subst_input = Default::default();
+            self.act(Command::SubstitutePath {
+                from: rule.0.clone(),
+                to: rule.1.clone(),
+            });
+            // GDB reemplaza la regla con el mismo `from`
+            self.settings.substitute_paths.retain(|(f, _)| *f != rule.0);
+            self.settings.substitute_paths.push(rule);
+            self.retry_sources();
+        }
+        if let Some(i) = remove {
+            let (from, _) = self.settings.substitute_paths.remove(i);
+            self.act(Command::RemoveSubstitutePath(from));
+            self.retry_sources();
+        }
+    }
+
+    /// Forgets files that failed to load so they're looked up again.
+    fn retry_sources(&mut self) {
+        self.source_cache.retain(|_, lines| lines.is_some());
+        self.source_file = None;
+        self.load_source_if_needed();
+    }
+
+    fn load_source_if_needed(&mut self) {
+        let target_file = match self.state.current_file() {
+            Some(f) => f.to_owned(),
+            None => {
+                self.source_lines.clear();
+                self.source_file = None;
+                return;
+            }
+        };
+
+        if self.source_file.as_deref() == Some(&target_file) {
+            return;
+        }
+
+        self.console_log
+            .push(format!("[DEBUG] GDB says file is: {:?}", target_file));
+        self.console_log.push(format!(
+            "[DEBUG] Current dir: {:?}",
+            std::env::current_dir()
+        ));
+
+        let content = self.try_load_source(&target_file);
+
+        match content {
+            Some((text, resolved, how)) => {
+                self.source_lines = text
+                    .lines()
+                    .enumerate()
+                    .map(|(i, line)| SourceLine {
+                        number: (i + 1) as u32,
+                        text: line.to_owned(),
+                    })
+                    .collect();
+                self.source_file = Some(target_file.clone());
+                let resolved = std::fs::canonicalize(&resolved).unwrap_or(resolved);
+                if how.is_fallback() {
+                    self.console_log.push(format!(
+                        "[UI] ⚠ {target_file} resolved to {} by {}",
+                        resolved.display(),
+                        how.describe()
+                    ));
+                }
+                self.source_resolved = Some((resolved, how));
+                self.console_log.push(format!(
+                    "[UI] ✓ Loaded {} ({} lines)",
+                    target_file,
+                    self.source_lines.len()
+                ));
+            }
+            None => {
+                self.console_log
+                    .push(format!("[UI] ✗ Could not find source file: {target_file}"));
+                self.console_log.push("[UI] Tried:".into());
+                self.console_log.push(format!("  1. {target_file}"));
+                if let Some(filename) = std::path::Path::new(&target_file).file_name() {
+                    self.console_log
+                        .push(format!("  2. {}", filename.to_string_lossy()));
+                    self.console_log
+                        .push(format!("  3. src/{}", filename.to_string_lossy()));
+                    self.console_log
+                        .push("  4. each source directory (Files panel)".into());
+                }
+                self.source_lines.clear();
+                self.source_file = None;
+                self.source_resolved = None;
+            }
+        }
+    }
+
+    fn cache_source(&mut self, path: String) {
+        if self.source_cache.contains_key(&path) {
+            return;
+        }
+        let lines = self.try_load_source(&path).map(|(text, resolved, _)| {
+            let resolved = std::fs::canonicalize(&resolved).unwrap_or(resolved);
+            self.tab_resolved.insert(path.clone(), resolved);
+            text.lines().map(str::to_owned).collect()
+        });
+        self.source_cache.insert(path, lines);
+    }
+
+    /// The files on screen: the one execution is in and the open tabs.
+    fn watched_sources(&self) -> Vec<(std::path::PathBuf, String)> {
+        let exec = self
+            .source_file
+            .clone()
+            .zip(self.source_resolved.as_ref())
+            .map(|(name, (path, _))| (path.clone(), name));
+        let tabs = self.open_tabs.iter().filter_map(|name| {
+            let path = self.tab_resolved.get(name)?;
+            Some((path.clone(), name.clone()))
+        });
+        exec.into_iter().chain(tabs).collect()
+    }
+
+    /// Source files changed on disk: reloaded right away, or flagged for the
+    /// Reload banner.
+    fn poll_source_changes(&mut self) {
+        self.source_watch.set(self.watched_sources());
+        for name in self.source_watch.poll(Instant::now()) {
+            if self.settings.auto_reload_sources {
+                self.reload_source(&name);
+            } else {
+                self.source_changed.insert(name);
+            }
+        }
+    }
+
+    fn reload_source(&mut self, name: &str) {
+        self.source_changed.remove(name);
+        if self.source_cache.remove(name).is_some() {
+            self.cache_source(name.to_owned());
+        }
+        if self.source_file.as_deref() == Some(name) {
+            // A different file name makes the next load read it again
+            self.source_file = None;
+            self.load_source_if_needed();
+        } else {
+            self.console_log.push(format!("[UI] ✓ Reloaded {name}"));
+        }
+    }
+
+    /// "changed on disk" bar over the source view, for the file it shows.
+    fn source_changed_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(name) = self
+            .viewed_file()
+            .filter(|f| self.source_changed.contains(f))
+        else {
+            return;
+        };
+        let mut reload = false;
+        ui.horizontal(|ui| {
+            ui.label(m(
+                &format!("⚠ {} changed on disk", short_name(&name)),
+                11.0,
+                TXT_YELLOW,
+            ));
+            reload = ui.small_button("Reload").clicked();
+            if ui.small_button("Ignore").clicked() {
+                self.source_changed.remove(&name);
+            }
+        });
+        hl(ui);
+        if reload {
+            self.reload_source(&name);
+        }
+    }
+
+    /// Contents of `path`, the file actually read and which candidate it was.
+    fn try_load_source(&self, path: &str) -> Option<(String, std::path::PathBuf, SourceMatch)> {
+        use std::path::{Path, PathBuf};
+
+        let read = |p: PathBuf, how| std::fs::read_to_string(&p).ok().map(|c| (c, p, how));
+
+        // 1. Intentar path tal cual (absoluto o relativo desde CWD)
+        if let Some(found) = read(PathBuf::from(path), SourceMatch::Exact) {
+            return Some(found);
+        }
+
+        // Build relocado: el prefijo del CI reemplazado por el local
+        if let Some(local) = substitute_path(path, &self.settings.substitute_paths)
+            && let Some(found) = read(PathBuf::from(local), SourceMatch::Substituted)
+        {
+            return Some(found);
+        }
+
+        let filename = Path::new(path).file_name()?;
+        if let Some(found) = read(PathBuf::from(filename), SourceMatch::FileName) {
+            return Some(found);
+        }
+        if let Some(found) = read(Path::new("src").join(filename), SourceMatch::SrcDir) {
+            return Some(found);
+        }
+
+        // Directorios añadidos a GDB ($cdir/$cwd no son rutas reales)
+        self.state
+            .source_dirs
+            .iter()
+            .filter(|d| !d.starts_with('$'))
+            .find_map(|dir| read(Path::new(dir).join(filename), SourceMatch::SourceDir))
+    }
+}
+
+// ─── eframe::App ──────────────────────────────────────────────────────────────
+
+impl eframe::App for App {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.settings.save(storage);
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        apply_theme(ctx);
+
+        while let Ok(event) = self.event_rx.try_recv() {
+            if self.open_mi_log {
+                self.mi_log.observe(&event);
+            }
+            match event {
+                DebuggerEvent::State(s) => {
+                    let was_paused = matches!(s, StateEvent::ProgramPaused { .. });
+                    let was_loaded = matches!(s, StateEvent::ProgramLoaded { .. });
+                    let locals_changed = matches!(s, StateEvent::LocalsUpdated { .. });
+                    let disasm_changed = matches!(s, StateEvent::DisasmUpdated { .. });
+                    let thread_switched = matches!(s, StateEvent::ThreadSelected { .. });
+                    if matches!(
+                        s,
+                        StateEvent::ProgramExited { .. }
+                            | StateEvent::ProgramKilled
+                            | StateEvent::RemoteDisconnected { .. }
+                    ) {
+                        self.profile.stop();
+                        self.smart_step.cancel();
+                        self.step_until.cancel();
+                    }
+                    self.track_remote(&s);
+                    self.record_snapshot(&s);
+                    if let StateEvent::StackUpdated { frames } = &s {
+                        // A short page means we reached the outermost frame
+                        self.stack_loading = false;
+                        self.stack_more = frames.len() == STACK_PAGE as usize;
+                    }
+                    if let Some(report) = &mut self.crash_report
+                        && report.on_event(&s)
+                        && report.is_complete()
+                    {
+                        self.save_crash_report();
+                    }
+                    if let StateEvent::MemoryRead { block } = &s {
+                        self.mem_changed = match &self.state.memory {
+                            Some(prev) => changed_bytes(prev, block),
+                            None => vec![],
+                        };
+                    }
+                    let watches_updated = matches!(s, StateEvent::WatchesUpdated { .. });
+                    if let StateEvent::WatchCreated { expr, result } = &s {
+                        self.watch_creating.remove(expr);
+                        if let Some(var) = orphaned_varobj(&self.state, expr, result) {
+                            self.send(Command::DeleteVarObject(var));
+                        }
+                    }
+                    let logpoint = self.logpoint_hit(&s);
+                    if let StateEvent::ProgramPaused { pause } = &s {
+                        self.visits.record(pause);
+                    }
+                    if let StateEvent::ProgramPaused { pause } = &s
+                        && let Some(hit) = HitEntry::from_pause(pause, self.started.elapsed())
+                    {
+                        self.hit_log.push(hit);
+                    }
+                    self.state.apply(s);
+                    self.enable_pretty_printing();
+                    self.load_source_if_needed();
+                    self.check_rbreak_count();
+                    if locals_changed {
+                        self.expand_receiver();
+                    }
+                    if disasm_changed {
+                        if self.focus_pending
+                            && self.focus.is_some_and(|r| r.is_listing(&self.state.disasm))
+                        {
+                            self.focus_pending = false;
+                            self.focus_disasm = self.state.disasm.clone();
+                        }
+                        self.request_asm_source_lines();
+                    }
+                    if thread_switched {
+                        self.on_thread_switch();
+                    }
+                    if watches_updated {
+                        self.send_watch_requests(watch_requests(
+                            &self.state,
+                            &self.watch_creating,
+                            false,
+                        ));
+                    }
+                    if was_loaded {
+                        self.asm_line_funcs.clear();
+                        self.bp_inserts.clear();
+                        self.focus = None;
+                        self.focus_disasm.clear();
+                        self.mem_watch = None;
+                        if let Some(exe) = &self.state.persistent.executable {
+                            self.settings.remember_executable(exe);
+                        }
+                        self.send(Command::RequestRegisterNames);
+                        self.send(Command::RequestSourceDirs);
+                        // The new program may be in another language
+                        self.pretty_printing = false;
+                        self.send(Command::SetLanguageAuto);
+                        self.send(Command::RequestLanguage);
+                    }
+                    if let Some((id, template)) = logpoint {
+                        self.start_logpoint(id, &template);
+                    } else if was_paused && self.profile.running {
+                        self.profile_step();
+                    } else if was_paused && self.step_until.is_active() {
+                        self.step_until_stop();
+                    } else if was_paused
+                        && let Some(cmd) = self
+                            .state
+                            .pause
+                            .as_ref()
+                            .and_then(|p| self.smart_step.on_stop(p, &self.state.source_dirs))
+                    {
+                        // Still in library code: views wait for the final stop
+                        self.send(cmd);
+                    } else if was_paused {
+                        self.on_stop();
+                    }
+                }
+                DebuggerEvent::Ui(UiEvent::ConsoleOutput(text)) => {
+                    // Without a terminal GDB takes the default answer itself
+                    if text.starts_with("[answered ")
+                        && let Some((question, _)) = self.query.take()
+                    {
+                        self.console_log
+                            .push(format!("[UI] ⚠ GDB answered by itself: {question}"));
+                    }
+                    self.console_log.push(text);
+                }
+                DebuggerEvent::Ui(UiEvent::MiRecord(line)) => {
+                    self.console_log.push_kind(LineKind::Record, line)
+                }
+                DebuggerEvent::Ui(UiEvent::CommandSent(mi)) => self
+                    .console_log
+                    .push_kind(LineKind::Echo, format!("> {mi}")),
+                DebuggerEvent::Ui(UiEvent::Query {
+                    question,
+                    pagination,
+                }) => {
+                    self.console_log.push(question.clone());
+                    self.query = Some((question, pagination));
+                }
+                DebuggerEvent::Ui(UiEvent::GdbError(err)) => self.on_gdb_error(err, None),
+                // Shown as `errno ?` instead of piling up once per stop
+                DebuggerEvent::Ui(UiEvent::CommandFailed { command, .. })
+                    if errno_request(&command) => {}
+                // Past the outermost frame: the last page came back exactly full
+                DebuggerEvent::Ui(UiEvent::CommandFailed { command, .. })
+                    if stack_page_request(&command) =>
+                {
+                    self.stack_loading = false;
+                    self.stack_more = false;
+                }
+                // Shown as `<error>` next to the watch
+                DebuggerEvent::Ui(UiEvent::CommandFailed { command, .. })
+                    if command.starts_with("-var-") => {}
+                DebuggerEvent::Ui(UiEvent::CommandFailed { command, msg }) => {
+                    self.on_gdb_error(msg, Some(command))
+                }
+                DebuggerEvent::Ui(UiEvent::MemoryWritten { addr, result }) => match result {
+                    Ok(()) => {
+                        self.console_log
+                            .push(format!("[UI] ✓ Wrote memory at 0x{addr:x}"));
+                        self.refresh_memory();
+                    }
+                    Err(msg) if msg.starts_with("Cannot access memory") => self.console_log.push(
+                        format!("[UI] ✗ 0x{addr:x} isn't writable (read-only or unmapped): {msg}"),
+                    ),
+                    Err(msg) => self
+                        .console_log
+                        .push(format!("[UI] ✗ Memory write at 0x{addr:x} failed: {msg}")),
+                },
+                DebuggerEvent::Ui(UiEvent::CommandStalled(command)) => self.stalled = command,
+                DebuggerEvent::Ui(UiEvent::SymbolsFound { regex, symbols }) => {
+                    self.symbols.on_result(&regex, symbols)
+                }
+                DebuggerEvent::Ui(UiEvent::StopHookOutput { command, output }) => {
+                    self.stop_output.push_str(&format!("> {command}\n{output}"));
+                    if !output.ends_with('\n') {
+                        self.stop_output.push('\n');
+                    }
+                }
+                DebuggerEvent::Ui(UiEvent::EvaluationResult { expr, value }) => {
+                    self.on_evaluation_result(expr, value);
+                }
+            }
+        }
+
+        self.poll_find();
+        if self.open_symbols
+            && !matches!(self.state.program, ProgramState::NoProgramLoaded)
+            && let Some(cmd) = self.symbols.poll(Instant::now())
+        {
+            self.send(cmd);
+        }
+        self.poll_source_changes();
+        if let Some(cmd) = self.remote.poll(Instant::now()) {
+            self.act(cmd);
+        }
+        self.query_dialog(ctx);
+        // Ctrl+E, Ctrl+K, Ctrl+L and F2 belong to a text field being typed in
+        let typing = ctx.wants_keyboard_input();
+        if !typing && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, Key::E)) {
+            self.reevaluate();
+        }
+        if !typing && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, Key::K)) {
+            self.toggle_bookmark();
+        }
+        self.keyboard_nav(ctx);
+        if !typing && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, Key::L)) {
+            self.copy_location(ctx);
+        }
+        if !typing && ctx.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, Key::F2)) {
+            self.jump_bookmark(false);
+        }
+        if !typing && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, Key::F2)) {
+            self.jump_bookmark(true);
+        }
+        ctx.request_repaint();
+
+        // ── TOP BAR ───────────────────────────────────────────────────────────
+        egui::TopBottomPanel::top("top_bar")
+            .frame(flat(BG_TOPBAR).inner_margin(Margin {
+                left: 8,
+                right: 8,
+                top: 4,
+                bottom: 4,
+            }))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(m("GDB GUI", 13.0, ACCENT).strong());
+                    ui.add(egui::Separator::default().vertical());
+
+                    let can_run = self.state.can_run();
+                    let run = ui
+                        .add_enabled_ui(can_run, |ui| tbtn(ui, "Run", true))
+                        .inner
+                        .on_disabled_hover_text("GDB is still loading the executable's symbols");
+                    if !can_run {
+                        ui.spinner();
+                        ui.label(m("loading symbols…", 11.0, TXT_DIM).italics());
+                    }
+                    if run.clicked() {
+                        if self.break_at_entry {
+                            for cmd in entry_run_sequence(&self.entry_symbol, self.bp_pending) {
+                                self.act(cmd);
+                            }
+                        } else {
+                            self.act(Command::Run);
+                        }
+                    }
+                    ui.checkbox(&mut self.break_at_entry, m("entry", 11.0, TXT_MUTED))
+                        .on_hover_text(
+                            "Stop at the entry point before any code runs. Leave the symbol \
+                             empty to stop at the very first instruction (starti)",
+                        );
+                    if self.break_at_entry {
+                        ui.add(
+                            TextEdit::singleline(&mut self.entry_symbol)
+                                .font(FontId::monospace(11.0))
+                                .hint_text("_start")
+                                .desired_width(70.0),
+                        );
+                    }
+                    if tbtn(ui, "Continue", false).clicked() {
+                        self.act(Command::Continue);
+                    }
+                    if tbtn(ui, "Step", false).clicked() {
+                        self.act(Command::Step);
+                    }
+                    if tbtn(ui, "Step ⤼", false)
+                        .on_hover_text("Step into, finishing out of library code")
+                        .clicked()
+                    {
+                        let cmd = self.smart_step.start();
+                        self.act(cmd);
+                    }
+                    if tbtn(ui, "Next", false).clicked() {
+                        self.act(Command::Next);
+                    }
+                    if tbtn(ui, "Finish", false).clicked() {
+                        self.act(Command::Finish);
+                    }
+                    if tbtn(ui, "Stepi", false)
+                        .on_hover_text("Step one instruction")
+                        .clicked()
+                    {
+                        self.act(Command::StepInstruction);
+                    }
+                    if tbtn(ui, "Nexti", false)
+                        .on_hover_text("Step one instruction, over calls")
+                        .clicked()
+                    {
+                        self.act(Command::NextInstruction);
+                    }
+                    let here = line_breakpoint_toggle(&self.state, self.bp_pending);
+                    let has_bp = matches!(here, Some(Command::RemoveBreakpoint(_)));
+                    if ui
+                        .add_enabled_ui(here.is_some(), |ui| tbtn(ui, "● Here", has_bp))
+                        .inner
+                        .on_hover_text("Toggle a breakpoint at the current line")
+                        .on_disabled_hover_text("Pause at a line with source first")
+                        .clicked()
+                        && let Some(cmd) = here
+                    {
+                        self.act(cmd);
+                    }
+                    if ui
+                        .add_enabled_ui(can_run, |ui| tbtn(ui, "Restart", false))
+                        .inner
+                        .clicked()
+                    {
+                        self.act(Command::Restart);
+                    }
+                    if ui
+                        .add_enabled_ui(self.state.is_running(), |ui| tbtn(ui, "Pause", false))
+                        .inner
+                        .clicked()
+                    {
+                        self.act(Command::Interrupt);
+                    }
+                    let alive = self.state.is_running() || self.state.is_paused();
+                    if ui
+                        .add_enabled_ui(alive, |ui| tbtn(ui, "Kill", false))
+                        .inner
+                        .on_hover_text("Terminate the program, keep GDB and the executable loaded")
+                        .clicked()
+                    {
+                        self.act(Command::Kill);
+                    }
+                    self.inferior_selector(ui);
+
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        let (r, _) = ui.allocate_exact_size(Vec2::splat(12.0), Sense::hover());
+                        let color = if self.state.is_running() {
+                            ACCENT
+                        } else if self.state.is_paused() {
+                            TXT_YELLOW
+                        } else {
+                            TXT_DIM
+                        };
+                        ui.painter().rect_filled(r, 2.0, color);
+                        ui.add_space(6.0);
+
+                        let status = match &self.state.program {
+                            ProgramState::NoProgramLoaded => "No program loaded",
+                            ProgramState::ProgramLoaded if self.state.symbols_loading => {
+                                "Loading symbols…"
+                            }
+                            ProgramState::ProgramLoaded => "Loaded",
+                            ProgramState::Running => "Running",
+                            ProgramState::Paused => "Paused",
+                            ProgramState::Exited { .. } => "Exited",
+                        };
+
+                        let location =
+                            match (self.state.current_function(), self.state.current_addr()) {
+                                (Some(func), Some(addr)) => location_text(
+                                    self.state.current_file(),
+                                    func,
+                                    addr,
+                                    self.settings.show_pc,
+                                ),
+                                _ => status.to_owned(),
+                            };
+
+                        let here = self.copy_location_text();
+                        let resp = ui.add(
+                            egui::Label::new(m(&location, 11.0, TXT_MUTED)).sense(Sense::click()),
+                        );
+                        if let Some(here) = here
+                            && resp
+                                .on_hover_text(format!("Click to copy {here} (Ctrl+L)"))
+                                .clicked()
+                        {
+                            self.copy_location(ui.ctx());
+                        }
+                        ui.add(egui::Separator::default().vertical());
+                        ui.label(m(
+                            if self.settings.mi_async {
+                                "async"
+                            } else {
+                                "sync"
+                            },
+                            11.0,
+                            TXT_DIM,
+                        ))
+                        .on_hover_text("GDB mi-async mode");
+                        if self.state.language != Language::Unknown {
+                            ui.add(egui::Separator::default().vertical());
+                            ui.label(m(self.state.language.label(), 11.0, TXT_DIM))
+                                .on_hover_text("Source language");
+                        }
+                        if self.step_count > 0 {
+                            ui.add(egui::Separator::default().vertical());
+                            ui.label(m(&format!("{} insn", self.step_count), 11.0, TXT_CYAN))
+                                .on_hover_text("Instruction steps since Run");
+                        }
+                        self.reconnect_status(ui);
+                        self.syscall_status(ui);
+                        self.errno_status(ui);
+                        self.errors_status(ui);
+                        ui.add(egui::Separator::default().vertical());
+                        self.layout_picker(ui);
+                    });
+                });
+            });
+
+        if self.exe_prompt.is_some() && self.state.persistent.executable.is_none() {
+            egui::TopBottomPanel::top("exe_prompt")
+                .frame(flat(BG_PANEL).inner_margin(Margin {
+                    left: 8,
+                    right: 8,
+                    top: 4,
+                    bottom: 4,
+                }))
+                .show(ctx, |ui| self.executable_prompt(ui));
+        }
+
+        if self.stalled.is_some() {
+            egui::TopBottomPanel::top("stalled")
+                .frame(flat(BG_PANEL).inner_margin(Margin {
+                    left: 8,
+                    right: 8,
+                    top: 4,
+                    bottom: 4,
+                }))
+                .show(ctx, |ui| self.stall_banner(ui));
+        }
+
+        // ── BREADCRUMB ────────────────────────────────────────────────────────
+        if self.state.pause.is_some() {
+            egui::TopBottomPanel::top("breadcrumb")
+                .frame(flat(BG_PANEL).inner_margin(Margin {
+                    left: 8,
+                    right: 8,
+                    top: 2,
+                    bottom: 2,
+                }))
+                .show(ctx, |ui| self.breadcrumb_bar(ui));
+        }
+
+        // ── CONSOLE (bottom) ──────────────────────────────────────────────────
+        let (side_width, console_height) = self.settings.layout.panel_sizes();
+        // A preset just picked forces its sizes for one frame
+        let resize = self.panel_resize.take();
+        egui::TopBottomPanel::bottom("console")
+            .resizable(true)
+            .height_range(match resize {
+                Some((_, height)) => egui::Rangef::point(height),
+                None => egui::Rangef::new(50.0, f32::INFINITY),
+            })
+            .default_height(console_height)
+            .frame(flat(BG_CONSOLE))
+            .show(ctx, |ui| {
+                // Header fijo arriba
+                Frame::new()
+                    .fill(BG_TOPBAR)
+                    .inner_margin(Margin {
+                        left: 8,
+                        right: 8,
+                        top: 3,
+                        bottom: 3,
+                    })
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            for (tab, name) in [
+                                (ConsoleTab::Gdb, "Console"),
+                                (ConsoleTab::Output, "Output"),
+                                (ConsoleTab::Stop, "On stop"),
+                            ] {
+                                let col = if self.console_tab == tab {
+                                    TXT
+                                } else {
+                                    TXT_MUTED
+                                };
+                                if ui
+                                    .selectable_label(self.console_tab == tab, m(name, 11.0, col))
+                                    .clicked()
+                                {
+                                    self.console_tab = tab;
+                                }
+                            }
+                            if self.console_tab == ConsoleTab::Gdb {
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    for level in Verbosity::ALL.into_iter().rev() {
+                                        let on = self.settings.console_verbosity == level;
+                                        let col = if on { TXT } else { TXT_DIM };
+                                        if ui
+                                            .selectable_label(on, m(level.label(), 10.0, col))
+                                            .clicked()
+                                        {
+                                            self.settings.console_verbosity = level;
+                                        }
+                                    }
+                                });
+                            }
+                        });
+                    });
+                hl(ui);
+
+                ui.with_layout(Layout::bottom_up(Align::LEFT), |ui| {
+                    // Input line
+                    Frame::new()
+                        .fill(BG_CONSOLE)
+                        .inner_margin(Margin {
+                            left: 8,
+                            right: 8,
+                            top: 3,
+                            bottom: 3,
+                        })
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(m("(gdb)", 12.0, ACCENT));
+                                ui.add_space(4.0);
+                                let resp = ui.add(
+                                    TextEdit::singleline(&mut self.console_input)
+                                        .font(FontId::monospace(12.0))
+                                        .desired_width(ui.available_width())
+                                        .frame(false)
+                                        .text_color(Color32::from_rgb(0xe0, 0xe0, 0xe0)),
+                                );
+                                if resp.lost_focus() && ctx.input(|i| i.key_pressed(Key::Enter)) {
+                                    let raw = self.console_input.trim().to_owned();
+                                    // Enter vacío repite el último comando, como en GDB
+                                    let cmd = if raw.is_empty() {
+                                        repeat_command(&self.console_history).map(str::to_owned)
+                                    } else {
+                                        self.console_history.push(raw.clone());
+                                        Some(raw)
+                                    };
+                                    // Its value goes to the console; ↻ re-evaluates it here
+                                    if let Some(expr) = cmd.as_deref().and_then(print_expression) {
+                                        self.last_eval = Some(expr.to_owned());
+                                        self.last_eval_result = None;
+                                        self.last_eval_pending = false;
+                                    }
+                                    if let Some(cmd) = cmd {
+                                        self.act(Command::Raw(cmd));
+                                    }
+                                    self.console_input.clear();
+                                    resp.request_focus();
+                                }
+                            });
+                        });
+
+                    hl(ui);
+
+                    ScrollArea::vertical()
+                        .id_salt("con_log")
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            ui.add_space(2.0);
+                            let lines: Box<dyn Iterator<Item = &str>> = match self.console_tab {
+                                ConsoleTab::Gdb => Box::new(
+                                    self.console_log.shown(self.settings.console_verbosity),
+                                ),
+                                ConsoleTab::Output => Box::new(self.state.program_output.lines()),
+                                ConsoleTab::Stop => Box::new(self.stop_output.lines()),
+                            };
+                            for line in lines {
+                                ui.horizontal(|ui| {
+                                    ui.add_space(6.0);
+                                    ui.label(m(line, 11.0, TXT));
+                                });
+                            }
+                            ui.add_space(2.0);
+                        });
+                });
+            });
+
+        // ── RIGHT PANEL ───────────────────────────────────────────────────────
+        egui::SidePanel::right("right_panel")
+            .resizable(true)
+            .width_range(match resize {
+                Some((width, _)) => egui::Rangef::point(width),
+                None => egui::Rangef::new(180.0, f32::INFINITY),
+            })
+            .default_width(side_width)
+            .frame(flat(BG_PANEL))
+            .show(ctx, |ui| {
+                // Upper collapsible sections
+                ScrollArea::vertical()
+                    .id_salt("right_upper")
+                    .max_height(ui.available_height() * 0.52)
+                    .show(ui, |ui| {
+                        ui.set_min_width(ui.available_width());
+
+                        // BREAKPOINTS ──────────────────────────────────────────
+                        sec_hdr(ui, "Breakpoints", &mut self.open_bp);
+                        if self.open_bp {
+                            let mut edit_log = None;
+                            let mut edit_cmds = None;
+                            let mut edit_cond = None;
+                            let mut reset_visits = None;
+                            let mut toggle = None;
+                            let mut remove = None;
+                            egui::Grid::new("bp_grid")
+                                .num_columns(3)
+                                .spacing([8.0, 2.0])
+                                .show(ui, |ui| {
+                                    for h in ["File", "Line", ""] {
+                                        ui.label(m(h, 11.0, TXT_DIM));
+                                    }
+                                    ui.end_row();
+
+                                    for bp in &self.state.persistent.breakpoints {
+                                        // Nombre corto del archivo
+                                        let short_file = bp
+                                            .file
+                                            .split('/')
+                                            .next_back()
+                                            .or_else(|| bp.file.split('\\').next_back())
+                                            .unwrap_or(&bp.file);
+
+                                        // What the user typed reads better than where
+                                        // a function breakpoint resolved to
+                                        let spec = bp.original_location.as_deref().filter(|loc| {
+                                            !loc.ends_with(&format!(":{}", bp.line))
+                                        });
+                                        let what = bp.what.as_deref().unwrap_or_default();
+                                        let file_label = match (bp.kind, spec) {
+                                            (BreakpointKind::Watch, _) => format!("👁 {what}"),
+                                            (BreakpointKind::Catch, _) => format!("catch {what}"),
+                                            (BreakpointKind::Address, _) => what.to_owned(),
+                                            (BreakpointKind::Trace, _) => {
+                                                format!("trace {short_file}")
+                                            }
+                                            _ if bp.log_message.is_some() => {
+                                                format!("◆ {short_file}")
+                                            }
+                                            (_, Some(spec)) if !bp.pending => spec.to_owned(),
+                                            _ => short_file.to_owned(),
+                                        };
+                                        let file_label = if bp.commands.is_empty() {
+                                            file_label
+                                        } else {
+                                            format!("{file_label} ⚙")
+                                        };
+                                        let visits = self.visits.get(bp.id);
+                                        let file_label = if visits == 0 {
+                                            file_label
+                                        } else {
+                                            format!("{file_label} [{visits}]")
+                                        };
+                                        let (color, hover) = match bp.kind {
+                                            _ if !bp.enabled => (TXT_DIM, "Disabled"),
+                                            _ if bp.pending => {
+                                                (TXT_MUTED, "Pending: location not loaded yet")
+                                            }
+                                            BreakpointKind::Watch => (TXT_YELLOW, "Watchpoint"),
+                                            BreakpointKind::Catch => (RED, "Catchpoint"),
+                                            BreakpointKind::Address => {
+                                                (TXT_MUTED, "No line info: stops at this address")
+                                            }
+                                            BreakpointKind::Trace => {
+                                                (TXT_CYAN, "Tracepoint: collects without stopping")
+                                            }
+                                            BreakpointKind::Line => (
+                                                TXT_CYAN,
+                                                bp.log_message.as_deref().unwrap_or(&bp.file),
+                                            ),
+                                        };
+                                        let resp = ui
+                                            .add(
+                                                egui::Label::new(m(&file_label, 12.0, color))
+                                                    .sense(Sense::click()),
+                                            )
+                                            .on_hover_ui(|ui| {
+                                                ui.label(hover);
+                                                if visits > 0 {
+                                                    ui.label(m(
+                                                        &format!(
+                                                            "Hit {visits}× this session"
+                                                        ),
+                                                        11.0,
+                                                        TXT_MUTED,
+                                                    ));
+                                                }
+                                                if let Some(cond) = &bp.condition {
+                                                    ui.label(m(
+                                                        &format!("if {cond}"),
+                                                        11.0,
+                                                        TXT_YELLOW,
+                                                    ));
+                                                }
+                                                if !bp.commands.is_empty() {
+                                                    ui.label(m(
+                                                        &bp.commands.join("\n"),
+                                                        11.0,
+                                                        TXT_MUTED,
+                                                    ));
+                                                }
+                                            });
+                                        resp.context_menu(|ui| {
+                                            let label =
+                                                if bp.enabled { "Disable" } else { "Enable" };
+                                            if ui.button(label).clicked() {
+                                                toggle = Some((bp.id, !bp.enabled));
+                                                ui.close();
+                                            }
+                                            if ui.button("Edit condition…").clicked() {
+                                                edit_cond = Some(CondEdit::new(
+                                                    bp.id,
+                                                    bp.condition.as_deref(),
+                                                ));
+                                                ui.close();
+                                            }
+                                            if ui.button("Edit commands…").clicked() {
+                                                edit_cmds = Some((bp.id, bp.commands.join("\n")));
+                                                ui.close();
+                                            }
+                                            if ui.button("Set log message…").clicked() {
+                                                edit_log = Some((
+                                                    bp.id,
+                                                    bp.log_message.clone().unwrap_or_default(),
+                                                ));
+                                                ui.close();
+                                            }
+                                            if bp.log_message.is_some()
+                                                && ui.button("Clear log message").clicked()
+                                            {
+                                                edit_log = Some((bp.id, String::new()));
+                                                ui.close();
+                                            }
+                                            if visits > 0
+                                                && ui.button("Reset hit count").clicked()
+                                            {
+                                                reset_visits = Some(bp.id);
+                                                ui.close();
+                                            }
+                                        });
+                                        let line = if bp.line > 0 {
+                                            bp.line.to_string()
+                                        } else {
+                                            String::new()
+                                        };
+                                        ui.label(m(&line, 12.0, TXT_YELLOW));
+                                        if ui
+                                            .add(
+                                                egui::Button::new(m("×", 12.0, RED))
+                                                    .fill(Color32::TRANSPARENT)
+                                                    .stroke(Stroke::NONE),
+                                            )
+                                            .clicked()
+                                        {
+                                            remove = Some(bp.id);
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            if let Some((id, enable)) = toggle {
+                                self.act(Command::ToggleBreakpoint { id, enable });
+                            }
+                            if let Some(id) = remove {
+                                self.act(Command::RemoveBreakpoint(id));
+                            }
+                            if let Some(id) = reset_visits {
+                                self.visits.reset(id);
+                            }
+                            if let Some((id, msg)) = edit_log {
+                                if msg.is_empty() {
+                                    self.state
+                                        .apply(StateEvent::LogMessageSet { id, message: None });
+                                } else {
+                                    self.log_edit = Some((id, msg));
+                                }
+                            }
+                            if edit_cmds.is_some() {
+                                self.cmds_edit = edit_cmds;
+                            }
+                            if edit_cond.is_some() {
+                                self.cond_edit = edit_cond;
+                            }
+                            self.logpoint_editor(ui);
+                            self.breakpoint_commands_editor(ui);
+                            self.breakpoint_condition_editor(ui);
+                            self.breakpoint_choices(ui);
+                            self.rbreak_row(ui);
+                            self.breakpoint_script_row(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // COMMANDS ──────────────────────────────────────────────
+                        sec_hdr(ui, "Commands", &mut self.open_cmd);
+                        if self.open_cmd {
+                            for cmd_str in
+                                &["info locals", "bt full", "info registers", "info threads"]
+                            {
+                                if ui
+                                    .add(
+                                        egui::Button::new(m(cmd_str, 11.0, TXT_CYAN))
+                                            .fill(Color32::TRANSPARENT)
+                                            .stroke(Stroke::NONE)
+                                            .min_size(Vec2::new(ui.available_width(), 18.0)),
+                                    )
+                                    .clicked()
+                                {
+                                    self.act(Command::Raw(cmd_str.to_string()));
+                                }
+                            }
+                            self.custom_commands(ui);
+                            self.stop_commands_editor(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // COMMAND LOG ───────────────────────────────────────────
+                        sec_hdr(ui, "Command Log", &mut self.open_cmd_log);
+                        if self.open_cmd_log {
+                            if self.command_log.is_empty() {
+                                ui.label(m("No actions yet", 11.0, TXT_DIM).italics());
+                            }
+                            for line in self.command_log.iter().flat_map(|c| c.lines()) {
+                                ui.label(m(line, 11.0, TXT_MUTED));
+                            }
+                            ui.horizontal(|ui| {
+                                if ui.small_button("Copy").clicked() {
+                                    ui.ctx().copy_text(self.command_log.join("\n") + "\n");
+                                }
+                                if ui.small_button("Clear").clicked() {
+                                    self.command_log.clear();
+                                }
+                            });
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // MI RAW ────────────────────────────────────────────────
+                        sec_hdr(ui, "MI Raw", &mut self.open_mi_log);
+                        if self.open_mi_log {
+                            self.mi_log_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // HIT LOG ───────────────────────────────────────────────
+                        sec_hdr(ui, "Hit Log", &mut self.open_hit_log);
+                        if self.open_hit_log {
+                            self.hit_log_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // BOOKMARKS ─────────────────────────────────────────────
+                        sec_hdr(ui, "Bookmarks", &mut self.open_bookmarks);
+                        if self.open_bookmarks {
+                            self.bookmarks_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // LAUNCH ────────────────────────────────────────────────
+                        sec_hdr(ui, "Launch", &mut self.open_launch);
+                        if self.open_launch {
+                            self.launch_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // ERRORS ────────────────────────────────────────────────
+                        sec_hdr(ui, "Errors", &mut self.open_errors);
+                        if self.open_errors {
+                            self.errors_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // MEMORY ────────────────────────────────────────────────
+                        sec_hdr(ui, "Memory", &mut self.open_memory);
+                        if self.open_memory {
+                            self.memory_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // MAPPINGS ──────────────────────────────────────────────
+                        sec_hdr(ui, "Mappings", &mut self.open_mappings);
+                        if self.open_mappings {
+                            self.mappings_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // CHANGES ───────────────────────────────────────────────
+                        sec_hdr(ui, "Changes", &mut self.open_changes);
+                        if self.open_changes {
+                            self.changes_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // STEP PROFILE ──────────────────────────────────────────
+                        sec_hdr(ui, "Step Profile", &mut self.open_profile);
+                        if self.open_profile {
+                            self.profile_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // STEP UNTIL ────────────────────────────────────────────
+                        sec_hdr(ui, "Step Until", &mut self.open_step_until);
+                        if self.open_step_until {
+                            self.step_until_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // COMPARE ───────────────────────────────────────────────
+                        sec_hdr(ui, "Compare", &mut self.open_compare);
+                        if self.open_compare {
+                            self.compare_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // STRUCT ────────────────────────────────────────────────
+                        sec_hdr(ui, "Struct", &mut self.open_struct);
+                        if self.open_struct {
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.label(
+                                    RichText::new("No struct selected")
+                                        .color(TXT_DIM)
+                                        .font(FontId::monospace(11.0))
+                                        .italics(),
+                                );
+                            });
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // STACK ─────────────────────────────────────────────────
+                        sec_hdr(ui, "Stack", &mut self.open_stack);
+                        if self.open_stack {
+                            let mut preview_miss = None;
+                            let mut load_more = None;
+                            let mut clicked = None;
+                            if let Some(pause) = &self.state.pause {
+                                egui::Grid::new("stack_grid")
+                                    .num_columns(3)
+                                    .spacing([6.0, 2.0])
+                                    .show(ui, |ui| {
+                                        for h in ["#", "Function", "Location"] {
+                                            ui.label(m(h, 11.0, TXT_DIM));
+                                        }
+                                        ui.end_row();
+
+                                        for (idx, frame) in pause.stack.iter().enumerate() {
+                                            let active = frame.level == self.selected_frame;
+                                            let cursor = self.nav == Some(NavPanel::Stack)
+                                                && idx == self.stack_cursor;
+
+                                            let (stripe, _) = ui.allocate_exact_size(
+                                                Vec2::new(2.0, 14.0),
+                                                Sense::hover(),
+                                            );
+                                            if cursor {
+                                                ui.painter().rect_filled(stripe, 0.0, ACCENT);
+                                            } else if active {
+                                                ui.painter().rect_filled(stripe, 0.0, BLUE);
+                                            }
+
+                                            let fn_col = if active { BLUE } else { TXT_CYAN };
+                                            ui.label(m(&idx.to_string(), 11.0, TXT_DIM));
+                                            let call = frame_call(frame);
+                                            let mut resp = ui.add(
+                                                egui::Label::new(m(
+                                                    &truncate_chars(&call, FRAME_CALL_MAX),
+                                                    11.0,
+                                                    fn_col,
+                                                ))
+                                                .sense(Sense::click()),
+                                            );
+                                            if !frame.args.is_empty() {
+                                                resp = resp.on_hover_text(&call);
+                                            }
+                                            if resp.clicked() {
+                                                clicked = Some((idx, frame.level));
+                                            }
+
+                                            let loc = if let (Some(file), Some(line)) =
+                                                (&frame.file, frame.line)
+                                            {
+                                                let short = file
+                                                    .split('/')
+                                                    .next_back()
+                                                    .or_else(|| file.split('\\').next_back())
+                                                    .unwrap_or(file);
+                                                format!("{short}:{line}")
+                                            } else {
+                                                format!("0x{:x}", frame.addr)
+                                            };
+                                            ui.label(m(&loc, 11.0, TXT_MUTED)).on_hover_ui(|ui| {
+                                                match (&frame.file, frame.line) {
+                                                    (Some(file), Some(line)) => {
+                                                        match self.source_cache.get(file) {
+                                                            Some(lines) => frame_preview(
+                                                                ui,
+                                                                lines.as_deref(),
+                                                                line,
+                                                            ),
+                                                            None => {
+                                                                preview_miss = Some(file.clone());
+                                                                ui.label(m("…", 11.0, TXT_DIM));
+                                                            }
+                                                        }
+                                                    }
+                                                    _ => {
+                                                        ui.label(m(
+                                                            &format!(
+                                                                "{} @ 0x{:x} (no source)",
+                                                                frame.function, frame.addr
+                                                            ),
+                                                            11.0,
+                                                            TXT_MUTED,
+                                                        ));
+                                                    }
+                                                }
+                                            });
+                                            ui.end_row();
+                                        }
+
+                                        // Scrolling this row into view fetches the next page
+                                        if self.stack_more {
+                                            ui.label("");
+                                            let more = ui.label(m("…", 11.0, TXT_DIM));
+                                            ui.end_row();
+                                            if !self.stack_loading && ui.is_rect_visible(more.rect)
+                                            {
+                                                load_more = Some(pause.stack.len() as u32);
+                                            }
+                                        }
+                                    });
+                            } else {
+                                ui.label(m("Not paused", 11.0, TXT_DIM).italics());
+                            }
+                            if let Some(file) = preview_miss {
+                                self.cache_source(file);
+                            }
+                            if let Some((idx, level)) = clicked {
+                                // ↑/↓ move from here, Enter selects
+                                self.nav = Some(NavPanel::Stack);
+                                self.stack_cursor = idx;
+                                self.select_frame(level);
+                            }
+                            if let Some(low) = load_more {
+                                self.request_stack_page(low);
+                            }
+                            if self.state.pause.is_some() {
+                                self.frame_eval_panel(ui);
+                                ui.horizontal(|ui| {
                                     ui.add_space(8.0);
-                                    let (r, _) =
-                                        ui.allocate_exact_size(Vec2::splat(8.0), Sense::hover());
-                                    ui.painter().circle_filled(r.center(), 4.0, ACCENT);
-                                    ui.add_space(4.0);
-                                    ui.label(m(
-                                        &format!("Thread {}", pause.thread_id),
-                                        11.0,
-                                        TXT_MUTED,
-                                    ));
+                                    if ui
+                                        .small_button("Save report")
+                                        .on_hover_text(
+                                            "Markdown snapshot of the breakpoints, stack, \
+                                             source, locals and registers, to share",
+                                        )
+                                        .clicked()
+                                    {
+                                        self.save_state_report();
+                                    }
+                                });
+                            }
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // FILES ─────────────────────────────────────────────────
+                        sec_hdr(ui, "Files", &mut self.open_files);
+                        if self.open_files {
+                            if let Some(exe) = &self.state.persistent.executable {
+                                ui.horizontal(|ui| {
+                                    ui.add_space(8.0);
+                                    ui.label(m(&format!("📄 {exe}"), 11.0, TXT_CYAN));
                                 });
                             }
+                            self.remote_panel(ui);
+                            self.source_dirs_panel(ui);
+                            self.substitute_paths_panel(ui);
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.checkbox(
+                                    &mut self.settings.show_source_path,
+                                    m("show loaded source path", 11.0, TXT_DIM),
+                                );
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.checkbox(
+                                    &mut self.settings.inline_values,
+                                    m("inline variable values", 11.0, TXT_DIM),
+                                );
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.checkbox(
+                                    &mut self.settings.show_pc,
+                                    m("pc address in top bar", 11.0, TXT_DIM),
+                                );
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.checkbox(
+                                    &mut self.settings.show_errno,
+                                    m("errno in status bar", 11.0, TXT_DIM),
+                                )
+                                .on_hover_text("Evaluated on every stop; needs libc's symbols");
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.checkbox(
+                                    &mut self.settings.auto_reload_sources,
+                                    m("reload sources changed on disk", 11.0, TXT_DIM),
+                                )
+                                .on_hover_text("Off: ask first");
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.label(m("editor", 11.0, TXT_DIM));
+                                ui.add(
+                                    TextEdit::singleline(&mut self.settings.editor_command)
+                                        .font(FontId::monospace(11.0))
+                                        .hint_text(editor::DEFAULT_COMMAND)
+                                        .desired_width(ui.available_width() - 8.0),
+                                )
+                                .on_hover_text(
+                                    "Run by \"Open in editor\": {file} and {line} are filled \
+                                     in, $VAR comes from the environment",
+                                );
+                            });
+                            self.fork_settings(ui);
+                            self.async_setting(ui);
+                            self.crash_report_setting(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // CHECKPOINTS ───────────────────────────────────────────
+                        // fork-based: GDB only supports them on Linux
+                        if cfg!(target_os = "linux") {
+                            sec_hdr(ui, "Checkpoints", &mut self.open_checkpoints);
+                            if self.open_checkpoints {
+                                self.checkpoints_panel(ui);
+                                ui.add_space(4.0);
+                            }
+                            hl(ui);
+                        }
+
+                        // FIND IN FILES ─────────────────────────────────────────
+                        sec_hdr(ui, "Find in Files", &mut self.open_find);
+                        if self.open_find {
+                            self.find_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // SYMBOLS ───────────────────────────────────────────────
+                        sec_hdr(ui, "Symbols", &mut self.open_symbols);
+                        if self.open_symbols {
+                            self.symbols_panel(ui);
+                            ui.add_space(4.0);
+                        }
+                        hl(ui);
+
+                        // THREAD ────────────────────────────────────────────────
+                        sec_hdr(ui, "Thread", &mut self.open_thread);
+                        if self.open_thread {
+                            self.threads_panel(ui);
                             ui.add_space(4.0);
                         }
                         hl(ui);
@@ -556,178 +5314,1228 @@ impl eframe::App for App {
                                 Stroke::new(2.0, ACCENT),
                             );
                         }
-                        if resp.clicked() {
-                            self.watch_tab = tab;
+                        if resp.clicked() && self.watch_tab != tab {
+                            self.watch_tab = tab;
+                            self.request_visible();
+                        }
+                    }
+                });
+                hl(ui);
+
+                // Tab body ──────────────────────────────────────────────────────
+                ScrollArea::vertical().id_salt("watch_body").show(ui, |ui| {
+                    ui.add_space(2.0);
+                    match self.watch_tab {
+                        WatchTab::Watch => {
+                            self.watches_panel(ui);
+                            self.eval_row(ui);
+                            ui.horizontal(|ui| {
+                                ui.add_space(8.0);
+                                ui.add(
+                                    TextEdit::singleline(&mut self.locals_filter)
+                                        .font(FontId::monospace(11.0))
+                                        .hint_text("filter")
+                                        .desired_width(ui.available_width() - 90.0),
+                                );
+                                if ui
+                                    .checkbox(
+                                        &mut self.receiver_first,
+                                        m("this/self", 11.0, TXT_MUTED),
+                                    )
+                                    .on_hover_text("Show this/self first, expanded one level")
+                                    .changed()
+                                {
+                                    self.expand_receiver();
+                                }
+                            });
+                            let language = self.state.language;
+                            let receiver = receiver_index(&self.state.locals, language)
+                                .filter(|_| self.receiver_first);
+                            // GDB auto-derefs `self.field` in Rust; C++ needs `this->field`
+                            let receiver_is_pointer = self.receiver_expr.is_some()
+                                && receiver.is_some_and(|i| self.state.locals[i].name == "this");
+                            let mut watch = None;
+                            for (i, var) in
+                                order_locals(&self.state.locals, language, self.receiver_first)
+                            {
+                                let Some(hit) = filter_match(&var.name, &self.locals_filter) else {
+                                    continue;
+                                };
+                                ui.horizontal(|ui| {
+                                    ui.add_space(8.0);
+                                    ui.spacing_mut().item_spacing.x = 0.0;
+                                    let (pre, rest) = var.name.split_at(hit.start);
+                                    let (mid, post) = rest.split_at(hit.len());
+                                    ui.label(m(pre, 11.0, TXT_CYAN));
+                                    ui.label(m(mid, 11.0, TXT_HL).background_color(BG_LINE_HL));
+                                    ui.label(m(post, 11.0, TXT_CYAN));
+                                    ui.label(m(" = ", 11.0, TXT_DIM));
+                                    let resp = ui.add(
+                                        egui::Label::new(m(&var.value, 11.0, TXT_YELLOW))
+                                            .sense(Sense::click()),
+                                    );
+                                    if let Some(kind) = watch_menu(&resp) {
+                                        watch =
+                                            Some((watch_expression(&var.name, false, &[]), kind));
+                                    }
+                                });
+                                if Some(i) == receiver
+                                    && let Some((field, kind)) =
+                                        receiver_fields(ui, self.receiver_value.as_ref())
+                                {
+                                    let expr =
+                                        watch_expression(&var.name, receiver_is_pointer, &[&field]);
+                                    watch = Some((expr, kind));
+                                }
+                            }
+                            if let Some((expr, kind)) = watch {
+                                self.act(Command::InsertWatchpoint { expr, kind });
+                            }
+                            if self.state.locals.is_empty() {
+                                ui.label(m("No locals", 11.0, TXT_DIM).italics());
+                            }
+                        }
+                        WatchTab::Registers => {
+                            // DEBUG info
+                            ui.label(m(
+                                &format!(
+                                    "registers: {}  names: {}",
+                                    self.state.registers.len(),
+                                    self.state.register_names.len()
+                                ),
+                                10.0,
+                                TXT_MUTED,
+                            ));
+
+                            if self.state.registers.is_empty() {
+                                ui.label(
+                                    m("Not paused — no register data", 11.0, TXT_DIM).italics(),
+                                );
+                            } else {
+                                let names = &self.state.register_names;
+                                let arch = Arch::from_register_names(names);
+
+                                // Unnamed numbers are gaps in GDB's register table
+                                let mut all: Vec<(RegCategory, String, &str)> = self
+                                    .state
+                                    .registers
+                                    .iter()
+                                    .filter(|r| {
+                                        names.get(r.number as usize).is_none_or(|n| !n.is_empty())
+                                    })
+                                    .map(|r| {
+                                        let name = names
+                                            .get(r.number as usize)
+                                            .cloned()
+                                            .unwrap_or_else(|| format!("#{}", r.number));
+                                        (categorize(arch, &name), name, r.value.as_str())
+                                    })
+                                    .collect();
+                                all.sort_by_key(|(_, name, _)| display_order(name));
+
+                                let shown = &mut self.settings.register_categories;
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.add_space(8.0);
+                                    for cat in RegCategory::ALL {
+                                        let mut on = shown.contains(&cat);
+                                        if ui
+                                            .checkbox(&mut on, m(cat.label(), 10.0, TXT_DIM))
+                                            .changed()
+                                        {
+                                            if on {
+                                                shown.push(cat);
+                                            } else {
+                                                shown.retain(|c| *c != cat);
+                                            }
+                                        }
+                                    }
+                                });
+                                let shown = shown.clone();
+                                let pins = self.settings.pinned_registers.clone();
+
+                                let mut toggle = None;
+                                let mut start_edit = None;
+                                let mut submit = None;
+                                egui::Grid::new("reg_grid")
+                                    .num_columns(2)
+                                    .spacing([12.0, 1.0])
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for cat in RegCategory::ALL {
+                                            if !shown.contains(&cat) {
+                                                continue;
+                                            }
+                                            let mut rows =
+                                                all.iter().filter(|(c, _, _)| *c == cat).peekable();
+                                            if rows.peek().is_none() {
+                                                continue;
+                                            }
+                                            ui.horizontal(|ui| {
+                                                ui.add_space(4.0);
+                                                ui.label(m(cat.label(), 10.0, TXT_MUTED).strong());
+                                            });
+                                            ui.end_row();
+                                            for (_, name, value) in rows {
+                                                ui.horizontal(|ui| {
+                                                    ui.add_space(8.0);
+                                                    let pinned = pins.contains(name);
+                                                    let col = if pinned { ACCENT } else { TXT_CYAN };
+                                                    ui.add(
+                                                        egui::Label::new(m(name, 11.0, col))
+                                                            .sense(Sense::click()),
+                                                    )
+                                                    .on_hover_text("Right-click to pin")
+                                                    .context_menu(|ui| {
+                                                        let label =
+                                                            if pinned { "Unpin" } else { "Pin" };
+                                                        if ui.button(label).clicked() {
+                                                            toggle = Some(name.clone());
+                                                            ui.close();
+                                                        }
+                                                    });
+                                                });
+                                                match &mut self.reg_edit {
+                                                    Some((editing, text)) if editing == name => {
+                                                        let resp = ui.add(
+                                                            TextEdit::singleline(text)
+                                                                .font(FontId::monospace(11.0))
+                                                                .desired_width(140.0),
+                                                        );
+                                                        resp.request_focus();
+                                                        if resp.lost_focus() {
+                                                            submit = Some(ui.input(|i| {
+                                                                i.key_pressed(Key::Enter)
+                                                            }));
+                                                        }
+                                                    }
+                                                    _ => {
+                                                        let resp = ui
+                                                            .add(
+                                                                egui::Label::new(m(
+                                                                    value, 11.0, TXT_YELLOW,
+                                                                ))
+                                                                .sense(Sense::click()),
+                                                            )
+                                                            .on_hover_text("Double-click to edit");
+                                                        if resp.double_clicked() {
+                                                            start_edit = Some((
+                                                                name.clone(),
+                                                                value.to_string(),
+                                                            ));
+                                                        }
+                                                    }
+                                                }
+                                                ui.end_row();
+                                            }
+                                        }
+                                    });
+                                if let Some(name) = toggle
+                                    && !toggle_pin(&mut self.settings.pinned_registers, &name)
+                                {
+                                    self.console_log.push(format!(
+                                        "[UI] ⚠ Can't pin ${name}: {MAX_PINNED} registers already pinned"
+                                    ));
+                                }
+                                if start_edit.is_some() {
+                                    self.reg_edit = start_edit;
+                                    self.reg_confirm = None;
+                                }
+                                match submit {
+                                    Some(true) => self.submit_register_edit(),
+                                    Some(false) => self.reg_edit = None,
+                                    None => {}
+                                }
+                                self.register_confirm(ui);
+                            }
+                        }
+                        WatchTab::Data => {
+                            self.pc_row(ui);
+                            self.disasm_jump_row(ui);
+                            self.focus_row(ui);
+                            self.disasm_listing(ui);
                         }
                     }
                 });
-                hl(ui);
+            });
 
-                // Tab body ──────────────────────────────────────────────────────
-                ScrollArea::vertical().id_salt("watch_body").show(ui, |ui| {
-                    ui.add_space(2.0);
-                    match self.watch_tab {
-                        WatchTab::Watch => {
-                            for var in &self.state.locals {
-                                ui.horizontal(|ui| {
-                                    ui.add_space(8.0);
-                                    ui.label(m(&var.name, 11.0, TXT_CYAN));
-                                    ui.label(m(" = ", 11.0, TXT_DIM));
-                                    ui.label(m(&var.value, 11.0, TXT_YELLOW));
-                                });
+        // ── SOURCE VIEW (central) ─────────────────────────────────────────────
+        egui::CentralPanel::default()
+            .frame(flat(BG_APP))
+            .show(ctx, |ui| {
+                if self.shows_welcome() {
+                    self.welcome(ui);
+                    return;
+                }
+                self.pinned_registers(ui);
+                self.source_tabs(ui);
+                self.source_changed_banner(ui);
+                if self.active_tab.is_none() && self.settings.show_source_path {
+                    self.source_path_header(ui);
+                }
+                let scroll_to = self.scroll_to_line.take();
+                let mut input = SourceInput::default();
+                let view = match self.settings.layout.central() {
+                    CentralView::Source if self.asm_fallback() => CentralView::Assembly,
+                    view => view,
+                };
+                let source_height = match view {
+                    CentralView::Split => ui.available_height() * 0.55,
+                    _ => f32::INFINITY,
+                };
+                if view != CentralView::Assembly {
+                    ScrollArea::both()
+                        .id_salt("source")
+                        .max_height(source_height)
+                        .show(ui, |ui| {
+                            if let Some(path) = self.active_tab.and_then(|i| self.open_tabs.get(i))
+                            {
+                                match self.source_cache.get(path) {
+                                    Some(Some(lines)) => {
+                                        let numbered = lines
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(i, l)| (i as u32 + 1, l.as_str(), None));
+                                        let current = self
+                                            .state
+                                            .current_line()
+                                            .filter(|_| self.state.current_file() == Some(path));
+                                        let gutter = Gutter {
+                                            profile: &self.profile,
+                                            bookmarks: self
+                                                .settings
+                                                .bookmarks
+                                                .get(path)
+                                                .map_or(&[], Vec::as_slice),
+                                        };
+                                        input = source_lines_view(
+                                            ui,
+                                            &self.state,
+                                            path,
+                                            numbered,
+                                            current,
+                                            scroll_to,
+                                            gutter,
+                                        );
+                                    }
+                                    _ => {
+                                        ui.label(m(
+                                            &format!("Could not read {path}"),
+                                            13.0,
+                                            TXT_DIM,
+                                        ));
+                                    }
+                                }
+                                return;
                             }
-                            if self.state.locals.is_empty() {
-                                ui.label(m("No locals", 11.0, TXT_DIM).italics());
+
+                            if self.source_lines.is_empty() {
+                                ui.centered_and_justified(|ui| {
+                                    ui.label(m("No source file loaded", 13.0, TXT_DIM).italics());
+                                });
+                                return;
                             }
-                        }
-                        WatchTab::Registers => {
-                            // DEBUG info
-                            ui.label(m(
-                                &format!(
-                                    "registers: {}  names: {}",
-                                    self.state.registers.len(),
-                                    self.state.register_names.len()
-                                ),
-                                10.0,
-                                TXT_MUTED,
-                            ));
 
-                            if self.state.registers.is_empty() {
-                                ui.label(
-                                    m("Not paused — no register data", 11.0, TXT_DIM).italics(),
-                                );
-                            } else {
-                                let names = &self.state.register_names;
+                            let path = self.source_file.as_deref().unwrap_or("");
+                            let current = self.state.current_line();
+                            let inline = match current {
+                                Some(line) if self.settings.inline_values => {
+                                    inline_values(&self.source_lines, line, &self.state.locals)
+                                }
+                                _ => HashMap::new(),
+                            };
+                            let numbered = self.source_lines.iter().map(|l| {
+                                let annotation = inline.get(&l.number).map(String::as_str);
+                                (l.number, l.text.as_str(), annotation)
+                            });
+                            let gutter = Gutter {
+                                profile: &self.profile,
+                                bookmarks: self
+                                    .settings
+                                    .bookmarks
+                                    .get(path)
+                                    .map_or(&[], Vec::as_slice),
+                            };
+                            input = source_lines_view(
+                                ui,
+                                &self.state,
+                                path,
+                                numbered,
+                                current,
+                                scroll_to,
+                                gutter,
+                            );
+                        });
+                }
+                if view != CentralView::Source {
+                    if view == CentralView::Split {
+                        hl(ui);
+                    }
+                    ScrollArea::vertical()
+                        .id_salt("central_asm")
+                        .auto_shrink(false)
+                        .show(ui, |ui| self.disasm_listing(ui));
+                }
+
+                if let ProgramState::Exited { code, signal } = &self.state.program
+                    && exit_overlay(ui, &exit_message(*code, signal.as_deref()))
+                {
+                    self.act(Command::Restart);
+                }
+                self.hovered_line = input.hovered;
+                for cmd in input.commands {
+                    self.act(cmd);
+                }
+                if let Some(location) = input.run_to {
+                    for cmd in run_to_line(location, self.bp_pending) {
+                        self.act(cmd);
+                    }
+                }
+                if let Some((file, line)) = input.open_in_editor {
+                    self.open_in_editor(&file, line);
+                }
+            });
+    }
+}
+
+// ─── Source row ───────────────────────────────────────────────────────────────
+
+/// Per-line decorations of the source gutter besides breakpoints.
+struct Gutter<'a> {
+    profile: &'a StepProfile,
+    /// Bookmarked lines of the file, sorted
+    bookmarks: &'a [u32],
+}
+
+/// What the pointer did over the source rows.
+#[derive(Default)]
+struct SourceInput {
+    hovered: Option<u32>,
+    /// Picked from a row's context menu
+    commands: Vec<Command>,
+    run_to: Option<String>,
+    open_in_editor: Option<(String, u32)>,
+}
+
+/// Rows of `path` with breakpoint markers, the execution arrow on
+/// `current_line` and `scroll_to` brought into view. Lines come with their
+/// inline values, if any. Right-clicking a row offers to open it in the
+/// editor and, while paused, to run or advance to it.
+fn source_lines_view<'a>(
+    ui: &mut egui::Ui,
+    state: &DebuggerState,
+    path: &str,
+    lines: impl Iterator<Item = (u32, &'a str, Option<&'a str>)>,
+    current_line: Option<u32>,
+    scroll_to: Option<u32>,
+    gutter: Gutter,
+) -> SourceInput {
+    let mut input = SourceInput::default();
+    // Headers and files without a telling extension go by the program's
+    let language = match Language::from_path(path) {
+        Language::Unknown => state.language,
+        language => language,
+    };
+    for (number, text, annotation) in lines {
+        let marker = state.breakpoint_at(path, number).map(|bp| {
+            if bp.pending {
+                BpMarker::Pending
+            } else if bp.log_message.is_some() {
+                BpMarker::Log
+            } else {
+                BpMarker::Break
+            }
+        });
+        let heat = gutter.profile.heat(path, number);
+        let resp = source_row(
+            ui,
+            number,
+            &syntax::tokens(text, language),
+            Some(number) == current_line,
+            marker,
+            heat,
+            annotation,
+        );
+        if gutter.bookmarks.binary_search(&number).is_ok() {
+            bookmark_marker(ui.painter(), resp.rect);
+        }
+        if resp.hovered() {
+            input.hovered = Some(number);
+        }
+        if scroll_to == Some(number) {
+            ui.scroll_to_rect(resp.rect, Some(Align::Center));
+        }
+        resp.context_menu(|ui| {
+            if state.is_paused() {
+                let location = format!("{path}:{number}");
+                if ui
+                    .button("Run to here")
+                    .on_hover_text(
+                        "Continue with a temporary breakpoint on this line. \
+                         Keeps running if the line is never reached.",
+                    )
+                    .clicked()
+                {
+                    input.run_to = Some(location.clone());
+                    ui.close();
+                }
+                if ui
+                    .button("Advance to here")
+                    .on_hover_text(
+                        "Run until this line, or until the current function \
+                         returns, whichever comes first.",
+                    )
+                    .clicked()
+                {
+                    input.commands = vec![Command::Advance(location)];
+                    ui.close();
+                }
+            }
+            if ui.button("Open in editor").clicked() {
+                input.open_in_editor = Some((path.to_owned(), number));
+                ui.close();
+            }
+        });
+    }
+    input
+}
+
+/// "Run to here": a breakpoint that deletes itself when hit, then continue.
+fn run_to_line(location: String, pending: bool) -> Vec<Command> {
+    let spec = BreakpointSpec {
+        temporary: true,
+        ..BreakpointSpec::new(location)
+    };
+    vec![spec.insert(pending), Command::Continue]
+}
+
+/// A small ribbon between the breakpoint dot and the line number.
+fn bookmark_marker(p: &egui::Painter, row: egui::Rect) {
+    let (x, top) = (row.left() + 18.0, row.top() + 3.0);
+    p.add(egui::Shape::convex_polygon(
+        vec![
+            egui::pos2(x, top),
+            egui::pos2(x + 6.0, top),
+            egui::pos2(x + 6.0, top + 12.0),
+            egui::pos2(x + 3.0, top + 9.0),
+            egui::pos2(x, top + 12.0),
+        ],
+        ACCENT,
+        Stroke::NONE,
+    ));
+}
+
+fn source_row(
+    ui: &mut egui::Ui,
+    line_no: u32,
+    code: &[(Token, &str)],
+    is_current: bool,
+    marker: Option<BpMarker>,
+    heat: Option<f32>,
+    annotation: Option<&str>,
+) -> egui::Response {
+    let (rect, resp) = ui.allocate_exact_size(
+        Vec2::new(f32::max(ui.available_width(), 900.0), 18.0),
+        Sense::click(),
+    );
+    let p = ui.painter();
+    let cy = rect.center().y;
+
+    // Step profile: the hotter the line, the redder its gutter
+    if let Some(heat) = heat {
+        let gutter = egui::Rect::from_min_size(rect.left_top(), Vec2::new(60.0, rect.height()));
+        let alpha = (40.0 + heat * 180.0) as u8;
+        p.rect_filled(
+            gutter,
+            0.0,
+            Color32::from_rgba_unmultiplied(0xcc, 0x44, 0x44, alpha),
+        );
+    }
+
+    if is_current {
+        p.rect_filled(rect, 0.0, BG_LINE_HL);
+        p.line_segment(
+            [rect.left_top(), rect.left_bottom()],
+            Stroke::new(2.0, ACCENT),
+        );
+    }
+
+    match marker {
+        Some(BpMarker::Break) => {
+            p.circle_filled(egui::pos2(rect.left() + 9.0, cy), 5.0, RED);
+        }
+        Some(BpMarker::Log) => {
+            let c = egui::pos2(rect.left() + 9.0, cy);
+            p.add(egui::Shape::convex_polygon(
+                vec![
+                    c + Vec2::new(0.0, -5.5),
+                    c + Vec2::new(5.5, 0.0),
+                    c + Vec2::new(0.0, 5.5),
+                    c + Vec2::new(-5.5, 0.0),
+                ],
+                TXT_YELLOW,
+                Stroke::NONE,
+            ));
+        }
+        Some(BpMarker::Pending) => {
+            p.circle_stroke(
+                egui::pos2(rect.left() + 9.0, cy),
+                4.5,
+                Stroke::new(1.5, TXT_MUTED),
+            );
+        }
+        None => {}
+    }
+
+    // Line number – right-aligned in a 56 px gutter
+    p.text(
+        egui::pos2(rect.left() + 56.0, cy),
+        egui::Align2::RIGHT_CENTER,
+        format!("{line_no}"),
+        FontId::monospace(12.0),
+        match marker {
+            Some(BpMarker::Pending) => TXT_MUTED,
+            Some(_) => RED,
+            None => TXT_DIM,
+        },
+    );
+
+    // Code
+    let mut job = egui::text::LayoutJob::default();
+    for &(token, text) in code {
+        let color = match token {
+            Token::Plain if is_current => TXT_HL,
+            Token::Plain => TXT,
+            Token::Keyword => BLUE,
+            Token::Str => TXT_YELLOW,
+            Token::Number => TXT_CYAN,
+            Token::Comment => TXT_MUTED,
+            Token::Directive => ACCENT,
+        };
+        job.append(
+            text,
+            0.0,
+            egui::TextFormat::simple(FontId::monospace(12.5), color),
+        );
+    }
+    let galley = p.layout_job(job);
+    let code_rect =
+        egui::Align2::LEFT_CENTER.anchor_size(egui::pos2(rect.left() + 66.0, cy), galley.size());
+    p.galley(code_rect.min, galley, TXT);
+
+    if let Some(values) = annotation {
+        p.text(
+            egui::pos2(code_rect.right() + 16.0, cy),
+            egui::Align2::LEFT_CENTER,
+            format!("// {values}"),
+            FontId::monospace(12.0),
+            TXT_DIM,
+        );
+    }
+    resp
+}
+
+// ─── Inline values ────────────────────────────────────────────────────────────
+
+/// `name = value` annotations for the lines of the current function up to
+/// `current`. The function starts at the closest line above that looks like
+/// a signature, at most `INLINE_WINDOW` lines back.
+fn inline_values(lines: &[SourceLine], current: u32, locals: &[Variable]) -> HashMap<u32, String> {
+    let upto: Vec<_> = lines
+        .iter()
+        .filter(|l| l.number <= current && l.number + INLINE_WINDOW > current)
+        .collect();
+    let start = upto
+        .iter()
+        .rposition(|l| looks_like_signature(&l.text))
+        .unwrap_or(0);
+
+    upto[start..]
+        .iter()
+        .filter_map(|l| {
+            let values: Vec<_> = locals
+                .iter()
+                .filter(|v| mentions(&l.text, &v.name))
+                .map(|v| format!("{} = {}", v.name, clip(&v.value, 30)))
+                .collect();
+            (!values.is_empty()).then(|| (l.number, values.join(", ")))
+        })
+        .collect()
+}
+
+/// `int compute(int n)` / `fn main() {` at column 0; not a call statement.
+fn looks_like_signature(text: &str) -> bool {
+    !text.starts_with(char::is_whitespace) && text.contains('(') && !text.trim_end().ends_with(';')
+}
+
+/// `name` as a whole identifier in `text`: `i` matches `a[i]`, not `if`.
+fn mentions(text: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+fn clip(value: &str, max: usize) -> String {
+    match value.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &value[..i]),
+        None => value.to_owned(),
+    }
+}
+
+/// Last path component, for tabs and result rows.
+fn short_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+// ─── Entry point ──────────────────────────────────────────────────────────────
+
+/// Commands for Run with "break at entry". A symbol (`_start`, `main`,
+/// `Reset_Handler`) gets a temporary breakpoint before running; without one
+/// `starti` stops at the first instruction, whatever the ELF entry is.
+fn entry_run_sequence(symbol: &str, pending: bool) -> Vec<Command> {
+    let symbol = symbol.trim();
+    if symbol.is_empty() {
+        return vec![Command::StartInstruction];
+    }
+    let mut spec = BreakpointSpec::new(format!("*{}", symbol.trim_start_matches('*')));
+    spec.temporary = true;
+    vec![spec.insert(pending), Command::Run]
+}
+
+// ─── Threads ──────────────────────────────────────────────────────────────────
+
+/// `2 worker`, or GDB's `Thread 0x… (LWP n)` for unnamed threads.
+fn thread_title(thread: &Thread) -> String {
+    let name = thread.name.as_deref().unwrap_or(&thread.target_id);
+    format!("{} {name}", thread.id)
+}
+
+/// Why the thread is stopped and where: the stop reason for the thread that
+/// reported it, the plain state for the ones all-stop mode halted with it.
+fn thread_detail(thread: &Thread, pause: &PauseState) -> String {
+    let reason = if thread.id == pause.thread_id {
+        match &pause.stop_reason {
+            StopReason::BreakpointHit(id) => format!("breakpoint {id}"),
+            StopReason::WatchpointTrigger { id, .. } => format!("watchpoint {id}"),
+            StopReason::EndStepping => "step".into(),
+            StopReason::FunctionFinished => "finish".into(),
+            StopReason::Signal(sig) => sig.clone(),
+            StopReason::SyscallReturn(name) => format!("return from {name}"),
+            StopReason::Unknown => thread.state.clone(),
+        }
+    } else {
+        thread.state.clone()
+    };
+    match &thread.frame {
+        Some(frame) => match (&frame.file, frame.line) {
+            (Some(file), Some(line)) => {
+                format!(
+                    "{reason} · {} at {}:{line}",
+                    frame.function,
+                    short_name(file)
+                )
+            }
+            _ => format!("{reason} · {}", frame.function),
+        },
+        None => reason,
+    }
+}
+
+// ─── Line breakpoint toggle ───────────────────────────────────────────────────
+
+/// What the toolbar's breakpoint toggle does: remove the breakpoint at the
+/// current line, or add one. None when not stopped at a source line.
+fn line_breakpoint_toggle(state: &DebuggerState, pending: bool) -> Option<Command> {
+    let file = state.current_file()?;
+    let line = state.current_line()?;
+    Some(match state.breakpoint_at(file, line) {
+        Some(bp) => Command::RemoveBreakpoint(bp.id),
+        None => BreakpointSpec::new(format!("{file}:{line}")).insert(pending),
+    })
+}
+
+// ─── Breadcrumb ───────────────────────────────────────────────────────────────
+
+/// Frames for the breadcrumb, outermost first; None marks elided frames.
+/// Keeps the two outermost and the innermost ones when there are more than
+/// `max`, and starts with None if outer frames aren't loaded yet.
+fn breadcrumb(
+    stack: &[crate::state::Frame],
+    more: bool,
+    max: usize,
+) -> Vec<Option<&crate::state::Frame>> {
+    let mut crumbs: Vec<_> = stack.iter().rev().map(Some).collect();
+    if crumbs.len() > max {
+        let tail = crumbs.split_off(crumbs.len() - (max - 3));
+        crumbs.truncate(2);
+        crumbs.push(None);
+        crumbs.extend(tail);
+    }
+    if more {
+        crumbs.insert(0, None);
+    }
+    crumbs
+}
+
+// ─── Source paths ─────────────────────────────────────────────────────────────
+
+/// `path` rewritten by the first rule whose `from` is a whole-component
+/// prefix of it, like GDB's `substitute-path` (`/foo` matches `/foo/a.c`,
+/// not `/foobar/a.c`).
+fn substitute_path(path: &str, rules: &[(String, String)]) -> Option<String> {
+    rules.iter().find_map(|(from, to)| {
+        let from = from.trim_end_matches(['/', '\\']);
+        let rest = path.strip_prefix(from)?;
+        (rest.is_empty() || rest.starts_with(['/', '\\'])).then(|| format!("{to}{rest}"))
+    })
+}
+
+// ─── Console ──────────────────────────────────────────────────────────────────
+
+// Commands GDB itself doesn't repeat on an empty line
+const NO_REPEAT: &[&str] = &[
+    "run", "r", "start", "starti", "kill", "k", "delete", "d", "quit", "q", "file", "attach",
+    "detach",
+];
+
+/// What an empty Enter re-sends: the last command, unless it's in `NO_REPEAT`.
+fn repeat_command(history: &[String]) -> Option<&str> {
+    let last = history.last()?;
+    let word = last.split_whitespace().next().unwrap_or("");
+    (!NO_REPEAT.contains(&word)).then_some(last.as_str())
+}
+
+/// Expression of a console `print`/`p` (with or without a `/fmt`), so it can
+/// be re-evaluated later.
+fn print_expression(cmd: &str) -> Option<&str> {
+    let (word, expr) = cmd.trim().split_once(char::is_whitespace)?;
+    let name = word.split('/').next().unwrap_or(word);
+    let expr = expr.trim();
+    (matches!(name, "print" | "p") && !expr.is_empty()).then_some(expr)
+}
+
+// ─── Method receiver ──────────────────────────────────────────────────────────
+
+/// Index of the implicit method receiver (`this` in C++, `self` in Rust).
+/// In C or Go those are just variable names; with the language unknown,
+/// either counts.
+fn receiver_index(vars: &[Variable], language: Language) -> Option<usize> {
+    vars.iter().position(|v| match language {
+        Language::Unknown => v.name == "this" || v.name == "self",
+        _ => language.receiver() == Some(v.name.as_str()),
+    })
+}
+
+/// Locals in display order with their original index; the receiver goes first
+/// when `receiver_first` is set.
+fn order_locals(
+    vars: &[Variable],
+    language: Language,
+    receiver_first: bool,
+) -> Vec<(usize, &Variable)> {
+    let mut ordered: Vec<_> = vars.iter().enumerate().collect();
+    if receiver_first && let Some(i) = receiver_index(vars, language) {
+        let receiver = ordered.remove(i);
+        ordered.insert(0, receiver);
+    }
+    ordered
+}
+
+/// Renders the receiver's fields; returns the field picked for a watchpoint.
+fn receiver_fields(
+    ui: &mut egui::Ui,
+    value: Option<&Result<String, String>>,
+) -> Option<(String, WatchKind)> {
+    let lines = match value {
+        Some(Ok(v)) => top_level_fields(v).into_iter().map(str::to_owned).collect(),
+        Some(Err(e)) => vec![format!("<{e}>")],
+        None => vec!["…".to_owned()],
+    };
+    let mut picked = None;
+    for line in lines {
+        ui.horizontal(|ui| {
+            ui.add_space(24.0);
+            let resp = ui.add(egui::Label::new(m(&line, 11.0, TXT_MUTED)).sense(Sense::click()));
+            // Base classes (`<Base> = {...}`) aren't addressable by name
+            if let Some((field, _)) = line.split_once(" = ")
+                && !field.starts_with('<')
+                && let Some(kind) = watch_menu(&resp)
+            {
+                picked = Some((field.to_owned(), kind));
+            }
+        });
+    }
+    picked
+}
+
+/// One visible line of an evaluated value's tree.
+struct TreeRow<'a> {
+    depth: usize,
+    /// `expr.field[2]`: identifies the node across re-evaluations
+    path: String,
+    /// Field name, or `[i]` for an array element
+    name: &'a str,
+    /// Empty for aggregates
+    value: &'a str,
+    expandable: bool,
+}
+
+/// The rows of `tree` a reader can see: children of closed nodes are left
+/// out, so the keyboard cursor never lands on them.
+fn tree_rows<'a>(name: &'a str, tree: &'a ValueTree, open: &HashSet<String>) -> Vec<TreeRow<'a>> {
+    let mut rows = vec![];
+    push_tree_rows(&mut rows, 0, name.to_owned(), name, tree, open);
+    rows
+}
+
+fn push_tree_rows<'a>(
+    rows: &mut Vec<TreeRow<'a>>,
+    depth: usize,
+    path: String,
+    name: &'a str,
+    tree: &'a ValueTree,
+    open: &HashSet<String>,
+) {
+    let (value, children) = match tree {
+        ValueTree::Leaf(value) => (value.as_str(), None),
+        ValueTree::Node(children) => ("", Some(children)),
+    };
+    rows.push(TreeRow {
+        depth,
+        path: path.clone(),
+        name,
+        value,
+        expandable: children.is_some(),
+    });
+    let Some(children) = children.filter(|_| open.contains(&path)) else {
+        return;
+    };
+    for (child, tree) in children {
+        let path = if child.starts_with('[') {
+            format!("{path}{child}")
+        } else {
+            format!("{path}.{child}")
+        };
+        push_tree_rows(rows, depth + 1, path, child, tree, open);
+    }
+}
+
+/// `cursor` moved by `delta` rows, kept inside a list of `len`.
+fn move_cursor(cursor: usize, delta: isize, len: usize) -> usize {
+    cursor
+        .saturating_add_signed(delta)
+        .min(len.saturating_sub(1))
+}
+
+/// Lists the arrow keys can drive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum NavPanel {
+    Stack,
+    Tree,
+}
+
+// ─── Casts ────────────────────────────────────────────────────────────────────
+
+/// `*(T *)(expr)`: what's at `expr` read as a `T`. A trailing `*` on the type
+/// is taken as naming the pointer (`struct foo *`), not a pointer to one. None
+/// if either part is empty, or could end the cast early: unbalanced brackets,
+/// quotes or statement separators.
+fn cast_expression(expr: &str, ty: &str) -> Option<String> {
+    let expr = expr.trim();
+    let ty = ty.trim();
+    let ty = ty.strip_suffix('*').map_or(ty, str::trim_end);
+    let valid_type = ty
+        .chars()
+        .all(|c| c.is_alphanumeric() || " _:<>,*[]&".contains(c));
+    if expr.is_empty() || ty.is_empty() || !valid_type || !balanced(expr) || !balanced(ty) {
+        return None;
+    }
+    if expr.contains([';', '\n', '"', '\'']) {
+        return None;
+    }
+    Some(format!("*({ty} *)({expr})"))
+}
+
+/// Every `(`, `[` and `<` closed in order.
+fn balanced(text: &str) -> bool {
+    let mut open = vec![];
+    for c in text.chars() {
+        match c {
+            '(' | '[' | '<' => open.push(c),
+            ')' | ']' => {
+                // A `<` still open was a comparison
+                while open.last() == Some(&'<') {
+                    open.pop();
+                }
+                let want = if c == ')' { '(' } else { '[' };
+                if open.pop() != Some(want) {
+                    return false;
+                }
+            }
+            // `a->b` and `x >> 2` aren't closing anything
+            '>' if open.last() == Some(&'<') => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    open.iter().all(|&c| c == '<')
+}
+
+// ─── Watchpoints ──────────────────────────────────────────────────────────────
+
+/// Expression for a node of the watch tree: `this->len`, `cfg.limits.max`.
+/// Array elements (`[3]`) attach without a dot.
+fn watch_expression(root: &str, root_is_pointer: bool, path: &[&str]) -> String {
+    let mut expr = root.to_owned();
+    for (i, field) in path.iter().enumerate() {
+        if field.starts_with('[') {
+            expr.push_str(field);
+        } else if i == 0 && root_is_pointer {
+            expr.push_str(&format!("->{field}"));
+        } else {
+            expr.push_str(&format!(".{field}"));
+        }
+    }
+    expr
+}
+
+fn watch_menu(resp: &egui::Response) -> Option<WatchKind> {
+    let mut kind = None;
+    resp.context_menu(|ui| {
+        for (label, k) in [
+            ("Break on write", WatchKind::Write),
+            ("Break on read", WatchKind::Read),
+            ("Break on access", WatchKind::Access),
+        ] {
+            if ui.button(label).clicked() {
+                kind = Some(k);
+                ui.close();
+            }
+        }
+    });
+    kind
+}
+
+/// Varobj commands for the watches. At a stop: one `-var-update` for those
+/// alive and a `-var-create` for those that aren't yet. Once the update is
+/// in, `at_stop` false: only those whose frame it found gone, made again in
+/// the current one. Those in `creating` are left alone until their answer
+/// is in, so stepping fast doesn't make a varobj twice.
+fn watch_requests(
+    state: &DebuggerState,
+    creating: &HashSet<String>,
+    at_stop: bool,
+) -> Vec<Command> {
+    let mut cmds = vec![];
+    if at_stop
+        && state
+            .watch_values
+            .values()
+            .any(|w| matches!(w, Ok(w) if w.in_scope))
+    {
+        cmds.push(Command::UpdateVarObjects);
+    }
+    for expr in state
+        .persistent
+        .watches
+        .iter()
+        .filter(|e| !creating.contains(*e))
+    {
+        match state.watch_values.get(expr) {
+            Some(Ok(watch)) if watch.in_scope => {}
+            Some(Ok(watch)) => {
+                cmds.push(Command::DeleteVarObject(watch.var.clone()));
+                cmds.push(Command::CreateVarObject(expr.clone()));
+            }
+            Some(Err(_)) | None if at_stop => cmds.push(Command::CreateVarObject(expr.clone())),
+            Some(Err(_)) | None => {}
+        }
+    }
+    cmds
+}
+
+/// The varobj a `-var-create` answer made for nothing, to delete: its watch
+/// was removed while GDB was creating it.
+fn orphaned_varobj(
+    state: &DebuggerState,
+    expr: &str,
+    result: &Result<crate::state::WatchValue, String>,
+) -> Option<String> {
+    match result {
+        Ok(watch) if !state.persistent.watches.iter().any(|w| w == expr) => Some(watch.var.clone()),
+        _ => None,
+    }
+}
+
+/// A `request_stack_page` command, frames or their arguments.
+fn stack_page_request(command: &str) -> bool {
+    command.starts_with("-stack-list-frames ") || command.starts_with("-stack-list-arguments 1 ")
+}
+
+/// The errno read `on_stop` sends, as MI.
+fn errno_request(command: &str) -> bool {
+    command == command_to_mi(&Command::Evaluate(ERRNO_EXPR.into()))
+}
+
+/// GDB's errors when the debug registers are all in use.
+fn hw_watchpoints_exhausted(err: &str) -> bool {
+    err.contains("Could not insert hardware watchpoint")
+        || err.contains("too many hardware breakpoints/watchpoints")
+        || err.contains("Hardware watchpoints used exceeds limit")
+}
+
+/// GDB refusing a location that names more than one file.
+fn ambiguous_location(err: &str) -> bool {
+    let err = err.to_ascii_lowercase();
+    err.contains("ambiguous") || (err.contains("multiple") && err.contains("match"))
+}
+
+/// `(file, line)` of a `file:line` breakpoint location.
+fn break_location(location: &str) -> Option<(&str, u32)> {
+    let (file, line) = location.rsplit_once(':')?;
+    Some((file, line.parse().ok()?))
+}
+
+/// The distinct `known` paths whose file name is `file`'s.
+fn same_basename<'a>(file: &str, known: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let name = std::path::Path::new(file).file_name();
+    let mut paths: Vec<String> = vec![];
+    for path in known {
+        if name.is_some()
+            && std::path::Path::new(path).file_name() == name
+            && !paths.iter().any(|p| p == path)
+        {
+            paths.push(path.to_owned());
+        }
+    }
+    paths
+}
 
-                                let mut all: Vec<(String, &str)> = self
-                                    .state
-                                    .registers
-                                    .iter()
-                                    .map(|r| {
-                                        let name = names
-                                            .get(r.number as usize)
-                                            .cloned()
-                                            .unwrap_or_else(|| format!("#{}", r.number));
-                                        (name, r.value.as_str())
-                                    })
-                                    .collect();
+// ─── Locals filter ────────────────────────────────────────────────────────────
 
-                                all.sort_by_key(|(name, _)| display_order(name));
+/// Byte range of `filter` inside `name`, ignoring ASCII case. An empty filter
+/// matches everything (with an empty range).
+fn filter_match(name: &str, filter: &str) -> Option<std::ops::Range<usize>> {
+    let filter = filter.trim();
+    let start = name
+        .to_ascii_lowercase()
+        .find(&filter.to_ascii_lowercase())?;
+    Some(start..start + filter.len())
+}
 
-                                // Mostrar todos (sin filtro) para debug
-                                let show_all = all.iter().take(30);
+// ─── Frame call ───────────────────────────────────────────────────────────────
 
-                                egui::Grid::new("reg_grid")
-                                    .num_columns(2)
-                                    .spacing([12.0, 1.0])
-                                    .striped(true)
-                                    .show(ui, |ui| {
-                                        for (name, value) in show_all {
-                                            ui.horizontal(|ui| {
-                                                ui.add_space(8.0);
-                                                let col = if is_general_purpose(name) {
-                                                    TXT_CYAN
-                                                } else {
-                                                    TXT_DIM // gris = filtrado normalmente
-                                                };
-                                                ui.label(m(name, 11.0, col));
-                                            });
-                                            ui.label(m(value, 11.0, TXT_YELLOW));
-                                            ui.end_row();
-                                        }
-                                    });
-                            }
-                        }
-                        WatchTab::Data => {
-                            if self.state.disasm.is_empty() {
-                                ui.label(m("Not paused", 11.0, TXT_DIM).italics());
-                            } else {
-                                for asm in &self.state.disasm {
-                                    let col = if asm.current { TXT_HL } else { TXT };
-                                    ui.horizontal(|ui| {
-                                        if asm.current {
-                                            ui.label(m("▶", 11.0, ACCENT));
-                                        } else {
-                                            ui.add_space(14.0);
-                                        }
-                                        ui.label(m(&format!("0x{:x}", asm.addr), 11.0, TXT_DIM));
-                                        ui.add_space(6.0);
-                                        ui.label(m(&asm.inst, 11.0, col));
-                                    });
-                                }
-                            }
-                        }
-                    }
-                });
-            });
+const FRAME_CALL_MAX: usize = 48;
 
-        // ── SOURCE VIEW (central) ─────────────────────────────────────────────
-        egui::CentralPanel::default()
-            .frame(flat(BG_APP))
-            .show(ctx, |ui| {
-                ScrollArea::both().id_salt("source").show(ui, |ui| {
-                    if self.source_lines.is_empty() {
-                        ui.centered_and_justified(|ui| {
-                            ui.label(m("No source file loaded", 13.0, TXT_DIM).italics());
-                        });
-                        return;
-                    }
+/// `parse(buf=0x4052a0 "abc", len=3)`; just the name until args arrive.
+fn frame_call(frame: &crate::state::Frame) -> String {
+    if frame.args.is_empty() {
+        return frame.function.clone();
+    }
+    let args: Vec<String> = frame
+        .args
+        .iter()
+        .map(|a| format!("{}={}", a.name, a.value))
+        .collect();
+    format!("{}({})", frame.function, args.join(", "))
+}
 
-                    let current_line = self.state.current_line();
+fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &s[..i]),
+        None => s.to_owned(),
+    }
+}
 
-                    for line in &self.source_lines {
-                        let is_current = Some(line.number) == current_line;
-                        let has_bp = self
-                            .state
-                            .breakpoint_at(self.source_file.as_deref().unwrap_or(""), line.number)
-                            .is_some();
+// ─── Frame preview ────────────────────────────────────────────────────────────
 
-                        source_row(ui, line.number, &line.text, is_current, has_bp);
-                    }
-                });
-            });
+const PREVIEW_CONTEXT: u32 = 2;
+
+fn frame_preview(ui: &mut egui::Ui, lines: Option<&[String]>, line: u32) {
+    let Some(lines) = lines else {
+        ui.label(m("Source not found", 11.0, TXT_DIM).italics());
+        return;
+    };
+    let first = line.saturating_sub(PREVIEW_CONTEXT).max(1);
+    for n in first..=line + PREVIEW_CONTEXT {
+        let Some(text) = lines.get(n as usize - 1) else {
+            break;
+        };
+        let col = if n == line { TXT_HL } else { TXT_MUTED };
+        ui.label(m(&format!("{n:>5}  {text}"), 11.0, col));
     }
 }
 
-// ─── Source row ───────────────────────────────────────────────────────────────
+/// `4K`, `132K`, `8M`: region sizes are page multiples, so whole units read fine.
+fn size_label(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{}G", b >> 30),
+        b if b >= 1 << 20 => format!("{}M", b >> 20),
+        b if b >= 1 << 10 => format!("{}K", b >> 10),
+        b => format!("{b}"),
+    }
+}
 
-fn source_row(ui: &mut egui::Ui, line_no: u32, code: &str, is_current: bool, has_bp: bool) {
-    let (rect, _) = ui.allocate_exact_size(
-        Vec2::new(f32::max(ui.available_width(), 900.0), 18.0),
-        Sense::hover(),
-    );
-    let p = ui.painter();
-    let cy = rect.center().y;
+// ─── Top bar ──────────────────────────────────────────────────────────────────
 
-    if is_current {
-        p.rect_filled(rect, 0.0, BG_LINE_HL);
-        p.line_segment(
-            [rect.left_top(), rect.left_bottom()],
-            Stroke::new(2.0, ACCENT),
-        );
+/// `file — function`, plus `@ 0x…` if asked. Without a file the address is
+/// all there is to go by, so it's always shown.
+fn location_text(file: Option<&str>, func: &str, addr: u64, show_pc: bool) -> String {
+    match file {
+        Some(file) if show_pc => format!("{file} — {func} @ 0x{addr:x}"),
+        Some(file) => format!("{file} — {func}"),
+        None => format!("{func} @ 0x{addr:x}"),
     }
+}
 
-    if has_bp {
-        p.circle_filled(egui::pos2(rect.left() + 9.0, cy), 5.0, RED);
+/// What the location label copies: `file:line` when there's source, the
+/// address otherwise.
+fn copy_location_text(file: Option<&str>, line: Option<u32>, addr: u64) -> String {
+    match (file, line) {
+        (Some(file), Some(line)) => format!("{file}:{line}"),
+        _ => format!("0x{addr:x}"),
     }
+}
 
-    // Line number – right-aligned in a 56 px gutter
-    p.text(
-        egui::pos2(rect.left() + 56.0, cy),
-        egui::Align2::RIGHT_CENTER,
-        format!("{line_no}"),
-        FontId::monospace(12.0),
-        if has_bp { RED } else { TXT_DIM },
-    );
+// ─── Exit overlay ─────────────────────────────────────────────────────────────
 
-    // Code
-    p.text(
-        egui::pos2(rect.left() + 66.0, cy),
-        egui::Align2::LEFT_CENTER,
-        code,
-        FontId::monospace(12.5),
-        if is_current { TXT_HL } else { TXT },
-    );
+fn exit_message(code: Option<i32>, signal: Option<&str>) -> String {
+    match (code, signal) {
+        (_, Some(sig)) => format!("Program terminated by signal {sig}"),
+        (Some(0), None) => "Program exited normally".into(),
+        (Some(c), None) => format!("Program exited with code {c}"),
+        (None, None) => "Program exited".into(),
+    }
+}
+
+/// Dims the panel and shows `msg` with a Restart button. Returns true on Restart.
+fn exit_overlay(ui: &mut egui::Ui, msg: &str) -> bool {
+    let rect = ui.max_rect();
+    ui.painter()
+        .rect_filled(rect, 0.0, Color32::from_black_alpha(160));
+
+    let mut restart = false;
+    egui::Area::new(egui::Id::new("exit_overlay"))
+        .fixed_pos(rect.center())
+        .pivot(egui::Align2::CENTER_CENTER)
+        .order(egui::Order::Foreground)
+        .show(ui.ctx(), |ui| {
+            Frame::new()
+                .fill(BG_TOPBAR)
+                .stroke(Stroke::new(1.0, SEP_COLOR))
+                .inner_margin(Margin::same(16))
+                .show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(m(msg, 14.0, TXT_HL).strong());
+                        ui.add_space(10.0);
+                        restart = tbtn(ui, "Restart", true).clicked();
+                    });
+                });
+        });
+    restart
 }
 
 // ─── Micro-helpers ────────────────────────────────────────────────────────────
@@ -806,33 +6614,45 @@ fn apply_theme(ctx: &egui::Context) {
     ctx.set_visuals(v);
 }
 
-// ─── Register filter ─────────────────────────────────────────────────────────
+// ─── Register editing ─────────────────────────────────────────────────────────
 
-fn is_general_purpose(name: &str) -> bool {
+/// Registers that redirect control flow or the stack; writes need confirmation.
+fn needs_confirmation(name: &str) -> bool {
     matches!(
         name,
-        // x86-64
-        "rax" | "rbx" | "rcx" | "rdx"
-        | "rsi" | "rdi" | "rbp" | "rsp"
-        | "r8"  | "r9"  | "r10" | "r11"
-        | "r12" | "r13" | "r14" | "r15"
-        | "rip" | "rflags" | "eflags"
-        // x86-32
-        | "eax" | "ebx" | "ecx" | "edx"
-        | "esi" | "edi" | "ebp" | "esp" | "eip"
-        // ARM64
-        | "x0"  | "x1"  | "x2"  | "x3"  | "x4"  | "x5"  | "x6"  | "x7"
-        | "x8"  | "x9"  | "x10" | "x11" | "x12" | "x13" | "x14" | "x15"
-        | "x16" | "x17" | "x18" | "x19" | "x20" | "x21" | "x22" | "x23"
-        | "x24" | "x25" | "x26" | "x27" | "x28" | "x29" | "x30"
-        | "sp" | "pc" | "cpsr"
-        // RISC-V
-        | "zero" | "ra" | "gp" | "tp"
-        | "a0" | "a1" | "a2" | "a3" | "a4" | "a5" | "a6" | "a7"
-        | "s0" | "s1" | "t0" | "t1" | "t2" | "t3" | "t4" | "t5" | "t6"
+        "pc" | "rip" | "eip" | "sp" | "rsp" | "esp" | "rbp" | "ebp" | "x29" | "x30"
     )
 }
 
+/// Why `addr` isn't a sensible new pc. Once the mappings have been read it
+/// has to be in an executable region.
+fn check_pc(addr: u64, mappings: &[MemoryRegion]) -> Result<(), String> {
+    if addr == 0 {
+        return Err("0x0 is never code".into());
+    }
+    match mappings.iter().find(|r| (r.start..r.end).contains(&addr)) {
+        _ if mappings.is_empty() => Ok(()),
+        None => Err(format!("0x{addr:x} isn't mapped")),
+        // Older GDBs don't print permissions
+        Some(r) if !r.perms.is_empty() && !r.perms.contains('x') => Err(format!(
+            "0x{addr:x} isn't in executable memory ({})",
+            r.perms
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Accepts `0x`-prefixed hex or (optionally negative) decimal. Returns the
+/// normalized value to hand to GDB.
+fn parse_register_value(input: &str) -> Option<String> {
+    let s = input.trim();
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()?;
+        return Some(format!("0x{}", hex.to_ascii_lowercase()));
+    }
+    s.parse::<i64>().ok().map(|v| v.to_string())
+}
+
 fn display_order(name: &str) -> u32 {
     match name {
         "rax" => 0,
@@ -865,3 +6685,795 @@ fn display_order(name: &str) -> u32 {
         _ => 99,
     }
 }
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_register_value() {
+        assert_eq!(parse_register_value("0x1F"), Some("0x1f".into()));
+        assert_eq!(parse_register_value(" 42 "), Some("42".into()));
+        assert_eq!(parse_register_value("-1"), Some("-1".into()));
+        assert_eq!(parse_register_value("0xzz"), None);
+        assert_eq!(parse_register_value("rax"), None);
+        assert_eq!(parse_register_value(""), None);
+    }
+
+    #[test]
+    fn test_stale_views_request_only_visible() {
+        let mut stale = StaleViews::all();
+        assert_eq!(
+            stale.requests_for(WatchTab::Registers),
+            vec![Command::RequestRegisters]
+        );
+        // Ya pedido: no se repite hasta la próxima parada
+        assert!(stale.requests_for(WatchTab::Registers).is_empty());
+        assert!(stale.locals && stale.disasm);
+
+        assert_eq!(
+            stale.requests_for(WatchTab::Data),
+            vec![Command::RequestDisasm]
+        );
+        assert!(
+            StaleViews::default()
+                .requests_for(WatchTab::Watch)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_entry_run_sequence() {
+        assert_eq!(
+            entry_run_sequence("  ", false),
+            vec![Command::StartInstruction]
+        );
+
+        let seq = entry_run_sequence("_start", false);
+        assert_eq!(seq.len(), 2);
+        match &seq[0] {
+            Command::InsertBreakpoint(spec) => {
+                assert_eq!(spec.location, "*_start");
+                assert!(spec.temporary);
+                assert!(spec.enabled);
+                assert!(!spec.pending);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(seq[1], Command::Run);
+
+        // Addresses and already-starred symbols aren't double-starred
+        match &entry_run_sequence("*0x401020", true)[0] {
+            Command::InsertBreakpoint(spec) => {
+                assert_eq!(spec.location, "*0x401020");
+                assert!(spec.pending);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_repeat_command() {
+        let history =
+            |cmds: &[&str]| -> Vec<String> { cmds.iter().map(|c| c.to_string()).collect() };
+        assert_eq!(repeat_command(&[]), None);
+        assert_eq!(
+            repeat_command(&history(&["break main", "next"])),
+            Some("next")
+        );
+        assert_eq!(repeat_command(&history(&["x/4x $sp"])), Some("x/4x $sp"));
+        assert_eq!(repeat_command(&history(&["next", "run"])), None);
+        assert_eq!(repeat_command(&history(&["delete 2"])), None);
+    }
+
+    #[test]
+    fn test_substitute_path() {
+        let rules = vec![
+            (
+                "/builds/project/".to_string(),
+                "/home/me/project".to_string(),
+            ),
+            ("/builds".to_string(), "/mnt/ci".to_string()),
+        ];
+        assert_eq!(
+            substitute_path("/builds/project/src/main.c", &rules).as_deref(),
+            Some("/home/me/project/src/main.c")
+        );
+        // First matching rule wins, later ones still apply to other paths
+        assert_eq!(
+            substitute_path("/builds/other/lib.c", &rules).as_deref(),
+            Some("/mnt/ci/other/lib.c")
+        );
+        // Only whole path components match
+        assert_eq!(substitute_path("/buildsX/main.c", &rules), None);
+        assert_eq!(substitute_path("/usr/include/stdio.h", &rules), None);
+        assert_eq!(
+            substitute_path("/builds", &rules).as_deref(),
+            Some("/mnt/ci")
+        );
+    }
+
+    #[test]
+    fn test_print_expression() {
+        assert_eq!(print_expression("print count"), Some("count"));
+        assert_eq!(print_expression("p/x flags & 0xff"), Some("flags & 0xff"));
+        assert_eq!(print_expression("  p  *node "), Some("*node"));
+        assert_eq!(print_expression("print"), None);
+        assert_eq!(print_expression("ptype node"), None);
+        assert_eq!(print_expression("next"), None);
+    }
+
+    #[test]
+    fn test_watch_expression() {
+        assert_eq!(watch_expression("counter", false, &[]), "counter");
+        assert_eq!(watch_expression("this", true, &["len"]), "this->len");
+        assert_eq!(
+            watch_expression("cfg", false, &["limits", "max"]),
+            "cfg.limits.max"
+        );
+        assert_eq!(
+            watch_expression("this", true, &["items", "[3]", "id"]),
+            "this->items[3].id"
+        );
+        assert!(hw_watchpoints_exhausted(
+            "Could not insert hardware watchpoint 5."
+        ));
+        assert!(!hw_watchpoints_exhausted(
+            "No symbol \"x\" in current context."
+        ));
+    }
+
+    #[test]
+    fn test_ambiguous_breakpoint_location() {
+        assert!(ambiguous_location("Ambiguous file name \"util.c\"."));
+        assert!(!ambiguous_location("No source file named util.c."));
+
+        assert_eq!(break_location("util.c:42"), Some(("util.c", 42)));
+        assert_eq!(
+            break_location("/my src/util.c:7"),
+            Some(("/my src/util.c", 7))
+        );
+        assert_eq!(break_location("main"), None);
+
+        let known = [
+            "/src/net/util.c",
+            "/src/main.c",
+            "/src/fs/util.c",
+            "/src/net/util.c",
+        ];
+        assert_eq!(
+            same_basename("util.c", known),
+            vec!["/src/net/util.c", "/src/fs/util.c"]
+        );
+        assert!(same_basename("other.c", known).is_empty());
+    }
+
+    #[test]
+    fn test_receiver_first() {
+        let var = |name: &str| Variable {
+            name: name.into(),
+            value: "0".into(),
+            type_: String::new(),
+        };
+        let locals = vec![var("i"), var("n"), var("this"), var("tmp")];
+        let names = |receiver_first| -> Vec<&str> {
+            order_locals(&locals, Language::Cpp, receiver_first)
+                .into_iter()
+                .map(|(_, v)| v.name.as_str())
+                .collect()
+        };
+        assert_eq!(names(true), vec!["this", "i", "n", "tmp"]);
+        assert_eq!(names(false), vec!["i", "n", "this", "tmp"]);
+        assert_eq!(order_locals(&locals, Language::Cpp, true)[0].0, 2);
+        assert_eq!(receiver_index(&[var("self")], Language::Unknown), Some(0));
+        assert_eq!(receiver_index(&[var("i")], Language::Unknown), None);
+        // A C variable that happens to be called `this`
+        assert_eq!(receiver_index(&[var("this")], Language::C), None);
+        assert_eq!(receiver_index(&[var("this")], Language::Rust), None);
+    }
+
+    #[test]
+    fn test_move_cursor() {
+        assert_eq!(move_cursor(0, 1, 3), 1);
+        assert_eq!(move_cursor(2, 1, 3), 2);
+        assert_eq!(move_cursor(0, -1, 3), 0);
+        assert_eq!(move_cursor(1, -1, 3), 0);
+        // The list shrank under the cursor
+        assert_eq!(move_cursor(7, 0, 3), 2);
+        assert_eq!(move_cursor(0, 1, 0), 0);
+    }
+
+    #[test]
+    fn test_stack_page_request() {
+        assert!(stack_page_request("-stack-list-frames 32 63"));
+        assert!(stack_page_request("-stack-list-arguments 1 32 63"));
+        assert!(!stack_page_request("-stack-list-variables --all-values"));
+        assert!(!stack_page_request("-stack-select-frame 2"));
+    }
+
+    #[test]
+    fn test_errno_request() {
+        assert!(errno_request(
+            r#"-data-evaluate-expression "*((int *(*)(void))__errno_location)()""#
+        ));
+        // The user's own look at it still gets its error shown
+        assert!(!errno_request(
+            r#"-data-evaluate-expression "__errno_location()""#
+        ));
+    }
+
+    #[test]
+    fn test_watch_requests() {
+        let mut state = DebuggerState::new();
+        for expr in ["n", "p", "q"] {
+            state.apply(StateEvent::WatchAdded { expr: expr.into() });
+        }
+        let created = |expr: &str, var: &str, in_scope| StateEvent::WatchCreated {
+            expr: expr.into(),
+            result: Ok(crate::state::WatchValue {
+                var: var.into(),
+                value: "0".into(),
+                type_: "int".into(),
+                in_scope,
+            }),
+        };
+        let create = |expr: &str| Command::CreateVarObject(expr.into());
+
+        let none = HashSet::new();
+
+        // Nothing alive yet: everything gets created
+        assert_eq!(
+            watch_requests(&state, &none, true),
+            vec![create("n"), create("p"), create("q")]
+        );
+
+        state.apply(created("n", "var1", true));
+        state.apply(created("p", "var2", false));
+        state.apply(StateEvent::WatchCreated {
+            expr: "q".into(),
+            result: Err("No symbol \"q\" in current context.".into()),
+        });
+        assert_eq!(
+            watch_requests(&state, &none, true),
+            vec![
+                Command::UpdateVarObjects,
+                Command::DeleteVarObject("var2".into()),
+                create("p"),
+                create("q"),
+            ]
+        );
+        // After the update only the ones that left scope are remade
+        assert_eq!(
+            watch_requests(&state, &none, false),
+            vec![Command::DeleteVarObject("var2".into()), create("p")]
+        );
+        // Creates still in flight aren't sent again
+        let creating = HashSet::from(["p".to_owned(), "q".to_owned()]);
+        assert_eq!(
+            watch_requests(&state, &creating, true),
+            vec![Command::UpdateVarObjects]
+        );
+        assert_eq!(watch_requests(&state, &creating, false), vec![]);
+    }
+
+    #[test]
+    fn test_orphaned_varobj() {
+        let mut state = DebuggerState::new();
+        state.apply(StateEvent::WatchAdded { expr: "n".into() });
+        let made = Ok(crate::state::WatchValue {
+            var: "var1".into(),
+            value: "0".into(),
+            type_: "int".into(),
+            in_scope: true,
+        });
+        assert_eq!(orphaned_varobj(&state, "n", &made), None);
+        assert_eq!(orphaned_varobj(&state, "p", &made), Some("var1".into()));
+        assert_eq!(orphaned_varobj(&state, "p", &Err("No symbol".into())), None);
+    }
+
+    #[test]
+    fn test_tree_rows_skip_closed_nodes() {
+        let value = ValueTree::parse("{a = 1, b = {c = 2, d = {e = 3}}, arr = {{x = 1}, {x = 2}}}");
+        let paths = |open: &HashSet<String>| -> Vec<String> {
+            tree_rows("s", &value, open)
+                .into_iter()
+                .map(|r| r.path)
+                .collect()
+        };
+
+        assert_eq!(paths(&HashSet::new()), ["s"]);
+
+        let mut open = HashSet::from(["s".to_owned()]);
+        assert_eq!(paths(&open), ["s", "s.a", "s.b", "s.arr"]);
+
+        // s.b.d stays hidden until s.b opens; moving down goes from b to arr
+        open.insert("s.b.d".into());
+        assert_eq!(paths(&open), ["s", "s.a", "s.b", "s.arr"]);
+        open.insert("s.b".into());
+        assert_eq!(
+            paths(&open),
+            ["s", "s.a", "s.b", "s.b.c", "s.b.d", "s.b.d.e", "s.arr"]
+        );
+
+        open.insert("s.arr".into());
+        let rows = tree_rows("s", &value, &open);
+        let last = rows.last().unwrap();
+        assert_eq!(
+            (last.path.as_str(), last.name, last.depth),
+            ("s.arr[1]", "[1]", 2)
+        );
+        assert!(last.expandable);
+        let leaf = &rows[1];
+        assert_eq!((leaf.name, leaf.value, leaf.expandable), ("a", "1", false));
+
+        // Scalar elements show their index and value
+        let xs = ValueTree::parse("{1, 2}");
+        let open = HashSet::from(["xs".to_owned()]);
+        let rows = tree_rows("xs", &xs, &open);
+        assert_eq!(
+            rows.iter()
+                .map(|r| (r.path.as_str(), r.name, r.value))
+                .collect::<Vec<_>>(),
+            [
+                ("xs", "xs", ""),
+                ("xs[0]", "[0]", "1"),
+                ("xs[1]", "[1]", "2")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thread_display() {
+        use crate::state::Frame;
+
+        let frame = |function: &str, file: Option<&str>, line| Frame {
+            level: 0,
+            args: vec![],
+            addr: 0,
+            function: function.into(),
+            file: file.map(Into::into),
+            line,
+        };
+        let faulting = Thread {
+            id: 1,
+            target_id: "Thread 0x7ffff7d8a740 (LWP 1234)".into(),
+            name: None,
+            state: "stopped".into(),
+            frame: Some(frame("deref", Some("/src/main.c"), Some(12))),
+        };
+        let waiting = Thread {
+            id: 2,
+            target_id: "Thread 0x7ffff7d89640 (LWP 1235)".into(),
+            name: Some("worker".into()),
+            state: "stopped".into(),
+            frame: Some(frame("__futex_abstimed_wait_common", None, None)),
+        };
+        let pause = PauseState {
+            thread_id: 1,
+            frame: frame("deref", Some("/src/main.c"), Some(12)),
+            stack: vec![],
+            stop_reason: StopReason::Signal("SIGSEGV".into()),
+        };
+
+        assert_eq!(
+            thread_title(&faulting),
+            "1 Thread 0x7ffff7d8a740 (LWP 1234)"
+        );
+        assert_eq!(thread_title(&waiting), "2 worker");
+        assert_eq!(
+            thread_detail(&faulting, &pause),
+            "SIGSEGV · deref at main.c:12"
+        );
+        assert_eq!(
+            thread_detail(&waiting, &pause),
+            "stopped · __futex_abstimed_wait_common"
+        );
+        let running = Thread {
+            state: "running".into(),
+            frame: None,
+            ..waiting
+        };
+        assert_eq!(thread_detail(&running, &pause), "running");
+    }
+
+    #[test]
+    fn test_line_breakpoint_toggle() {
+        use crate::state::{Breakpoint, BreakpointKind, Frame};
+
+        let mut state = DebuggerState::new();
+        assert_eq!(line_breakpoint_toggle(&state, false), None);
+
+        state.apply(StateEvent::ProgramPaused {
+            pause: PauseState {
+                thread_id: 1,
+                frame: Frame {
+                    level: 0,
+                    args: vec![],
+                    addr: 0x401136,
+                    function: "main".into(),
+                    file: Some("/src/main.c".into()),
+                    line: Some(7),
+                },
+                stack: vec![],
+                stop_reason: StopReason::EndStepping,
+            },
+        });
+        assert_eq!(
+            line_breakpoint_toggle(&state, false),
+            Some(Command::InsertBreakpoint(BreakpointSpec::new(
+                "/src/main.c:7"
+            )))
+        );
+        assert_eq!(
+            line_breakpoint_toggle(&state, true),
+            Some(Command::InsertBreakpoint(BreakpointSpec {
+                pending: true,
+                ..BreakpointSpec::new("/src/main.c:7")
+            }))
+        );
+
+        state.apply(StateEvent::BreakpointAdded {
+            breakpoint: Breakpoint {
+                id: 4,
+                kind: BreakpointKind::Line,
+                file: "/src/main.c".into(),
+                line: 7,
+                enabled: true,
+                temporary: false,
+                condition: None,
+                ignore_count: 0,
+                pending: false,
+                log_message: None,
+                original_location: None,
+                what: None,
+                commands: vec![],
+            },
+        });
+        assert_eq!(
+            line_breakpoint_toggle(&state, true),
+            Some(Command::RemoveBreakpoint(4))
+        );
+    }
+
+    #[test]
+    fn test_breadcrumb() {
+        let stack: Vec<_> = ["scan", "parse", "process", "main"]
+            .iter()
+            .enumerate()
+            .map(|(level, f)| crate::state::Frame {
+                level: level as u32,
+                args: vec![],
+                addr: 0,
+                function: f.to_string(),
+                file: None,
+                line: None,
+            })
+            .collect();
+        let names = |crumbs: Vec<Option<&crate::state::Frame>>| -> Vec<String> {
+            crumbs
+                .iter()
+                .map(|c| c.map_or("…".into(), |f| f.function.clone()))
+                .collect()
+        };
+        assert_eq!(
+            names(breadcrumb(&stack, false, 8)),
+            ["main", "process", "parse", "scan"]
+        );
+        // Middle elided, the innermost frames stay
+        let deep: Vec<_> = (0..6)
+            .map(|level| crate::state::Frame {
+                level,
+                function: format!("f{level}"),
+                ..stack[0].clone()
+            })
+            .collect();
+        assert_eq!(names(breadcrumb(&deep, false, 4)), ["f5", "f4", "…", "f0"]);
+        assert_eq!(
+            names(breadcrumb(&stack[..3], true, 8)),
+            ["…", "process", "parse", "scan"]
+        );
+        assert!(breadcrumb(&[], false, 8).is_empty());
+    }
+
+    #[test]
+    fn test_frame_call() {
+        let mut frame = crate::state::Frame {
+            level: 0,
+            args: vec![],
+            addr: 0,
+            function: "parse".into(),
+            file: None,
+            line: None,
+        };
+        assert_eq!(frame_call(&frame), "parse");
+        frame.args = ["buf", "len"]
+            .iter()
+            .zip(["0x4052a0", "3"])
+            .map(|(n, v)| Variable {
+                name: n.to_string(),
+                value: v.to_string(),
+                type_: String::new(),
+            })
+            .collect();
+        assert_eq!(frame_call(&frame), "parse(buf=0x4052a0, len=3)");
+        assert_eq!(truncate_chars("abcdef", 3), "abc…");
+        assert_eq!(truncate_chars("abc", 3), "abc");
+    }
+
+    #[test]
+    fn test_filter_match() {
+        assert_eq!(filter_match("bufLen", ""), Some(0..0));
+        assert_eq!(filter_match("bufLen", "len"), Some(3..6));
+        assert_eq!(filter_match("bufLen", "BUF"), Some(0..3));
+        assert_eq!(filter_match("count", "idx"), None);
+    }
+
+    #[test]
+    fn test_cast_expression() {
+        assert_eq!(
+            cast_expression("0x7fffffffe010", "struct foo").as_deref(),
+            Some("*(struct foo *)(0x7fffffffe010)")
+        );
+        // The pointer type named as such reads the same
+        assert_eq!(
+            cast_expression(" buf + 8 ", "struct foo *").as_deref(),
+            Some("*(struct foo *)(buf + 8)")
+        );
+        assert_eq!(
+            cast_expression("node->next", "std::vector<int>").as_deref(),
+            Some("*(std::vector<int> *)(node->next)")
+        );
+        assert_eq!(cast_expression("", "int"), None);
+        assert_eq!(cast_expression("0x10", " "), None);
+        assert_eq!(cast_expression("0x10) + (1", "int"), None);
+        assert_eq!(cast_expression("0x10", "int); call abort("), None);
+        assert_eq!(cast_expression("0x10; kill", "int"), None);
+    }
+
+    #[test]
+    fn test_welcome_until_something_loads() {
+        let (_event_tx, event_rx) = std::sync::mpsc::channel();
+        let (cmd_tx, _cmd_rx) = std::sync::mpsc::channel();
+        let mut app = App::new(DebuggerState::new(), Settings::default(), event_rx, cmd_tx);
+        assert!(app.shows_welcome());
+
+        app.state.apply(StateEvent::ProgramLoaded {
+            executable: "./a.out".into(),
+        });
+        assert!(!app.shows_welcome());
+
+        // A source file passed by mistake is shown instead
+        let mut app = App::new(
+            DebuggerState::new(),
+            Settings::default(),
+            std::sync::mpsc::channel().1,
+            std::sync::mpsc::channel().0,
+        );
+        app.prompt_executable("main.c".into());
+        assert!(!app.shows_welcome());
+    }
+
+    #[test]
+    fn test_logpoint_result_before_last_eval() {
+        let (_event_tx, event_rx) = std::sync::mpsc::channel();
+        let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+        let mut app = App::new(DebuggerState::new(), Settings::default(), event_rx, cmd_tx);
+        cmd_rx.try_iter().for_each(drop);
+        app.evaluate("i".into());
+        app.start_logpoint(1, "i = {i}");
+        assert_eq!(
+            cmd_rx.try_iter().collect::<Vec<_>>(),
+            [Command::Evaluate("i".into()), Command::Evaluate("i".into())]
+        );
+
+        // The same expression in flight for the Eval row doesn't take it
+        app.on_evaluation_result("i".into(), Ok("3".into()));
+        assert!(app.pending_log.is_none());
+        assert_eq!(cmd_rx.try_iter().collect::<Vec<_>>(), [Command::Continue]);
+        assert!(
+            app.console_log
+                .shown(Verbosity::Quiet)
+                .any(|l| l == "[log #1] i = 3")
+        );
+        assert!(app.last_eval_pending);
+
+        app.on_evaluation_result("i".into(), Ok("3".into()));
+        assert_eq!(app.last_eval_result, Some(Ok("3".into())));
+    }
+
+    #[test]
+    fn test_ambiguous_breakpoint_retry() {
+        let (_event_tx, event_rx) = std::sync::mpsc::channel();
+        let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+        let mut app = App::new(DebuggerState::new(), Settings::default(), event_rx, cmd_tx);
+        app.open_tabs = vec!["/src/net/util.c".into(), "/src/fs/util.c".into()];
+        let spec = BreakpointSpec {
+            temporary: true,
+            condition: Some("n > 2".into()),
+            ignore_count: 3,
+            enabled: false,
+            ..BreakpointSpec::new("util.c:42")
+        };
+        let insert = spec.clone().insert(false);
+        app.act(insert.clone());
+        cmd_rx.try_iter().for_each(drop);
+
+        app.on_gdb_error(
+            "Ambiguous file name \"util.c\".".into(),
+            Some(command_to_mi(&insert)),
+        );
+        assert!(matches!(&app.bp_choices, Some((_, 42, paths)) if paths.len() == 2));
+
+        // Only the location changes: still temporary, conditional, ignored
+        app.pick_breakpoint_file("/src/fs/util.c");
+        let retry = cmd_rx.try_iter().collect::<Vec<_>>();
+        assert_eq!(
+            retry,
+            [Command::InsertBreakpoint(BreakpointSpec {
+                location: "/src/fs/util.c:42".into(),
+                ..spec
+            })]
+        );
+        assert_eq!(
+            command_to_mi(&retry[0]),
+            "-break-insert -t -d -c \"n > 2\" -i 3 /src/fs/util.c:42"
+        );
+        assert!(app.bp_choices.is_none());
+    }
+
+    #[test]
+    fn test_apply_layout_preset() {
+        let (_event_tx, event_rx) = std::sync::mpsc::channel();
+        let (cmd_tx, _cmd_rx) = std::sync::mpsc::channel();
+        let mut app = App::new(DebuggerState::new(), Settings::default(), event_rx, cmd_tx);
+
+        app.apply_preset(LayoutPreset::Assembly);
+        assert_eq!(app.settings.layout, LayoutPreset::Assembly);
+        assert_eq!(app.settings.layout.central(), CentralView::Assembly);
+        assert_eq!(app.watch_tab, WatchTab::Registers);
+        assert_eq!(app.panel_resize, Some(LayoutPreset::Assembly.panel_sizes()));
+        assert!(app.open_bp && app.open_stack && app.open_memory);
+        assert!(!app.open_files);
+
+        app.panel_resize = None;
+        app.apply_preset(LayoutPreset::Mixed);
+        assert_eq!(app.settings.layout.central(), CentralView::Split);
+        assert_eq!(app.watch_tab, WatchTab::Watch);
+        assert!(app.panel_resize.is_some());
+        assert!(!app.open_memory);
+
+        app.apply_preset(LayoutPreset::Source);
+        assert_eq!(app.settings.layout.central(), CentralView::Source);
+        assert!(app.open_files);
+    }
+
+    #[test]
+    fn test_size_label() {
+        assert_eq!(size_label(0x1000), "4K");
+        assert_eq!(size_label(0x21000), "132K");
+        assert_eq!(size_label(8 << 20), "8M");
+        assert_eq!(size_label(512), "512");
+    }
+
+    #[test]
+    fn test_location_text() {
+        assert_eq!(
+            location_text(Some("main.c"), "main", 0x401136, true),
+            "main.c — main @ 0x401136"
+        );
+        assert_eq!(
+            location_text(Some("main.c"), "main", 0x401136, false),
+            "main.c — main"
+        );
+        assert_eq!(
+            location_text(None, "__libc_start_main", 0x7ffff7dd1d90, false),
+            "__libc_start_main @ 0x7ffff7dd1d90"
+        );
+    }
+
+    #[test]
+    fn test_copy_location_text() {
+        assert_eq!(
+            copy_location_text(Some("/src/main.c"), Some(12), 0x401136),
+            "/src/main.c:12"
+        );
+        assert_eq!(
+            copy_location_text(None, None, 0x7ffff7dd1d90),
+            "0x7ffff7dd1d90"
+        );
+        assert_eq!(
+            copy_location_text(Some("main.c"), None, 0x401136),
+            "0x401136"
+        );
+    }
+
+    #[test]
+    fn test_exit_message() {
+        assert_eq!(exit_message(Some(0), None), "Program exited normally");
+        assert_eq!(exit_message(Some(3), None), "Program exited with code 3");
+        assert_eq!(
+            exit_message(None, Some("SIGSEGV")),
+            "Program terminated by signal SIGSEGV"
+        );
+    }
+
+    #[test]
+    fn test_needs_confirmation() {
+        assert!(needs_confirmation("rip"));
+        assert!(needs_confirmation("pc"));
+        assert!(!needs_confirmation("rax"));
+    }
+
+    #[test]
+    fn test_check_pc() {
+        let region = |start, end, perms: &str| MemoryRegion {
+            start,
+            end,
+            offset: 0,
+            perms: perms.into(),
+            path: String::new(),
+        };
+        // Nothing to check against yet
+        assert!(check_pc(0x401000, &[]).is_ok());
+        assert!(check_pc(0, &[]).is_err());
+
+        let maps = [
+            region(0x400000, 0x401000, "r--p"),
+            region(0x401000, 0x402000, "r-xp"),
+        ];
+        assert!(check_pc(0x401130, &maps).is_ok());
+        assert!(check_pc(0x400100, &maps).is_err());
+        assert!(check_pc(0x402000, &maps).is_err());
+        // No permissions column: any mapped address goes
+        assert!(check_pc(0x400100, &[region(0x400000, 0x401000, "")]).is_ok());
+    }
+
+    #[test]
+    fn test_inline_values() {
+        let source = [
+            "int total;",
+            "",
+            "int sum(int *a, int n) {",
+            "    int s = 0;",
+            "    for (int i = 0; i < n; i++)",
+            "        s += a[i];",
+            "    if (s > total) print(s);",
+            "    return s;",
+        ];
+        let lines: Vec<_> = source
+            .iter()
+            .zip(1..)
+            .map(|(text, number)| SourceLine {
+                number,
+                text: text.to_string(),
+            })
+            .collect();
+        let var = |name: &str, value: &str| Variable {
+            name: name.into(),
+            value: value.into(),
+            type_: "int".into(),
+        };
+        let locals = [
+            var("n", "4"),
+            var("s", "6"),
+            var("i", "2"),
+            var("a", "0x7ffd0000 <buf> \"a very long string value\""),
+        ];
+
+        let inline = inline_values(&lines, 7, &locals);
+        assert_eq!(inline[&3], "n = 4, a = 0x7ffd0000 <buf> \"a very long …");
+        assert_eq!(inline[&4], "s = 6");
+        assert_eq!(inline[&5], "n = 4, i = 2");
+        // `i` isn't in `if`/`int`/`print`; nothing past the current line
+        assert_eq!(inline[&7], "s = 6");
+        assert!(!inline.contains_key(&8));
+        // Stops at the function's signature
+        assert!(!inline.contains_key(&1));
+
+        assert!(mentions("a[i]", "i"));
+        assert!(!mentions("if (x)", "i"));
+        assert!(!mentions("s_len", "s"));
+    }
+}
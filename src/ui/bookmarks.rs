@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+// ─── Bookmarks ────────────────────────────────────────────────────────────────
+
+/// Bookmarked lines per source file, sorted. Navigation only: GDB never
+/// hears about them.
+pub type Bookmarks = HashMap<String, Vec<u32>>;
+
+/// Adds the bookmark, or removes it if already there. True when added.
+pub fn toggle(bookmarks: &mut Bookmarks, file: &str, line: u32) -> bool {
+    let lines = bookmarks.entry(file.to_owned()).or_default();
+    let added = match lines.binary_search(&line) {
+        Ok(i) => {
+            lines.remove(i);
+            false
+        }
+        Err(i) => {
+            lines.insert(i, line);
+            true
+        }
+    };
+    if lines.is_empty() {
+        bookmarks.remove(file);
+    }
+    added
+}
+
+/// The first bookmark after `from`, wrapping around to the first one.
+pub fn next(lines: &[u32], from: u32) -> Option<u32> {
+    lines.iter().find(|&&l| l > from).or(lines.first()).copied()
+}
+
+/// The last bookmark before `from`, wrapping around to the last one.
+pub fn prev(lines: &[u32], from: u32) -> Option<u32> {
+    lines.iter().rfind(|&&l| l < from).or(lines.last()).copied()
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bookmark_toggle_and_order() {
+        let mut bookmarks = Bookmarks::new();
+        assert!(toggle(&mut bookmarks, "main.c", 40));
+        assert!(toggle(&mut bookmarks, "main.c", 12));
+        assert!(toggle(&mut bookmarks, "main.c", 25));
+        assert!(toggle(&mut bookmarks, "util.c", 7));
+        assert_eq!(bookmarks["main.c"], vec![12, 25, 40]);
+
+        assert!(!toggle(&mut bookmarks, "main.c", 25));
+        assert_eq!(bookmarks["main.c"], vec![12, 40]);
+        // The last one gone takes the file with it
+        assert!(!toggle(&mut bookmarks, "util.c", 7));
+        assert!(!bookmarks.contains_key("util.c"));
+
+        let lines = &bookmarks["main.c"];
+        assert_eq!(next(lines, 12), Some(40));
+        assert_eq!(next(lines, 40), Some(12));
+        assert_eq!(prev(lines, 40), Some(12));
+        assert_eq!(prev(lines, 5), Some(40));
+        assert_eq!(next(&[], 5), None);
+    }
+}
@@ -0,0 +1,228 @@
+// ─── Snapshots ────────────────────────────────────────────────────────────────
+
+/// GDB's frame base of the selected frame, stable while it runs.
+pub const FRAME_EXPR: &str = "$fp";
+
+/// What a stop looked like, filled in as the refresh replies arrive. Only
+/// categories present in both snapshots are compared.
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    pub function: String,
+    /// `$fp` of frame 0: a recursive call's frame from its caller's
+    pub frame: Option<String>,
+    /// (name, value)
+    pub locals: Option<Vec<(String, String)>>,
+    pub registers: Option<Vec<(String, String)>>,
+    /// Watchpoint that caused this stop: (expression, old, new)
+    pub watch: Option<(String, Option<String>, Option<String>)>,
+    /// Region watched in the Memory view: (address, bytes)
+    pub region: Option<(u64, Vec<u8>)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeKind {
+    Memory,
+    Local,
+    Register,
+}
+
+impl ChangeKind {
+    pub fn tag(self) -> &'static str {
+        match self {
+            ChangeKind::Memory => "mem",
+            ChangeKind::Local => "var",
+            ChangeKind::Register => "reg",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub name: String,
+    /// None for a local that just came into scope
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+// ─── Diff ─────────────────────────────────────────────────────────────────────
+
+/// Everything that changed from `before` to `after`: the watched memory first,
+/// then locals, then registers. Locals are only compared within the same
+/// frame; after a call or return they're a different one's, even in the same
+/// function.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> Vec<Change> {
+    let mut changes = vec![];
+
+    if let Some((expr, old, new)) = &after.watch
+        && old != new
+    {
+        changes.push(Change {
+            kind: ChangeKind::Memory,
+            name: expr.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        });
+    }
+
+    if let (Some((addr, old)), Some((new_addr, new))) = (&before.region, &after.region)
+        && addr == new_addr
+        && let Some(change) = changed_span(*addr, old, new)
+    {
+        changes.push(change);
+    }
+
+    if before.function == after.function
+        && before.frame == after.frame
+        && let (Some(old), Some(new)) = (&before.locals, &after.locals)
+    {
+        changes.extend(changed(ChangeKind::Local, old, new, true));
+    }
+    if let (Some(old), Some(new)) = (&before.registers, &after.registers) {
+        changes.extend(changed(ChangeKind::Register, old, new, false));
+    }
+    changes
+}
+
+/// The bytes from the first to the last that differ, as hex, named by the
+/// first one's address.
+fn changed_span(addr: u64, old: &[u8], new: &[u8]) -> Option<Change> {
+    if old.len() != new.len() {
+        return None;
+    }
+    let differs = |i: &usize| old[*i] != new[*i];
+    let first = (0..new.len()).find(differs)?;
+    let last = (0..new.len()).rfind(differs)?;
+    let hex = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    Some(Change {
+        kind: ChangeKind::Memory,
+        name: format!("0x{:x}", addr + first as u64),
+        old: Some(hex(&old[first..=last])),
+        new: Some(hex(&new[first..=last])),
+    })
+}
+
+/// Values in `new` that differ from `old`; with `added`, also names `old`
+/// didn't have. Names that went away aren't changes worth listing.
+fn changed(
+    kind: ChangeKind,
+    old: &[(String, String)],
+    new: &[(String, String)],
+    added: bool,
+) -> Vec<Change> {
+    new.iter()
+        .filter_map(|(name, value)| {
+            let prev = old.iter().find(|(n, _)| n == name).map(|(_, v)| v);
+            match prev {
+                Some(prev) if prev == value => None,
+                None if !added => None,
+                _ => Some(Change {
+                    kind,
+                    name: name.clone(),
+                    old: prev.cloned(),
+                    new: Some(value.clone()),
+                }),
+            }
+        })
+        .collect()
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Option<Vec<(String, String)>> {
+        Some(
+            items
+                .iter()
+                .map(|(n, v)| (n.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_combined_diff() {
+        let before = Snapshot {
+            function: "main".into(),
+            frame: Some("0x7fffffffe0f0".into()),
+            locals: pairs(&[("i", "1"), ("sum", "0"), ("p", "0x0")]),
+            registers: pairs(&[("rax", "0x1"), ("rip", "0x401130")]),
+            watch: None,
+            region: Some((0x4040, vec![0, 1, 2, 3, 4])),
+        };
+        let after = Snapshot {
+            function: "main".into(),
+            locals: pairs(&[("i", "2"), ("sum", "0"), ("p", "0x0"), ("tmp", "7")]),
+            frame: Some("0x7fffffffe0f0".into()),
+            registers: pairs(&[("rax", "0x1"), ("rip", "0x401138")]),
+            watch: Some(("buf[3]".into(), Some("0".into()), Some("65".into()))),
+            region: Some((0x4040, vec![0, 0xff, 2, 0x41, 4])),
+        };
+
+        let changes = diff(&before, &after);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.kind, c.name.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Memory, "buf[3]", Some("0"), Some("65")),
+                (
+                    ChangeKind::Memory,
+                    "0x4041",
+                    Some("01 02 03"),
+                    Some("ff 02 41")
+                ),
+                (ChangeKind::Local, "i", Some("1"), Some("2")),
+                (ChangeKind::Local, "tmp", None, Some("7")),
+                (
+                    ChangeKind::Register,
+                    "rip",
+                    Some("0x401130"),
+                    Some("0x401138")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_skips_missing_and_other_frames() {
+        let before = Snapshot {
+            function: "main".into(),
+            locals: pairs(&[("i", "1")]),
+            ..Snapshot::default()
+        };
+        // Stepped into a callee; registers weren't fetched before it
+        let after = Snapshot {
+            function: "helper".into(),
+            locals: pairs(&[("n", "3")]),
+            registers: pairs(&[("rax", "0x2")]),
+            ..Snapshot::default()
+        };
+        assert!(diff(&before, &after).is_empty());
+
+        // A recursive call: same function, another frame
+        let outer = Snapshot {
+            function: "fact".into(),
+            frame: Some("0x7fffffffe0f0".into()),
+            locals: pairs(&[("n", "3")]),
+            ..Snapshot::default()
+        };
+        let inner = Snapshot {
+            frame: Some("0x7fffffffe0c0".into()),
+            locals: pairs(&[("n", "2")]),
+            ..outer.clone()
+        };
+        assert!(diff(&outer, &inner).is_empty());
+        assert!(diff(&Snapshot::default(), &Snapshot::default()).is_empty());
+    }
+}
@@ -1,28 +1,228 @@
-#[derive(Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Command {
     // Execution
     Run,
+    /// `starti`: run and stop at the very first instruction, before any
+    /// initializer or loader code. No MI form.
+    StartInstruction,
     Continue,
     Step,
     Next,
     Finish,
+    /// `-exec-until {location}`: run to the location, but stop anyway if
+    /// the current function returns first. No breakpoint is left behind.
+    Advance(String),
+    StepInstruction,
+    NextInstruction,
     Interrupt,
     Restart,
+    Kill,
 
     // Breakpoints
-    AddBreakpoint { file: String, line: u32 },
+    InsertBreakpoint(BreakpointSpec),
+    /// `rbreak {regex}`: a breakpoint on every function matching the regex.
+    AddRegexBreakpoints(String),
+    /// `-break-watch`: stop when `expr` is written, read or either.
+    InsertWatchpoint {
+        expr: String,
+        kind: WatchKind,
+    },
     RemoveBreakpoint(u32),
-    ToggleBreakpoint { id: u32, enable: bool },
+    ToggleBreakpoint {
+        id: u32,
+        enable: bool,
+    },
+    /// `-break-commands`: GDB commands run each time the breakpoint hits.
+    /// An empty list removes them.
+    SetBreakpointCommands {
+        id: u32,
+        commands: Vec<String>,
+    },
+    /// `-break-condition`: stop only when the expression holds. None
+    /// removes the condition.
+    SetBreakpointCondition {
+        id: u32,
+        condition: Option<String>,
+    },
 
     // Program
     LoadExecutable(String),
+    /// `inferior {n}`: switch GDB to another thread group.
+    SelectInferior(u32),
+    /// `-gdb-set follow-fork-mode`: which process to debug after `fork`.
+    SetFollowForkMode(ForkMode),
+    /// `-gdb-set detach-on-fork`: off keeps both processes under GDB.
+    SetDetachOnFork(bool),
+    /// `-gdb-set mi-async`: keep accepting commands while the target runs.
+    /// GDB refuses to change it with a live inferior.
+    SetMiAsync(bool),
+    /// `-exec-arguments`: argv for the next `run`, redirections included.
+    SetArguments(String),
+    /// `set environment {name}={value}` for the next `run`.
+    SetEnvironment {
+        name: String,
+        value: String,
+    },
+    /// `unset environment {name}`: drop a variable set for a previous `run`.
+    UnsetEnvironment(String),
+    /// `-environment-cd`: the program's working directory.
+    ChangeDirectory(String),
+    /// `-gdb-set pagination`: off so long output never waits for <RET>.
+    SetPagination(bool),
+    /// `-gdb-set confirm`: off so `delete`/`run` never ask `(y or n)`.
+    SetConfirm(bool),
+    /// `checkpoint`: fork-based snapshot of the process (Linux only).
+    CreateCheckpoint,
+    /// `restart {n}`: switch to checkpoint `n`.
+    RestartCheckpoint(u32),
+    DeleteCheckpoint(u32),
 
+    /// `-var-create`: a variable object for a watch expression, in the
+    /// selected frame.
+    CreateVarObject(String),
+    /// `-var-update`: new values of every varobj that changed.
+    UpdateVarObjects,
+    /// `-var-delete`, by varobj name.
+    DeleteVarObject(String),
+    /// `-stack-select-frame`: later frame-relative commands apply to this level.
+    SelectFrame(u32),
+    /// `-thread-select`: stack, locals and registers follow this thread.
+    SelectThread(u32),
     RequestLocals,
-    RequestStack,
+    /// `-stack-list-frames`, optionally limited to levels `low..=high`
+    /// (`high: None` lists to the outermost frame).
+    RequestStack {
+        low: Option<u32>,
+        high: Option<u32>,
+    },
+    /// `-stack-list-arguments 1` for the same level range as `RequestStack`.
+    RequestFrameArguments {
+        low: Option<u32>,
+        high: Option<u32>,
+    },
     RequestRegisterNames,
+    /// `-thread-info`: every thread with its state and top frame
+    RequestThreads,
+    /// Back to picking the language from each frame, undoing a `set language`
+    SetLanguageAuto,
+    /// `show language`, for frames whose file extension doesn't tell
+    RequestLanguage,
+    /// Python pretty-printers for STL/Rust std containers
+    EnablePrettyPrinting,
     RequestRegisters,
     RequestDisasm,
+    /// `-symbol-info-functions`: functions whose name matches `regex`, at
+    /// most `max` of them.
+    SearchSymbols {
+        regex: String,
+        max: usize,
+    },
+    /// Disassemble 128 bytes from an address, symbol or expression (`main`, `$sp`).
+    DisassembleAt {
+        start: String,
+    },
+    /// Disassemble `start..end` (exclusive), for a pinned focus region.
+    DisassembleRange {
+        start: u64,
+        end: u64,
+    },
+    /// `-data-disassemble -a addr -- 1`: source lines of the whole function
+    /// containing `addr`, to annotate the plain disassembly.
+    RequestAsmSourceLines {
+        addr: u64,
+    },
     Evaluate(String),
+    /// `-environment-directory` with no argument: just report the source path.
+    RequestSourceDirs,
+    AddSourceDir(String),
+    /// `set substitute-path`: read sources under `from` (build-time prefix) from `to`.
+    SubstitutePath {
+        from: String,
+        to: String,
+    },
+    /// `unset substitute-path {from}`
+    RemoveSubstitutePath(String),
+    /// `target remote {addr}`, e.g. a gdbserver at `host:port`
+    ConnectRemote(String),
+    /// `attach {pid}` to a running process
+    Attach(u32),
+    /// `core-file {path}`: inspect a crash after the fact
+    LoadCore(String),
+    SetRegister {
+        name: String,
+        value: String,
+    },
+    /// `len` bytes from an address or expression (`&buf`, `$sp`).
+    ReadMemory {
+        addr: String,
+        len: u32,
+    },
+    WriteMemory {
+        addr: u64,
+        bytes: Vec<u8>,
+    },
+    /// `info proc mappings`: the process's memory regions. Console output
+    /// only, so the session collects it until the result.
+    RequestMappings,
+    /// One of the user's stop commands, sent as typed after each stop. Its
+    /// console output is collected for the "On stop" tab.
+    StopHook(String),
 
     Raw(String),
 }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ForkMode {
+    #[default]
+    Parent,
+    Child,
+}
+
+impl ForkMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ForkMode::Parent => "parent",
+            ForkMode::Child => "child",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WatchKind {
+    Write,
+    Read,
+    Access,
+}
+
+/// Full `-break-insert` request: any location GDB accepts plus the options a
+/// `.gdb` script can express.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BreakpointSpec {
+    pub location: String,
+    pub temporary: bool,
+    pub enabled: bool,
+    pub condition: Option<String>,
+    pub ignore_count: u32,
+    /// `-f`: create a pending breakpoint if the location isn't found yet.
+    pub pending: bool,
+}
+
+impl BreakpointSpec {
+    pub fn new(location: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// The insert request for this spec. `pending` is the "pending if not
+    /// found" option: when set, every breakpoint gets `-f`, whatever the
+    /// spec itself asked for.
+    pub fn insert(mut self, pending: bool) -> Command {
+        self.pending |= pending;
+        Command::InsertBreakpoint(self)
+    }
+}
@@ -0,0 +1,174 @@
+use crate::state::{Frame, Variable};
+
+/// The comparisons the builder offers.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Op {
+    #[default]
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    pub const ALL: [Op; 6] = [Op::Eq, Op::Ne, Op::Lt, Op::Le, Op::Gt, Op::Ge];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+}
+
+/// One `name op value` comparison.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Clause {
+    pub name: String,
+    pub op: Op,
+    pub value: String,
+}
+
+impl Clause {
+    /// `len > 10`; None until both sides are filled in.
+    pub fn expr(&self) -> Option<String> {
+        let (name, value) = (self.name.trim(), self.value.trim());
+        if name.is_empty() || value.is_empty() {
+            return None;
+        }
+        Some(format!("{name} {} {value}", self.op.as_str()))
+    }
+}
+
+/// `existing` with `clause` ANDed on. An `||` in `existing` gets
+/// parenthesized so the new clause applies to all of it.
+pub fn and_clause(existing: &str, clause: &Clause) -> String {
+    let existing = existing.trim();
+    let Some(expr) = clause.expr() else {
+        return existing.to_owned();
+    };
+    match existing {
+        "" => expr,
+        e if e.contains("||") => format!("({e}) && {expr}"),
+        e => format!("{e} && {expr}"),
+    }
+}
+
+/// Names to compare on: `frame`'s arguments, then the locals not shadowing
+/// one.
+pub fn candidate_names(frame: Option<&Frame>, locals: &[Variable]) -> Vec<String> {
+    let mut names: Vec<String> = frame
+        .map(|f| f.args.iter().map(|a| a.name.clone()).collect())
+        .unwrap_or_default();
+    for var in locals {
+        if !names.contains(&var.name) {
+            names.push(var.name.clone());
+        }
+    }
+    names
+}
+
+// ─── Editor ───────────────────────────────────────────────────────────────────
+
+/// A breakpoint's condition being edited, with the clause being built.
+pub struct CondEdit {
+    pub id: u32,
+    pub text: String,
+    pub clause: Clause,
+}
+
+impl CondEdit {
+    pub fn new(id: u32, condition: Option<&str>) -> Self {
+        Self {
+            id,
+            text: condition.unwrap_or_default().to_owned(),
+            clause: Clause::default(),
+        }
+    }
+
+    /// Moves the built clause into the condition text.
+    pub fn add_clause(&mut self) {
+        self.text = and_clause(&self.text, &self.clause);
+        self.clause.value.clear();
+    }
+
+    /// What to send: None clears the condition.
+    pub fn condition(&self) -> Option<String> {
+        let text = self.text.trim();
+        (!text.is_empty()).then(|| text.to_owned())
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str) -> Variable {
+        Variable {
+            name: name.into(),
+            value: "0".into(),
+            type_: "int".into(),
+        }
+    }
+
+    #[test]
+    fn test_build_condition() {
+        let mut edit = CondEdit::new(2, None);
+        edit.clause = Clause {
+            name: "len".into(),
+            op: Op::Gt,
+            value: "10".into(),
+        };
+        edit.add_clause();
+        assert_eq!(edit.text, "len > 10");
+        assert!(edit.clause.value.is_empty());
+
+        // Incomplete clauses add nothing
+        edit.clause.name = "buf".into();
+        edit.add_clause();
+        assert_eq!(edit.text, "len > 10");
+
+        edit.clause.op = Op::Ne;
+        edit.clause.value = "0".into();
+        edit.add_clause();
+        assert_eq!(edit.condition().as_deref(), Some("len > 10 && buf != 0"));
+
+        // An existing `||` keeps its meaning
+        let mut edit = CondEdit::new(3, Some("i == 1 || i == 2"));
+        edit.clause = Clause {
+            name: "n".into(),
+            op: Op::Le,
+            value: "3".into(),
+        };
+        edit.add_clause();
+        assert_eq!(edit.text, "(i == 1 || i == 2) && n <= 3");
+
+        assert_eq!(CondEdit::new(4, Some("  ")).condition(), None);
+    }
+
+    #[test]
+    fn test_candidate_names() {
+        let frame = Frame {
+            level: 0,
+            args: vec![var("buf"), var("len")],
+            addr: 0x401000,
+            function: "parse".into(),
+            file: None,
+            line: None,
+        };
+        let locals = [var("i"), var("len")];
+        assert_eq!(
+            candidate_names(Some(&frame), &locals),
+            vec!["buf", "len", "i"]
+        );
+        assert_eq!(candidate_names(None, &locals), vec!["i", "len"]);
+    }
+}
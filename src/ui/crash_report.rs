@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use super::command::Command;
+use crate::state::{Frame, Register, StateEvent, Variable};
+
+/// Signals worth a crash report; SIGINT/SIGTRAP and friends are just stops.
+pub fn is_fatal(signal: &str) -> bool {
+    matches!(
+        signal,
+        "SIGSEGV" | "SIGBUS" | "SIGFPE" | "SIGILL" | "SIGABRT" | "SIGSYS"
+    )
+}
+
+// ─── Report ───────────────────────────────────────────────────────────────────
+
+/// Snapshot taken on a fatal signal: full backtrace, frame 0 locals and
+/// registers. Filled from the replies to `requests()`; `render` once complete.
+pub struct CrashReport {
+    pub signal: String,
+    executable: Option<String>,
+    stack: Option<Vec<Frame>>,
+    locals: Option<Vec<Variable>>,
+    register_names: Option<Vec<String>>,
+    registers: Option<Vec<Register>>,
+}
+
+impl CrashReport {
+    pub fn new(signal: impl Into<String>, executable: Option<String>) -> Self {
+        Self {
+            signal: signal.into(),
+            executable,
+            stack: None,
+            locals: None,
+            register_names: None,
+            registers: None,
+        }
+    }
+
+    pub fn requests() -> Vec<Command> {
+        vec![
+            Command::RequestStack {
+                low: None,
+                high: None,
+            },
+            Command::RequestLocals,
+            Command::RequestRegisterNames,
+            Command::RequestRegisters,
+        ]
+    }
+
+    /// Keeps the first reply of each kind. True if the event was one of ours.
+    pub fn on_event(&mut self, event: &StateEvent) -> bool {
+        match event {
+            StateEvent::StackUpdated { frames } if self.stack.is_none() => {
+                self.stack = Some(frames.clone());
+            }
+            StateEvent::LocalsUpdated { vars } if self.locals.is_none() => {
+                self.locals = Some(vars.clone());
+            }
+            StateEvent::RegisterNamesReceived { names } if self.register_names.is_none() => {
+                self.register_names = Some(names.clone());
+            }
+            StateEvent::RegistersUpdated { registers } if self.registers.is_none() => {
+                self.registers = Some(registers.clone());
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.stack.is_some()
+            && self.locals.is_some()
+            && self.register_names.is_some()
+            && self.registers.is_some()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Crash report: {}", self.signal);
+        if let Some(exe) = &self.executable {
+            let _ = writeln!(out, "Executable: {exe}");
+        }
+
+        let _ = writeln!(out, "\nBacktrace:");
+        for frame in self.stack.iter().flatten() {
+            let location = match (&frame.file, frame.line) {
+                (Some(file), Some(line)) => format!("{file}:{line}"),
+                _ => "??".into(),
+            };
+            let _ = writeln!(
+                out,
+                "  #{:<3} 0x{:016x} in {} at {location}",
+                frame.level, frame.addr, frame.function
+            );
+        }
+
+        let _ = writeln!(out, "\nLocals (frame 0):");
+        for var in self.locals.iter().flatten() {
+            let _ = writeln!(out, "  {} {} = {}", var.type_, var.name, var.value);
+        }
+
+        let _ = writeln!(out, "\nRegisters:");
+        let names = self.register_names.as_deref().unwrap_or(&[]);
+        for reg in self.registers.iter().flatten() {
+            let name = names
+                .get(reg.number as usize)
+                .map(String::as_str)
+                .filter(|n| !n.is_empty())
+                .unwrap_or("?");
+            let _ = writeln!(out, "  {name:<8} {}", reg.value);
+        }
+        out
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(level: u32, function: &str, line: Option<u32>) -> Frame {
+        Frame {
+            level,
+            args: vec![],
+            addr: 0x401000 + level as u64,
+            function: function.into(),
+            file: line.map(|_| "/src/main.c".into()),
+            line,
+        }
+    }
+
+    #[test]
+    fn test_is_fatal() {
+        assert!(is_fatal("SIGSEGV"));
+        assert!(is_fatal("SIGABRT"));
+        assert!(!is_fatal("SIGINT"));
+        assert!(!is_fatal("SIGTRAP"));
+    }
+
+    #[test]
+    fn test_crash_report_assembly() {
+        let mut report = CrashReport::new("SIGSEGV", Some("./app".into()));
+        assert!(!report.on_event(&StateEvent::ProgramStarted));
+
+        report.on_event(&StateEvent::StackUpdated {
+            frames: vec![frame(0, "deref", Some(12)), frame(1, "main", Some(30))],
+        });
+        report.on_event(&StateEvent::LocalsUpdated {
+            vars: vec![Variable {
+                name: "p".into(),
+                value: "0x0".into(),
+                type_: "int *".into(),
+            }],
+        });
+        report.on_event(&StateEvent::RegisterNamesReceived {
+            names: vec!["rax".into(), "rbx".into()],
+        });
+        assert!(!report.is_complete());
+        report.on_event(&StateEvent::RegistersUpdated {
+            registers: vec![Register {
+                number: 1,
+                name: String::new(),
+                value: "0x2a".into(),
+            }],
+        });
+        assert!(report.is_complete());
+
+        // A later refresh doesn't overwrite the snapshot
+        report.on_event(&StateEvent::LocalsUpdated { vars: vec![] });
+
+        let text = report.render();
+        assert!(text.starts_with("Crash report: SIGSEGV\nExecutable: ./app\n"));
+        assert!(text.contains("#0   0x0000000000401000 in deref at /src/main.c:12"));
+        assert!(text.contains("#1   0x0000000000401001 in main at /src/main.c:30"));
+        assert!(text.contains("int * p = 0x0"));
+        assert!(text.contains("rbx      0x2a"));
+    }
+}
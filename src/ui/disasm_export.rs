@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::state::AsmLine;
+
+// ─── Disassembly export ───────────────────────────────────────────────────────
+
+/// The Data tab's disassembly as plain text for diffing: a header per
+/// function, then address, offset, opcodes and instruction. `source_lines`
+/// adds the source line where each line's block starts.
+pub fn format_disasm(lines: &[AsmLine], source_lines: &HashMap<u64, u32>) -> String {
+    let mut out = String::new();
+    let mut function = None;
+    let mut prev_line = None;
+    for asm in lines {
+        if function != Some(&asm.function) {
+            function = Some(&asm.function);
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let name = asm.function.as_deref().unwrap_or("??");
+            let _ = writeln!(out, "<{name}>:");
+            prev_line = None;
+        }
+        let src_line = source_lines.get(&asm.addr);
+        let gutter = match src_line {
+            Some(l) if src_line != prev_line => format!("{l:>5}"),
+            _ => String::new(),
+        };
+        prev_line = src_line;
+        let _ = writeln!(
+            out,
+            "{gutter:>5}  0x{:016x} <+{}>  {:<24} {}",
+            asm.addr,
+            asm.offset,
+            asm.opcodes.as_deref().unwrap_or_default(),
+            asm.inst
+        );
+    }
+    out
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asm(addr: u64, function: &str, offset: u32, opcodes: &str, inst: &str) -> AsmLine {
+        AsmLine {
+            addr,
+            function: Some(function.into()),
+            offset,
+            opcodes: Some(opcodes.into()),
+            inst: inst.into(),
+            current: false,
+        }
+    }
+
+    #[test]
+    fn test_format_disasm() {
+        let lines = [
+            asm(0x401126, "main", 0, "55", "push   %rbp"),
+            asm(0x401127, "main", 1, "48 89 e5", "mov    %rsp,%rbp"),
+            asm(0x40112a, "main", 4, "b8 00 00 00 00", "mov    $0x0,%eax"),
+            asm(0x401130, "helper", 0, "c3", "ret"),
+        ];
+        let source = HashMap::from([(0x401126, 4), (0x401127, 4), (0x40112a, 5)]);
+        let expected = [
+            "<main>:",
+            "    4  0x0000000000401126 <+0>  55                       push   %rbp",
+            "       0x0000000000401127 <+1>  48 89 e5                 mov    %rsp,%rbp",
+            "    5  0x000000000040112a <+4>  b8 00 00 00 00           mov    $0x0,%eax",
+            "",
+            "<helper>:",
+            "       0x0000000000401130 <+0>  c3                       ret",
+            "",
+        ];
+        assert_eq!(format_disasm(&lines, &source), expected.join("\n"));
+        assert_eq!(format_disasm(&[], &HashMap::new()), "");
+    }
+}
@@ -0,0 +1,92 @@
+use std::process::{Command, Stdio};
+
+/// The editor command until one is set.
+pub const DEFAULT_COMMAND: &str = "code --goto {file}:{line}";
+
+/// `template` as program and arguments: `{file}` and `{line}` filled in,
+/// `$VAR` words taken from `env`. Split before filling in, so a path with
+/// spaces stays one argument. Without a `{file}`, the file goes last.
+pub fn expand(
+    template: &str,
+    file: &str,
+    line: u32,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<String>, String> {
+    let mut argv = vec![];
+    for word in template.split_whitespace() {
+        match word.strip_prefix('$') {
+            Some(var) => {
+                let value = env(var)
+                    .filter(|v| !v.trim().is_empty())
+                    .ok_or_else(|| format!("${var} isn't set"))?;
+                // EDITOR="code --wait" is a program and its flags
+                argv.extend(value.split_whitespace().map(str::to_owned));
+            }
+            None => argv.push(
+                word.replace("{file}", file)
+                    .replace("{line}", &line.to_string()),
+            ),
+        }
+    }
+    if argv.is_empty() {
+        return Err("no editor command set".into());
+    }
+    if !template.contains("{file}") {
+        argv.push(file.to_owned());
+    }
+    Ok(argv)
+}
+
+/// Starts the editor on `file:line` without waiting for it.
+pub fn open(template: &str, file: &str, line: u32) -> Result<(), String> {
+    let argv = expand(template, file, line, |var| std::env::var(var).ok())?;
+    let mut child = Command::new(&argv[0])
+        .args(&argv[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{}: {e}", argv[0]))?;
+    // Reaped off the UI thread: the editor may stay open for hours
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(var: &str) -> Option<String> {
+        match var {
+            "EDITOR" => Some("nvim -R".into()),
+            "BLANK" => Some(" ".into()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_editor_command() {
+        assert_eq!(
+            expand(DEFAULT_COMMAND, "/my src/main.c", 42, env).unwrap(),
+            ["code", "--goto", "/my src/main.c:42"]
+        );
+        assert_eq!(
+            expand("$EDITOR +{line} {file}", "main.c", 7, env).unwrap(),
+            ["nvim", "-R", "+7", "main.c"]
+        );
+        // No {file}: appended
+        assert_eq!(
+            expand("subl", "main.c", 7, env).unwrap(),
+            ["subl", "main.c"]
+        );
+
+        assert_eq!(
+            expand("$VISUAL {file}", "main.c", 7, env),
+            Err("$VISUAL isn't set".into())
+        );
+        assert!(expand("$BLANK {file}", "main.c", 7, env).is_err());
+        assert!(expand("  ", "main.c", 7, env).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+/// `errno` without libc debug info: the macro isn't there, and GDB won't call
+/// `__errno_location` until told its return type.
+pub const ERRNO_EXPR: &str = "*((int *(*)(void))__errno_location)()";
+
+/// Linux's errno values, `errno-base.h` and the common part of `errno.h`.
+const NAMES: [(i32, &str); 45] = [
+    (1, "EPERM"),
+    (2, "ENOENT"),
+    (3, "ESRCH"),
+    (4, "EINTR"),
+    (5, "EIO"),
+    (6, "ENXIO"),
+    (7, "E2BIG"),
+    (8, "ENOEXEC"),
+    (9, "EBADF"),
+    (10, "ECHILD"),
+    (11, "EAGAIN"),
+    (12, "ENOMEM"),
+    (13, "EACCES"),
+    (14, "EFAULT"),
+    (15, "ENOTBLK"),
+    (16, "EBUSY"),
+    (17, "EEXIST"),
+    (18, "EXDEV"),
+    (19, "ENODEV"),
+    (20, "ENOTDIR"),
+    (21, "EISDIR"),
+    (22, "EINVAL"),
+    (23, "ENFILE"),
+    (24, "EMFILE"),
+    (25, "ENOTTY"),
+    (26, "ETXTBSY"),
+    (27, "EFBIG"),
+    (28, "ENOSPC"),
+    (29, "ESPIPE"),
+    (30, "EROFS"),
+    (31, "EMLINK"),
+    (32, "EPIPE"),
+    (33, "EDOM"),
+    (34, "ERANGE"),
+    (35, "EDEADLK"),
+    (36, "ENAMETOOLONG"),
+    (38, "ENOSYS"),
+    (39, "ENOTEMPTY"),
+    (40, "ELOOP"),
+    (75, "EOVERFLOW"),
+    (88, "ENOTSOCK"),
+    (98, "EADDRINUSE"),
+    (104, "ECONNRESET"),
+    (110, "ETIMEDOUT"),
+    (111, "ECONNREFUSED"),
+];
+
+pub fn errno_name(errno: i32) -> Option<&'static str> {
+    NAMES
+        .iter()
+        .find(|(n, _)| *n == errno)
+        .map(|(_, name)| *name)
+}
+
+/// The status bar text for an evaluated errno: `ENOENT (2)`, or just the
+/// number when it isn't in the table. None if GDB's value isn't a number.
+pub fn describe(value: &str) -> Option<String> {
+    let errno: i32 = value.trim().parse().ok()?;
+    Some(match errno_name(errno) {
+        Some(name) => format!("{name} ({errno})"),
+        None => errno.to_string(),
+    })
+}
+
+/// The return value of the syscall a `catch syscall` stopped on, read from
+/// `register`.
+pub struct SyscallResult {
+    pub name: String,
+    pub register: String,
+    pub value: Option<Result<String, String>>,
+}
+
+/// The register a syscall's return value comes back in, from
+/// `-data-list-register-names`, as a GDB expression.
+pub fn syscall_return_register(names: &[String]) -> Option<String> {
+    ["rax", "eax", "x0", "r0"]
+        .into_iter()
+        .find(|r| names.iter().any(|n| n == r))
+        .map(|r| format!("${r}"))
+}
+
+/// A syscall's raw return value: the kernel's `-errno` by name, anything
+/// else as is. None if GDB's value isn't a number.
+pub fn describe_syscall(value: &str) -> Option<String> {
+    let ret: i64 = value.trim().parse().ok()?;
+    let name = (-4095..0)
+        .contains(&ret)
+        .then(|| errno_name(-ret as i32))
+        .flatten();
+    Some(match name {
+        Some(name) => format!("{ret} ({name})"),
+        None => ret.to_string(),
+    })
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errno_names() {
+        assert_eq!(errno_name(2), Some("ENOENT"));
+        assert_eq!(errno_name(22), Some("EINVAL"));
+        assert_eq!(errno_name(0), None);
+        assert_eq!(describe("2"), Some("ENOENT (2)".into()));
+        assert_eq!(describe("0"), Some("0".into()));
+        assert_eq!(describe("4095"), Some("4095".into()));
+        assert_eq!(describe("<error: Cannot access memory>"), None);
+    }
+
+    #[test]
+    fn test_syscall_return() {
+        let names = |n: &[&str]| n.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            syscall_return_register(&names(&["rax", "rbx", "eax"])).as_deref(),
+            Some("$rax")
+        );
+        assert_eq!(
+            syscall_return_register(&names(&["x0", "x1", "sp"])).as_deref(),
+            Some("$x0")
+        );
+        assert_eq!(syscall_return_register(&names(&["pc"])), None);
+
+        assert_eq!(describe_syscall("-9"), Some("-9 (EBADF)".into()));
+        assert_eq!(describe_syscall("12"), Some("12".into()));
+        assert_eq!(
+            describe_syscall("-140737488347136"),
+            Some("-140737488347136".into())
+        );
+        assert_eq!(describe_syscall("void"), None);
+    }
+}
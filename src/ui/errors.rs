@@ -0,0 +1,82 @@
+// ─── Error list ───────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorEntry {
+    pub msg: String,
+    /// MI command that got the `^error`; None for errors without a token
+    pub command: Option<String>,
+    pub count: u32,
+}
+
+/// GDB errors since the last Run, one entry per distinct (message, command).
+/// A breakpoint on a missing file retried ten times is one row with ×10.
+#[derive(Default)]
+pub struct ErrorList {
+    pub entries: Vec<ErrorEntry>,
+}
+
+impl ErrorList {
+    pub fn push(&mut self, msg: &str, command: Option<&str>) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.msg == msg && e.command.as_deref() == command)
+        {
+            Some(entry) => entry.count += 1,
+            None => self.entries.push(ErrorEntry {
+                msg: msg.to_owned(),
+                command: command.map(str::to_owned),
+                count: 1,
+            }),
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.entries.iter().map(|e| e.count).sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_aggregation() {
+        let mut errors = ErrorList::default();
+        let missing = "No source file named foo.c.";
+        errors.push(missing, Some("-break-insert foo.c:3"));
+        errors.push(
+            "No symbol \"x\" in current context.",
+            Some("-data-evaluate-expression x"),
+        );
+        errors.push(missing, Some("-break-insert foo.c:3"));
+        errors.push(missing, Some("-break-insert foo.c:9"));
+        errors.push("Error escribiendo a GDB: broken pipe", None);
+        errors.push("Error escribiendo a GDB: broken pipe", None);
+
+        let rows: Vec<_> = errors
+            .entries
+            .iter()
+            .map(|e| (e.command.as_deref(), e.count))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("-break-insert foo.c:3"), 2),
+                (Some("-data-evaluate-expression x"), 1),
+                (Some("-break-insert foo.c:9"), 1),
+                (None, 2),
+            ]
+        );
+        assert_eq!(errors.total(), 6);
+
+        errors.clear();
+        assert_eq!(errors.total(), 0);
+    }
+}
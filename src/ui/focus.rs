@@ -0,0 +1,90 @@
+use crate::state::AsmLine;
+
+/// Longest range worth pinning; GDB takes a while over whole functions.
+const MAX_LEN: u64 = 0x10000;
+
+// ─── Focus region ─────────────────────────────────────────────────────────────
+
+/// An address range the Data tab keeps disassembled while stepping through
+/// it, instead of following `$pc`. `end` is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusRegion {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl FocusRegion {
+    /// From the start and end fields: hex with or without `0x`, or `+len`
+    /// for the end. None unless it's a non-empty range under `MAX_LEN`.
+    pub fn parse(start: &str, end: &str) -> Option<Self> {
+        let hex = |s: &str| {
+            let s = s.trim();
+            let digits = s
+                .strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .unwrap_or(s);
+            u64::from_str_radix(digits, 16).ok()
+        };
+        let start = hex(start)?;
+        let end = match end.trim().strip_prefix('+') {
+            Some(len) => start.checked_add(len.trim().parse().ok()?)?,
+            None => hex(end)?,
+        };
+        (end > start && end - start <= MAX_LEN).then_some(Self { start, end })
+    }
+
+    pub fn contains(&self, addr: u64) -> bool {
+        (self.start..self.end).contains(&addr)
+    }
+
+    /// Whether `disasm` is the answer for this region rather than a `$pc`
+    /// view that was already on its way: GDB starts at the address asked for.
+    pub fn is_listing(&self, disasm: &[AsmLine]) -> bool {
+        disasm.first().is_some_and(|line| line.addr == self.start)
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_region() {
+        let region = FocusRegion::parse("0x401000", "401040").unwrap();
+        assert!(region.contains(0x401000));
+        assert!(region.contains(0x40103f));
+        assert!(!region.contains(0x401040));
+        assert!(!region.contains(0x400fff));
+
+        assert_eq!(
+            FocusRegion::parse("0x401000", "+64"),
+            Some(FocusRegion {
+                start: 0x401000,
+                end: 0x401040
+            })
+        );
+        assert_eq!(FocusRegion::parse("0x401040", "0x401000"), None);
+        assert_eq!(FocusRegion::parse("0x401000", "0x401000"), None);
+        assert_eq!(FocusRegion::parse("main", "+64"), None);
+        assert_eq!(FocusRegion::parse("0x0", "0x100000"), None);
+    }
+
+    #[test]
+    fn test_is_listing() {
+        let line = |addr| AsmLine {
+            addr,
+            function: None,
+            offset: 0,
+            opcodes: None,
+            inst: "nop".into(),
+            current: false,
+        };
+        let region = FocusRegion::parse("0x401000", "+64").unwrap();
+        assert!(region.is_listing(&[line(0x401000), line(0x401001)]));
+        // The $pc view, even inside the region
+        assert!(!region.is_listing(&[line(0x401010), line(0x401011)]));
+        assert!(!region.is_listing(&[]));
+    }
+}
@@ -0,0 +1,110 @@
+use super::command::Command;
+
+// ─── Driver ───────────────────────────────────────────────────────────────────
+
+/// Evaluates one expression in every stack frame, one frame at a time:
+/// `-stack-select-frame N` + `Evaluate`, and the next frame only once the
+/// previous result is back. The originally selected frame is restored at the end.
+pub struct FrameEval {
+    pub expr: String,
+    levels: Vec<u32>,
+    original: u32,
+    /// (frame level, value), in stack order.
+    pub results: Vec<(u32, Result<String, String>)>,
+}
+
+impl FrameEval {
+    pub fn new(expr: impl Into<String>, levels: Vec<u32>, original: u32) -> Self {
+        Self {
+            expr: expr.into(),
+            levels,
+            original,
+            results: vec![],
+        }
+    }
+
+    /// Commands for the first frame.
+    pub fn start(&self) -> Vec<Command> {
+        self.eval_next()
+    }
+
+    /// Whether an `Evaluate(expr)` result belongs to this run.
+    pub fn awaits(&self, expr: &str) -> bool {
+        expr == self.expr && !self.is_complete()
+    }
+
+    /// Records the result for the current frame and returns what to send next:
+    /// the following frame, or the restore once every frame is done.
+    pub fn on_result(&mut self, value: Result<String, String>) -> Vec<Command> {
+        let Some(&level) = self.levels.get(self.results.len()) else {
+            return vec![];
+        };
+        self.results.push((level, value));
+        if self.is_complete() {
+            vec![Command::SelectFrame(self.original)]
+        } else {
+            self.eval_next()
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.results.len() == self.levels.len()
+    }
+
+    fn eval_next(&self) -> Vec<Command> {
+        match self.levels.get(self.results.len()) {
+            Some(&level) => vec![
+                Command::SelectFrame(level),
+                Command::Evaluate(self.expr.clone()),
+            ],
+            None => vec![],
+        }
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_eval_driver() {
+        let mut eval = FrameEval::new("err", vec![0, 1, 2], 0);
+        assert_eq!(
+            eval.start(),
+            vec![Command::SelectFrame(0), Command::Evaluate("err".into())]
+        );
+
+        // Results for other expressions (logpoints, watches) are not ours
+        assert!(!eval.awaits("other"));
+        assert!(eval.awaits("err"));
+
+        assert_eq!(
+            eval.on_result(Ok("0x0".into())),
+            vec![Command::SelectFrame(1), Command::Evaluate("err".into())]
+        );
+        assert_eq!(
+            eval.on_result(Err("No symbol \"err\" in current context.".into())),
+            vec![Command::SelectFrame(2), Command::Evaluate("err".into())]
+        );
+        assert!(!eval.is_complete());
+        assert_eq!(
+            eval.on_result(Ok("0x4052a0".into())),
+            vec![Command::SelectFrame(0)]
+        );
+        assert!(eval.is_complete());
+        assert!(!eval.awaits("err"));
+
+        let levels: Vec<_> = eval.results.iter().map(|(l, _)| *l).collect();
+        assert_eq!(levels, vec![0, 1, 2]);
+        assert!(eval.results[1].1.is_err());
+    }
+
+    #[test]
+    fn test_frame_eval_empty_stack() {
+        let eval = FrameEval::new("x", vec![], 0);
+        assert!(eval.start().is_empty());
+        assert!(eval.is_complete());
+    }
+}
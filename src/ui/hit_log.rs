@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::state::{PauseState, StopReason};
+
+// ─── Entry ────────────────────────────────────────────────────────────────────
+
+/// One breakpoint or watchpoint stop, for the Hit Log section.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HitEntry {
+    /// Time since the session started.
+    pub at: Duration,
+    pub id: u32,
+    pub thread_id: u32,
+    pub function: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    /// Watchpoints only: `expr: old → new` (or just the value for reads).
+    pub value: Option<String>,
+}
+
+impl HitEntry {
+    /// Entry for a stop caused by a breakpoint or watchpoint; None for steps,
+    /// signals and interrupts.
+    pub fn from_pause(pause: &PauseState, at: Duration) -> Option<Self> {
+        let (id, value) = match &pause.stop_reason {
+            StopReason::BreakpointHit(id) => (*id, None),
+            StopReason::WatchpointTrigger { id, expr, old, new } => {
+                let value = match (old, new) {
+                    (Some(old), Some(new)) => format!("{expr}: {old} → {new}"),
+                    (None, Some(v)) | (Some(v), None) => format!("{expr} = {v}"),
+                    (None, None) => expr.clone(),
+                };
+                (*id, Some(value))
+            }
+            _ => return None,
+        };
+        Some(HitEntry {
+            at,
+            id,
+            thread_id: pause.thread_id,
+            function: pause.frame.function.clone(),
+            file: pause.frame.file.clone(),
+            line: pause.frame.line,
+            value,
+        })
+    }
+
+    /// `main.c:12` when the location has a source line, else the function.
+    pub fn location(&self) -> String {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => {
+                let short = file.rsplit(['/', '\\']).next().unwrap_or(file);
+                format!("{short}:{line}")
+            }
+            _ => self.function.clone(),
+        }
+    }
+
+    /// `+mm:ss.mmm`
+    pub fn timestamp(&self) -> String {
+        let ms = self.at.as_millis();
+        format!("+{:02}:{:02}.{:03}", ms / 60_000, ms / 1000 % 60, ms % 1000)
+    }
+}
+
+// ─── Visit counts ─────────────────────────────────────────────────────────────
+
+/// Breakpoint hits this session, by breakpoint id. Unlike GDB's `times`
+/// these survive a restart; they only go when the user resets them.
+#[derive(Default)]
+pub struct VisitCounts(HashMap<u32, u32>);
+
+impl VisitCounts {
+    pub fn record(&mut self, pause: &PauseState) {
+        if let StopReason::BreakpointHit(id) = pause.stop_reason {
+            *self.0.entry(id).or_default() += 1;
+        }
+    }
+
+    pub fn get(&self, id: u32) -> u32 {
+        self.0.get(&id).copied().unwrap_or_default()
+    }
+
+    pub fn reset(&mut self, id: u32) {
+        self.0.remove(&id);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Frame;
+
+    fn pause(stop_reason: StopReason) -> PauseState {
+        let frame = Frame {
+            level: 0,
+            args: vec![],
+            addr: 0x401136,
+            function: "main".into(),
+            file: Some("/src/main.c".into()),
+            line: Some(12),
+        };
+        PauseState {
+            thread_id: 2,
+            frame: frame.clone(),
+            stack: vec![frame],
+            stop_reason,
+        }
+    }
+
+    #[test]
+    fn test_entry_from_breakpoint() {
+        let at = Duration::from_millis(75_250);
+        let entry = HitEntry::from_pause(&pause(StopReason::BreakpointHit(1)), at).unwrap();
+        assert_eq!(entry.id, 1);
+        assert_eq!(entry.thread_id, 2);
+        assert_eq!(entry.location(), "main.c:12");
+        assert_eq!(entry.timestamp(), "+01:15.250");
+        assert_eq!(entry.value, None);
+    }
+
+    #[test]
+    fn test_entry_from_watchpoint() {
+        let reason = StopReason::WatchpointTrigger {
+            id: 3,
+            expr: "count".into(),
+            old: Some("1".into()),
+            new: Some("2".into()),
+        };
+        let entry = HitEntry::from_pause(&pause(reason), Duration::ZERO).unwrap();
+        assert_eq!(entry.id, 3);
+        assert_eq!(entry.value.as_deref(), Some("count: 1 → 2"));
+    }
+
+    #[test]
+    fn test_visit_counts() {
+        let mut visits = VisitCounts::default();
+        visits.record(&pause(StopReason::BreakpointHit(1)));
+        visits.record(&pause(StopReason::BreakpointHit(1)));
+        visits.record(&pause(StopReason::BreakpointHit(2)));
+        visits.record(&pause(StopReason::EndStepping));
+        assert_eq!((visits.get(1), visits.get(2), visits.get(3)), (2, 1, 0));
+
+        visits.reset(1);
+        assert_eq!((visits.get(1), visits.get(2)), (0, 1));
+        visits.clear();
+        assert_eq!(visits.get(2), 0);
+    }
+
+    #[test]
+    fn test_steps_are_not_logged() {
+        assert!(HitEntry::from_pause(&pause(StopReason::EndStepping), Duration::ZERO).is_none());
+        let signal = StopReason::Signal("SIGSEGV".into());
+        assert!(HitEntry::from_pause(&pause(signal), Duration::ZERO).is_none());
+    }
+}
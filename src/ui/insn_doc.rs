@@ -0,0 +1,136 @@
+// ─── Architecture ─────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Arch {
+    X86,
+    Arm,
+}
+
+impl Arch {
+    /// Guesses the target from `-data-list-register-names`.
+    pub fn from_register_names(names: &[String]) -> Option<Arch> {
+        names.iter().find_map(|n| match n.as_str() {
+            "rax" | "eax" | "rip" | "eip" => Some(Arch::X86),
+            "x0" | "r0" | "cpsr" => Some(Arch::Arm),
+            _ => None,
+        })
+    }
+}
+
+// ─── Mnemonic table ───────────────────────────────────────────────────────────
+
+const X86: &[(&str, &str)] = &[
+    ("mov", "Copy source operand to destination"),
+    ("movzx", "Move with zero-extension"),
+    ("movsx", "Move with sign-extension"),
+    ("lea", "Load effective address (no memory access)"),
+    ("push", "Push operand onto the stack"),
+    ("pop", "Pop top of stack into operand"),
+    ("add", "Integer addition"),
+    ("sub", "Integer subtraction"),
+    ("imul", "Signed multiply"),
+    ("idiv", "Signed divide"),
+    ("inc", "Increment by 1"),
+    ("dec", "Decrement by 1"),
+    ("neg", "Two's complement negation"),
+    ("and", "Bitwise AND"),
+    ("or", "Bitwise OR"),
+    ("xor", "Bitwise exclusive OR"),
+    ("not", "Bitwise NOT"),
+    ("shl", "Shift left"),
+    ("shr", "Logical shift right"),
+    ("sar", "Arithmetic shift right"),
+    ("cmp", "Compare (subtract and set flags, discard result)"),
+    ("test", "Bitwise AND, set flags, discard result"),
+    ("jmp", "Unconditional jump"),
+    ("je", "Jump if equal (ZF=1)"),
+    ("jne", "Jump if not equal (ZF=0)"),
+    ("jg", "Jump if greater (signed)"),
+    ("jge", "Jump if greater or equal (signed)"),
+    ("jl", "Jump if less (signed)"),
+    ("jle", "Jump if less or equal (signed)"),
+    ("ja", "Jump if above (unsigned)"),
+    ("jb", "Jump if below (unsigned)"),
+    ("call", "Push return address and jump to procedure"),
+    ("ret", "Pop return address and jump to it"),
+    ("leave", "Restore stack frame (mov rsp,rbp; pop rbp)"),
+    ("nop", "No operation"),
+    ("endbr64", "CET branch target marker (no-op otherwise)"),
+    ("syscall", "Fast system call"),
+    ("cqo", "Sign-extend rax into rdx:rax"),
+    ("cdqe", "Sign-extend eax into rax"),
+];
+
+const ARM: &[(&str, &str)] = &[
+    ("mov", "Copy register or immediate"),
+    ("ldr", "Load register from memory"),
+    ("str", "Store register to memory"),
+    ("ldp", "Load pair of registers"),
+    ("stp", "Store pair of registers"),
+    ("add", "Integer addition"),
+    ("sub", "Integer subtraction"),
+    ("mul", "Multiply"),
+    ("and", "Bitwise AND"),
+    ("orr", "Bitwise OR"),
+    ("eor", "Bitwise exclusive OR"),
+    ("lsl", "Logical shift left"),
+    ("lsr", "Logical shift right"),
+    ("cmp", "Compare (subtract and set flags)"),
+    ("b", "Branch"),
+    ("bl", "Branch with link (call)"),
+    ("br", "Branch to register"),
+    ("blr", "Branch with link to register"),
+    ("cbz", "Compare and branch if zero"),
+    ("cbnz", "Compare and branch if not zero"),
+    ("b.eq", "Branch if equal"),
+    ("b.ne", "Branch if not equal"),
+    ("ret", "Return from subroutine"),
+    ("adrp", "Address of 4KB page, PC-relative"),
+    ("nop", "No operation"),
+    ("svc", "Supervisor call (system call)"),
+];
+
+/// One-line description of `mnemonic`, or None if it isn't in the table.
+/// AT&T size suffixes (`movq`, `addl`) are stripped on x86.
+pub fn describe_insn(arch: Arch, mnemonic: &str) -> Option<&'static str> {
+    let mnemonic = mnemonic.to_ascii_lowercase();
+    let table = match arch {
+        Arch::X86 => X86,
+        Arch::Arm => ARM,
+    };
+    let lookup = |m: &str| table.iter().find(|(k, _)| *k == m).map(|(_, d)| *d);
+
+    lookup(&mnemonic).or_else(|| match arch {
+        Arch::X86 => mnemonic.strip_suffix(['b', 'w', 'l', 'q']).and_then(lookup),
+        Arch::Arm => None,
+    })
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_insn() {
+        assert!(describe_insn(Arch::X86, "mov").is_some());
+        assert_eq!(
+            describe_insn(Arch::X86, "movq"),
+            describe_insn(Arch::X86, "mov")
+        );
+        assert!(describe_insn(Arch::X86, "CALL").is_some());
+        assert!(describe_insn(Arch::X86, "vfmadd231ps").is_none());
+        assert!(describe_insn(Arch::Arm, "ldp").is_some());
+        assert!(describe_insn(Arch::Arm, "lea").is_none());
+    }
+
+    #[test]
+    fn test_arch_from_register_names() {
+        let x86 = vec!["rax".to_string(), "rbx".to_string()];
+        let arm = vec!["x0".to_string(), "x1".to_string()];
+        assert_eq!(Arch::from_register_names(&x86), Some(Arch::X86));
+        assert_eq!(Arch::from_register_names(&arm), Some(Arch::Arm));
+        assert_eq!(Arch::from_register_names(&[]), None);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::command::Command;
+
+// ─── Launch configuration ─────────────────────────────────────────────────────
+
+/// How to run the program: everything GDB needs to know right before `run`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchConfig {
+    pub name: String,
+    /// As typed on a shell command line; GDB runs the program through one.
+    pub args: String,
+    /// (name, value)
+    pub env: Vec<(String, String)>,
+    /// Working directory; empty keeps GDB's.
+    pub cwd: String,
+    /// Redirections, as files; empty for the terminal.
+    pub stdin: String,
+    pub stdout: String,
+}
+
+impl LaunchConfig {
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            ..Self::default()
+        }
+    }
+
+    /// Commands to apply this config, in the order to send them before `run`.
+    /// GDB keeps what `applied`, the previous run's config, set: its variables
+    /// missing here are unset, and without a directory here GDB goes back to
+    /// `start_dir`, the one it started in. Arguments are always set, so
+    /// switching to a config without any clears the previous one's.
+    pub fn commands(&self, applied: Option<&LaunchConfig>, start_dir: &str) -> Vec<Command> {
+        let mut cmds = vec![];
+        if !self.cwd.trim().is_empty() {
+            cmds.push(Command::ChangeDirectory(self.cwd.trim().to_owned()));
+        } else if applied.is_some_and(|a| !a.cwd.trim().is_empty()) && !start_dir.is_empty() {
+            cmds.push(Command::ChangeDirectory(start_dir.to_owned()));
+        }
+        let names = |config: &LaunchConfig| -> Vec<String> {
+            config
+                .env
+                .iter()
+                .map(|(name, _)| name.trim().to_owned())
+                .filter(|name| !name.is_empty())
+                .collect()
+        };
+        let kept = names(self);
+        cmds.extend(
+            applied
+                .map(names)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|name| !kept.contains(name))
+                .map(Command::UnsetEnvironment),
+        );
+        cmds.extend(
+            self.env
+                .iter()
+                .filter(|(name, _)| !name.trim().is_empty())
+                .map(|(name, value)| Command::SetEnvironment {
+                    name: name.trim().to_owned(),
+                    value: value.clone(),
+                }),
+        );
+
+        let mut args = self.args.trim().to_owned();
+        for (op, file) in [("<", &self.stdin), (">", &self.stdout)] {
+            if !file.trim().is_empty() {
+                args = format!("{args} {op} {}", file.trim())
+                    .trim_start()
+                    .to_owned();
+            }
+        }
+        cmds.push(Command::SetArguments(args));
+        cmds
+    }
+}
+
+/// Launch configs of one executable and the one `run` uses.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchConfigs {
+    pub configs: Vec<LaunchConfig>,
+    pub active: usize,
+}
+
+impl LaunchConfigs {
+    pub fn active(&self) -> Option<&LaunchConfig> {
+        self.configs.get(self.active)
+    }
+
+    pub fn remove_active(&mut self) {
+        if self.active < self.configs.len() {
+            self.configs.remove(self.active);
+        }
+        self.active = self.active.min(self.configs.len().saturating_sub(1));
+    }
+}
+
+/// Launch configs keyed by executable path.
+pub type LaunchTable = BTreeMap<String, LaunchConfigs>;
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_commands() {
+        let config = LaunchConfig {
+            name: "big input".into(),
+            args: " --threads 4 \"a b\" ".into(),
+            env: vec![
+                ("RUST_LOG".into(), "debug".into()),
+                (" ".into(), "ignored".into()),
+                ("EMPTY".into(), String::new()),
+            ],
+            cwd: "/tmp/run".into(),
+            stdin: "input.txt".into(),
+            stdout: "out.log".into(),
+        };
+        assert_eq!(
+            config.commands(None, "/home/me"),
+            vec![
+                Command::ChangeDirectory("/tmp/run".into()),
+                Command::SetEnvironment {
+                    name: "RUST_LOG".into(),
+                    value: "debug".into()
+                },
+                Command::SetEnvironment {
+                    name: "EMPTY".into(),
+                    value: String::new()
+                },
+                Command::SetArguments("--threads 4 \"a b\" < input.txt > out.log".into()),
+            ]
+        );
+
+        // Nothing set still clears the arguments
+        assert_eq!(
+            LaunchConfig::named("default").commands(None, "/home/me"),
+            vec![Command::SetArguments(String::new())]
+        );
+        let redirected = LaunchConfig {
+            stdout: "out.log".into(),
+            ..LaunchConfig::default()
+        };
+        assert_eq!(
+            redirected.commands(None, "/home/me"),
+            vec![Command::SetArguments("> out.log".into())]
+        );
+
+        // What the previous run set and this one doesn't is undone
+        let next = LaunchConfig {
+            env: vec![("EMPTY".into(), "1".into())],
+            ..LaunchConfig::default()
+        };
+        assert_eq!(
+            next.commands(Some(&config), "/home/me"),
+            vec![
+                Command::ChangeDirectory("/home/me".into()),
+                Command::UnsetEnvironment("RUST_LOG".into()),
+                Command::SetEnvironment {
+                    name: "EMPTY".into(),
+                    value: "1".into()
+                },
+                Command::SetArguments(String::new()),
+            ]
+        );
+        assert_eq!(
+            next.commands(Some(&next), "/home/me"),
+            next.commands(None, "/home/me")
+        );
+    }
+
+    #[test]
+    fn test_remove_active() {
+        let mut table = LaunchConfigs {
+            configs: vec![LaunchConfig::named("a"), LaunchConfig::named("b")],
+            active: 1,
+        };
+        table.remove_active();
+        assert_eq!(table.active().map(|c| c.name.as_str()), Some("a"));
+        table.remove_active();
+        assert!(table.active().is_none());
+        assert_eq!(table.active, 0);
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+// ─── Layout presets ───────────────────────────────────────────────────────────
+
+/// One-click arrangements of the existing panels, like GDB's TUI layouts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum LayoutPreset {
+    /// The default arrangement: source in the middle, panels at their usual sizes
+    #[default]
+    Source,
+    /// Disassembly in the middle, registers up front
+    Assembly,
+    /// Source above its disassembly
+    Mixed,
+}
+
+/// What the central panel shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CentralView {
+    Source,
+    Assembly,
+    Split,
+}
+
+impl LayoutPreset {
+    pub const ALL: [LayoutPreset; 3] = [
+        LayoutPreset::Source,
+        LayoutPreset::Assembly,
+        LayoutPreset::Mixed,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LayoutPreset::Source => "Source focus",
+            LayoutPreset::Assembly => "Assembly focus",
+            LayoutPreset::Mixed => "Mixed",
+        }
+    }
+
+    pub fn central(self) -> CentralView {
+        match self {
+            LayoutPreset::Source => CentralView::Source,
+            LayoutPreset::Assembly => CentralView::Assembly,
+            LayoutPreset::Mixed => CentralView::Split,
+        }
+    }
+
+    /// Right panel width and console height, in points.
+    pub fn panel_sizes(self) -> (f32, f32) {
+        match self {
+            LayoutPreset::Source => (280.0, 180.0),
+            LayoutPreset::Assembly => (380.0, 160.0),
+            LayoutPreset::Mixed => (300.0, 140.0),
+        }
+    }
+}
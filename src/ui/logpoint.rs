@@ -0,0 +1,198 @@
+// ─── Template ─────────────────────────────────────────────────────────────────
+
+/// Piece of a logpoint template: literal text or a `{expr}` placeholder.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Expr(String),
+}
+
+/// `"i = {i}, p = {p->x}"` → [Text("i = "), Expr("i"), Text(", p = "), Expr("p->x")].
+/// `{{` and `}}` are literal braces; an unclosed `{` is kept as text.
+pub fn parse_template(template: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut text = String::new();
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|&(_, n)| n) == Some('{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek().map(|&(_, n)| n) == Some('}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                // Track depth so expressions like `{(struct s){1}}` keep their braces
+                let mut depth = 1;
+                let mut end = None;
+                for (j, d) in chars.by_ref() {
+                    match d {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                end = Some(j);
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                match end {
+                    Some(j) => {
+                        let expr = template[i + 1..j].trim();
+                        if !text.is_empty() {
+                            segments.push(Segment::Text(std::mem::take(&mut text)));
+                        }
+                        if !expr.is_empty() {
+                            segments.push(Segment::Expr(expr.to_owned()));
+                        }
+                    }
+                    None => text.push_str(&template[i..]),
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+/// Fills each placeholder with the matching entry of `values`, in order.
+pub fn format_message(segments: &[Segment], values: &[String]) -> String {
+    let mut values = values.iter();
+    let mut out = String::new();
+    for seg in segments {
+        match seg {
+            Segment::Text(t) => out.push_str(t),
+            Segment::Expr(e) => match values.next() {
+                Some(v) => out.push_str(v),
+                None => {
+                    out.push('{');
+                    out.push_str(e);
+                    out.push('}');
+                }
+            },
+        }
+    }
+    out
+}
+
+// ─── Pending hit ──────────────────────────────────────────────────────────────
+
+/// A logpoint hit waiting for its `Evaluate` results before printing and continuing.
+pub struct PendingLog {
+    pub bp_id: u32,
+    segments: Vec<Segment>,
+    values: Vec<Option<String>>,
+}
+
+impl PendingLog {
+    pub fn new(bp_id: u32, template: &str) -> Self {
+        let segments = parse_template(template);
+        let n = segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Expr(_)))
+            .count();
+        Self {
+            bp_id,
+            segments,
+            values: vec![None; n],
+        }
+    }
+
+    pub fn expressions(&self) -> Vec<String> {
+        self.segments
+            .iter()
+            .filter_map(|s| match s {
+                Segment::Expr(e) => Some(e.clone()),
+                Segment::Text(_) => None,
+            })
+            .collect()
+    }
+
+    /// Stores the result in the first unfilled slot for `expr`. Returns false if
+    /// this hit wasn't waiting for it.
+    pub fn fill(&mut self, expr: &str, value: Result<String, String>) -> bool {
+        let exprs = self.expressions();
+        let slot = exprs
+            .iter()
+            .zip(self.values.iter_mut())
+            .find(|(e, v)| e.as_str() == expr && v.is_none());
+        match slot {
+            Some((_, v)) => {
+                *v = Some(value.unwrap_or_else(|err| format!("<error: {err}>")));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.values.iter().all(Option::is_some)
+    }
+
+    pub fn message(&self) -> String {
+        let values: Vec<String> = self.values.iter().flatten().cloned().collect();
+        format_message(&self.segments, &values)
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_template() {
+        assert_eq!(
+            parse_template("i = {i}, p = {p->x}"),
+            vec![
+                Segment::Text("i = ".into()),
+                Segment::Expr("i".into()),
+                Segment::Text(", p = ".into()),
+                Segment::Expr("p->x".into()),
+            ]
+        );
+        assert_eq!(
+            parse_template("{{literal}} {a}"),
+            vec![
+                Segment::Text("{literal} ".into()),
+                Segment::Expr("a".into()),
+            ]
+        );
+        assert_eq!(
+            parse_template("open {x"),
+            vec![Segment::Text("open {x".into())]
+        );
+    }
+
+    #[test]
+    fn test_format_message() {
+        let segs = parse_template("i = {i}, p = {p->x}");
+        assert_eq!(
+            format_message(&segs, &["3".into(), "0x10".into()]),
+            "i = 3, p = 0x10"
+        );
+    }
+
+    #[test]
+    fn test_pending_log() {
+        let mut log = PendingLog::new(1, "{n} + {n} = {sum}");
+        assert_eq!(log.expressions(), vec!["n", "n", "sum"]);
+        assert!(log.fill("n", Ok("2".into())));
+        assert!(log.fill("n", Ok("2".into())));
+        assert!(!log.fill("other", Ok("9".into())));
+        assert!(!log.is_complete());
+        assert!(log.fill("sum", Err("No symbol \"sum\"".into())));
+        assert!(log.is_complete());
+        assert_eq!(log.message(), "2 + 2 = <error: No symbol \"sum\">");
+    }
+}
@@ -0,0 +1,96 @@
+use crate::state::MemoryBlock;
+
+/// Bytes per hex dump row.
+pub const ROW: usize = 16;
+/// Bytes read per request.
+pub const READ_LEN: u32 = 128;
+/// Largest watched region: it's re-read on every stop.
+pub const MAX_WATCH_LEN: u32 = 1024;
+
+/// Bytes typed over a hex dump cell: `41`, `0x41`, `41 42 43` or `414243`.
+/// None unless every digit pair is valid hex.
+pub fn parse_hex_bytes(input: &str) -> Option<Vec<u8>> {
+    let digits: String = input
+        .split_whitespace()
+        .map(|t| t.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// ASCII column of a hex dump: printable bytes as-is, the rest as `.`.
+pub fn ascii(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+/// Which bytes of `after` differ from what `before` held at the same
+/// address. Bytes `before` didn't cover count as unchanged.
+pub fn changed_bytes(before: &MemoryBlock, after: &MemoryBlock) -> Vec<bool> {
+    (after.addr..)
+        .zip(&after.bytes)
+        .map(|(addr, byte)| {
+            addr.checked_sub(before.addr)
+                .and_then(|i| before.bytes.get(usize::try_from(i).ok()?))
+                .is_some_and(|old| old != byte)
+        })
+        .collect()
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_bytes() {
+        assert_eq!(parse_hex_bytes("41"), Some(vec![0x41]));
+        assert_eq!(parse_hex_bytes("0x41 0X0a"), Some(vec![0x41, 0x0a]));
+        assert_eq!(
+            parse_hex_bytes(" dead beef "),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse_hex_bytes(""), None);
+        assert_eq!(parse_hex_bytes("4"), None);
+        assert_eq!(parse_hex_bytes("zz"), None);
+        assert_eq!(ascii(b"Hi\n\0~"), "Hi..~");
+    }
+
+    #[test]
+    fn test_changed_bytes() {
+        let block = |addr, bytes: &[u8]| MemoryBlock {
+            addr,
+            bytes: bytes.to_vec(),
+        };
+        let before = block(0x1000, &[1, 2, 3, 4]);
+        assert_eq!(
+            changed_bytes(&before, &block(0x1000, &[1, 9, 3, 0])),
+            vec![false, true, false, true]
+        );
+        assert_eq!(changed_bytes(&before, &before), vec![false; 4]);
+        // Shifted window: only the overlap compares
+        assert_eq!(
+            changed_bytes(&before, &block(0x1002, &[3, 5, 7])),
+            vec![false, true, false]
+        );
+        assert_eq!(
+            changed_bytes(&before, &block(0x0ffe, &[0, 0, 1, 7])),
+            vec![false, false, false, true]
+        );
+        assert_eq!(changed_bytes(&before, &block(0x2000, &[1])), vec![false]);
+    }
+}
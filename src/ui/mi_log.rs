@@ -0,0 +1,149 @@
+use std::collections::VecDeque;
+
+use crate::state::{DebuggerEvent, UiEvent};
+
+/// Records kept; older ones scroll away.
+const CAPACITY: usize = 200;
+
+/// Longest event text kept per record: disassembly dumps run to pages.
+const MAX_EVENT_CHARS: usize = 300;
+
+/// One raw MI line and what the parser made of it.
+pub struct MiRecord {
+    pub line: String,
+    /// `Debug` of the events it produced, None if the parser dropped it
+    pub event: Option<String>,
+}
+
+// ─── Raw MI log ───────────────────────────────────────────────────────────────
+
+/// The last records from GDB with their parse result, for working on the
+/// parser. `run_loop` forwards each raw line before the events parsed from
+/// it, so events belong to the latest line until a command is sent.
+#[derive(Default)]
+pub struct MiLog {
+    pub records: VecDeque<MiRecord>,
+    events_to: EventsTo,
+}
+
+/// Where the next events come from.
+#[derive(Default)]
+enum EventsTo {
+    /// Not from a line: a failed write, a lost read
+    #[default]
+    Nothing,
+    LastRecord,
+    /// A prompt, only recorded if something comes of it
+    Prompt(String),
+}
+
+impl MiLog {
+    pub fn observe(&mut self, event: &DebuggerEvent) {
+        match event {
+            DebuggerEvent::Ui(UiEvent::MiRecord(line)) => {
+                // Prompts are rarely parsed into anything
+                if line.trim_end() == "(gdb)" {
+                    self.events_to = EventsTo::Prompt(line.clone());
+                } else {
+                    self.push(line.clone());
+                }
+            }
+            // Our own echo of the next command: what follows until GDB
+            // answers isn't from a record
+            DebuggerEvent::Ui(UiEvent::CommandSent(_)) => self.events_to = EventsTo::Nothing,
+            DebuggerEvent::Ui(UiEvent::CommandStalled(_)) => {}
+            event => {
+                match std::mem::take(&mut self.events_to) {
+                    EventsTo::Nothing => return,
+                    EventsTo::LastRecord => {}
+                    EventsTo::Prompt(line) => self.push(line),
+                }
+                self.events_to = EventsTo::LastRecord;
+                let Some(record) = self.records.back_mut() else {
+                    return;
+                };
+                let text: String = format!("{event:?}").chars().take(MAX_EVENT_CHARS).collect();
+                match &mut record.event {
+                    Some(prev) => {
+                        prev.push_str("; ");
+                        prev.push_str(&text);
+                    }
+                    None => record.event = Some(text),
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.records.len() == CAPACITY {
+            self.records.pop_front();
+        }
+        self.records.push_back(MiRecord { line, event: None });
+        self.events_to = EventsTo::LastRecord;
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.events_to = EventsTo::Nothing;
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StateEvent;
+
+    fn record(line: &str) -> DebuggerEvent {
+        DebuggerEvent::Ui(UiEvent::MiRecord(line.into()))
+    }
+
+    #[test]
+    fn test_mi_log_flags_unhandled() {
+        let mut log = MiLog::default();
+        log.observe(&record("*running,thread-id=\"all\""));
+        log.observe(&DebuggerEvent::State(StateEvent::ProgramStarted));
+        log.observe(&record("(gdb) "));
+        log.observe(&DebuggerEvent::Ui(UiEvent::CommandSent(
+            "-exec-interrupt".into(),
+        )));
+        log.observe(&record(
+            "=tsv-created,name=\"trace_timestamp\",initial=\"0\"",
+        ));
+        log.observe(&record("~\"Starting program\\n\""));
+        log.observe(&DebuggerEvent::Ui(UiEvent::ConsoleOutput(
+            "Starting program\n".into(),
+        )));
+        // Symbols done once a prompt shows: that prompt's record
+        log.observe(&record("(gdb)"));
+        log.observe(&DebuggerEvent::State(StateEvent::SymbolsLoaded));
+        // A failed write comes from no line at all
+        log.observe(&DebuggerEvent::Ui(UiEvent::CommandSent("-exec-run".into())));
+        log.observe(&DebuggerEvent::Ui(UiEvent::GdbError("broken pipe".into())));
+
+        let records: Vec<_> = log
+            .records
+            .iter()
+            .map(|r| (r.line.as_str(), r.event.as_deref()))
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                ("*running,thread-id=\"all\"", Some("State(ProgramStarted)")),
+                ("=tsv-created,name=\"trace_timestamp\",initial=\"0\"", None),
+                (
+                    "~\"Starting program\\n\"",
+                    Some("Ui(ConsoleOutput(\"Starting program\\n\"))")
+                ),
+                ("(gdb)", Some("State(SymbolsLoaded)")),
+            ]
+        );
+
+        // Bounded
+        for _ in 0..CAPACITY + 10 {
+            log.observe(&record("^done"));
+        }
+        assert_eq!(log.records.len(), CAPACITY);
+    }
+}
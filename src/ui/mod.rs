@@ -1,4 +1,35 @@
 mod app;
+mod bookmarks;
+mod changes;
 pub mod command;
+mod condition;
+mod crash_report;
+mod disasm_export;
+mod editor;
+mod errno;
+mod errors;
+mod focus;
+mod frame_eval;
+mod hit_log;
+mod insn_doc;
+mod launch;
+mod layout;
+mod logpoint;
+mod memory;
+mod mi_log;
+mod profile;
+mod reconnect;
+mod registers;
+mod search;
+pub mod settings;
+mod smart_step;
+mod source_watch;
+mod state_report;
+mod step_until;
+mod stop_hook;
+mod symbols;
+mod syntax;
+mod value_diff;
+mod verbosity;
 
 pub use app::App;
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// ─── Step profiler ────────────────────────────────────────────────────────────
+
+/// Wall-clock time per source line while auto-stepping. Each stop closes the
+/// interval of the line we stepped from: the time `-exec-step` took to get
+/// past it, round trip through GDB included.
+#[derive(Default)]
+pub struct StepProfile {
+    pub running: bool,
+    /// (file, line) → (accumulated time, steps)
+    lines: HashMap<(String, u32), (Duration, u32)>,
+    last: Option<(String, u32, Instant)>,
+}
+
+impl StepProfile {
+    pub fn start(&mut self) {
+        self.running = true;
+        self.last = None;
+    }
+
+    pub fn stop(&mut self) {
+        self.running = false;
+        self.last = None;
+    }
+
+    pub fn reset(&mut self) {
+        self.lines.clear();
+        self.last = None;
+    }
+
+    /// A stop at `file:line` at time `now`. `None` (no line info) just ends
+    /// the previous interval.
+    pub fn record(&mut self, location: Option<(&str, u32)>, now: Instant) {
+        if let Some((file, line, since)) = self.last.take() {
+            let entry = self.lines.entry((file, line)).or_default();
+            entry.0 += now.saturating_duration_since(since);
+            entry.1 += 1;
+        }
+        self.last = location.map(|(file, line)| (file.to_owned(), line, now));
+    }
+
+    pub fn time_at(&self, file: &str, line: u32) -> Option<Duration> {
+        self.lines.get(&(file.to_owned(), line)).map(|(t, _)| *t)
+    }
+
+    /// 0.0..=1.0 relative to the hottest line, for the gutter overlay.
+    pub fn heat(&self, file: &str, line: u32) -> Option<f32> {
+        let max = self.lines.values().map(|(t, _)| *t).max()?;
+        let t = self.time_at(file, line)?;
+        if max.is_zero() {
+            return Some(0.0);
+        }
+        Some(t.as_secs_f32() / max.as_secs_f32())
+    }
+
+    /// Hottest lines first: (file, line, time, steps).
+    pub fn hottest(&self, n: usize) -> Vec<(&str, u32, Duration, u32)> {
+        let mut lines: Vec<_> = self
+            .lines
+            .iter()
+            .map(|((file, line), (t, steps))| (file.as_str(), *line, *t, *steps))
+            .collect();
+        lines.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(b.0)).then(a.1.cmp(&b.1)));
+        lines.truncate(n);
+        lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_per_line() {
+        let t0 = Instant::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+        let mut profile = StepProfile::default();
+        profile.start();
+
+        // First stop only opens an interval
+        profile.record(Some(("main.c", 3)), ms(0));
+        assert!(profile.is_empty());
+
+        profile.record(Some(("main.c", 4)), ms(10));
+        profile.record(Some(("main.c", 3)), ms(40)); // loop back
+        profile.record(Some(("main.c", 4)), ms(45));
+        profile.record(None, ms(50)); // stepped into code without line info
+        profile.record(Some(("main.c", 5)), ms(90));
+
+        assert_eq!(
+            profile.time_at("main.c", 3),
+            Some(Duration::from_millis(15))
+        );
+        assert_eq!(
+            profile.time_at("main.c", 4),
+            Some(Duration::from_millis(35))
+        );
+        assert_eq!(profile.time_at("main.c", 5), None);
+        assert_eq!(profile.heat("main.c", 4), Some(1.0));
+
+        let hottest = profile.hottest(1);
+        assert_eq!(hottest, vec![("main.c", 4, Duration::from_millis(35), 2)]);
+
+        // Stopping drops the open interval: the pause isn't line time
+        profile.stop();
+        profile.start();
+        profile.record(Some(("main.c", 6)), ms(5000));
+        assert_eq!(profile.time_at("main.c", 5), None);
+
+        profile.reset();
+        assert!(profile.is_empty());
+        assert_eq!(profile.heat("main.c", 4), None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::{Duration, Instant};
+
+use super::command::Command;
+
+/// Retries before giving up on a dropped link.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+// ─── Remote link ──────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkState {
+    Idle,
+    /// `attempt` 0 is the user's own connect; retries count from 1.
+    Connecting {
+        attempt: u32,
+    },
+    Connected,
+    Waiting {
+        attempt: u32,
+        at: Instant,
+    },
+    GaveUp,
+}
+
+/// `target remote` connection with automatic reconnect. Breakpoints live in
+/// GDB, so a fresh `target remote` to the same address is all it takes.
+pub struct RemoteLink {
+    pub addr: Option<String>,
+    pub state: LinkState,
+}
+
+impl Default for RemoteLink {
+    fn default() -> Self {
+        Self {
+            addr: None,
+            state: LinkState::Idle,
+        }
+    }
+}
+
+/// 1s, 2s, 4s… capped at 16s.
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.saturating_sub(1).min(4))
+}
+
+impl RemoteLink {
+    pub fn connect(&mut self, addr: &str) -> Command {
+        self.addr = Some(addr.to_owned());
+        self.state = LinkState::Connecting { attempt: 0 };
+        Command::ConnectRemote(addr.to_owned())
+    }
+
+    pub fn on_connected(&mut self) {
+        self.state = LinkState::Connected;
+    }
+
+    /// A connect attempt failed. The first one is the user's to retry;
+    /// reconnects back off until `MAX_ATTEMPTS`.
+    pub fn on_failed(&mut self, now: Instant) {
+        self.state = match self.state {
+            LinkState::Connecting { attempt: 0 } => LinkState::Idle,
+            LinkState::Connecting { attempt } if attempt >= MAX_ATTEMPTS => LinkState::GaveUp,
+            LinkState::Connecting { attempt } => LinkState::Waiting {
+                attempt: attempt + 1,
+                at: now + backoff(attempt + 1),
+            },
+            other => other,
+        };
+    }
+
+    pub fn on_disconnected(&mut self, now: Instant) {
+        if self.state == LinkState::Connected {
+            self.state = LinkState::Waiting {
+                attempt: 1,
+                at: now + backoff(1),
+            };
+        }
+    }
+
+    /// The reconnect command once the wait is over.
+    pub fn poll(&mut self, now: Instant) -> Option<Command> {
+        let LinkState::Waiting { attempt, at } = self.state else {
+            return None;
+        };
+        if now < at {
+            return None;
+        }
+        self.state = LinkState::Connecting { attempt };
+        self.addr.clone().map(Command::ConnectRemote)
+    }
+
+    pub fn cancel(&mut self) {
+        self.state = LinkState::Idle;
+    }
+
+    pub fn is_reconnecting(&self) -> bool {
+        matches!(
+            self.state,
+            LinkState::Waiting { .. } | LinkState::Connecting { attempt: 1.. }
+        )
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_state_machine() {
+        let t0 = Instant::now();
+        let secs = |n| t0 + Duration::from_secs(n);
+        let mut link = RemoteLink::default();
+
+        // A refused first connect is reported, not retried
+        link.connect("board:2345");
+        link.on_failed(t0);
+        assert_eq!(link.state, LinkState::Idle);
+
+        assert_eq!(
+            link.connect("board:2345"),
+            Command::ConnectRemote("board:2345".into())
+        );
+        link.on_connected();
+        assert!(!link.is_reconnecting());
+
+        link.on_disconnected(t0);
+        assert!(link.is_reconnecting());
+        assert_eq!(link.poll(t0), None);
+        assert_eq!(
+            link.poll(secs(1)),
+            Some(Command::ConnectRemote("board:2345".into()))
+        );
+        assert_eq!(link.state, LinkState::Connecting { attempt: 1 });
+
+        // Backoff doubles between failed retries
+        link.on_failed(secs(1));
+        assert_eq!(
+            link.state,
+            LinkState::Waiting {
+                attempt: 2,
+                at: secs(3)
+            }
+        );
+        assert_eq!(link.poll(secs(2)), None);
+        assert!(link.poll(secs(3)).is_some());
+        link.on_connected();
+        assert_eq!(link.state, LinkState::Connected);
+
+        // Gives up after MAX_ATTEMPTS
+        link.on_disconnected(secs(10));
+        let mut now = secs(10);
+        for _ in 0..MAX_ATTEMPTS {
+            now += Duration::from_secs(60);
+            assert!(link.poll(now).is_some());
+            link.on_failed(now);
+        }
+        assert_eq!(link.state, LinkState::GaveUp);
+        assert_eq!(link.poll(now + Duration::from_secs(60)), None);
+
+        // Cancel stops a pending retry
+        link.on_connected();
+        link.on_disconnected(now);
+        link.cancel();
+        assert_eq!(link.poll(now + Duration::from_secs(60)), None);
+    }
+}
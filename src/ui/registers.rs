@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+
+use super::insn_doc::Arch;
+
+// ─── Register categories ──────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RegCategory {
+    General,
+    Float,
+    Vector,
+    Segment,
+    Flags,
+    /// Control/status registers, and anything we don't recognize
+    Control,
+}
+
+impl RegCategory {
+    /// Display order of the Registers grid groups.
+    pub const ALL: [RegCategory; 6] = [
+        RegCategory::General,
+        RegCategory::Flags,
+        RegCategory::Float,
+        RegCategory::Vector,
+        RegCategory::Segment,
+        RegCategory::Control,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RegCategory::General => "General",
+            RegCategory::Float => "FP",
+            RegCategory::Vector => "Vector",
+            RegCategory::Segment => "Segment",
+            RegCategory::Flags => "Flags",
+            RegCategory::Control => "Control",
+        }
+    }
+
+    /// What a SIMD-heavy crash needs without the x87/segment noise.
+    pub fn default_shown() -> Vec<RegCategory> {
+        vec![
+            RegCategory::General,
+            RegCategory::Flags,
+            RegCategory::Float,
+            RegCategory::Vector,
+        ]
+    }
+}
+
+/// Registers the pinned strip has room for.
+pub const MAX_PINNED: usize = 6;
+
+/// Pins `name`, or unpins it if it already was. False when the strip is full.
+pub fn toggle_pin(pinned: &mut Vec<String>, name: &str) -> bool {
+    if let Some(i) = pinned.iter().position(|p| p == name) {
+        pinned.remove(i);
+    } else if pinned.len() < MAX_PINNED {
+        pinned.push(name.to_owned());
+    } else {
+        return false;
+    }
+    true
+}
+
+/// `name` is `prefix` followed by a number in `0..=max`, e.g. `xmm12`.
+fn numbered(name: &str, prefix: &str, max: u32) -> bool {
+    name.strip_prefix(prefix)
+        .filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|n| n.parse::<u32>().ok())
+        .is_some_and(|n| n <= max)
+}
+
+/// Category of a register from `-data-list-register-names`. The arch only
+/// matters where names collide: `s0` is a float on ARM, a saved GP on RISC-V.
+pub fn categorize(arch: Option<Arch>, name: &str) -> RegCategory {
+    use RegCategory::*;
+
+    match arch {
+        Some(Arch::X86) => x86(name),
+        Some(Arch::Arm) => arm(name),
+        // RISC-V and friends; then whatever x86/ARM name it happens to be
+        None => [riscv, x86, arm]
+            .into_iter()
+            .map(|f| f(name))
+            .find(|c| *c != Control)
+            .unwrap_or(Control),
+    }
+}
+
+fn x86(name: &str) -> RegCategory {
+    use RegCategory::*;
+
+    let gp16 = ["ax", "bx", "cx", "dx", "si", "di", "bp", "sp"];
+    let gp8 = [
+        "al", "ah", "bl", "bh", "cl", "ch", "dl", "dh", "sil", "dil", "bpl", "spl",
+    ];
+    // r8..r15 and their r8d/r8w/r8l/r8b views
+    let extended = name
+        .strip_prefix('r')
+        .map(|n| n.trim_end_matches(['d', 'w', 'l', 'b']))
+        .and_then(|n| n.parse::<u32>().ok())
+        .is_some_and(|n| (8..=15).contains(&n));
+    match name {
+        "rip" | "eip" | "ip" => General,
+        n if gp16.contains(&n)
+            || gp8.contains(&n)
+            || n.strip_prefix(['r', 'e'])
+                .is_some_and(|r| gp16.contains(&r)) =>
+        {
+            General
+        }
+        _ if extended => General,
+        "eflags" | "rflags" | "flags" => Flags,
+        "cs" | "ss" | "ds" | "es" | "fs" | "gs" | "fs_base" | "gs_base" => Segment,
+        "fctrl" | "fstat" | "ftag" | "fiseg" | "fioff" | "foseg" | "fooff" | "fop" => Float,
+        n if numbered(n, "st", 7) => Float,
+        n if numbered(n, "xmm", 31)
+            || numbered(n, "ymm", 31)
+            || numbered(n, "zmm", 31)
+            || numbered(n, "k", 7)
+            || numbered(n, "mm", 7)
+            || n.strip_suffix('h').is_some_and(|v| numbered(v, "ymm", 31)) =>
+        {
+            Vector
+        }
+        _ => Control,
+    }
+}
+
+fn arm(name: &str) -> RegCategory {
+    use RegCategory::*;
+
+    match name {
+        "sp" | "pc" | "lr" | "fp" | "ip" => General,
+        n if numbered(n, "x", 30) || numbered(n, "w", 30) || numbered(n, "r", 12) => General,
+        "cpsr" | "apsr" => Flags,
+        n if numbered(n, "d", 31)
+            || numbered(n, "s", 31)
+            || numbered(n, "h", 31)
+            || numbered(n, "b", 31) =>
+        {
+            Float
+        }
+        "ffr" | "vg" => Vector,
+        n if numbered(n, "v", 31)
+            || numbered(n, "q", 31)
+            || numbered(n, "z", 31)
+            || numbered(n, "p", 15) =>
+        {
+            Vector
+        }
+        _ => Control,
+    }
+}
+
+fn riscv(name: &str) -> RegCategory {
+    use RegCategory::*;
+
+    match name {
+        "zero" | "ra" | "sp" | "gp" | "tp" | "fp" | "pc" => General,
+        n if numbered(n, "x", 31)
+            || numbered(n, "a", 7)
+            || numbered(n, "s", 11)
+            || numbered(n, "t", 6) =>
+        {
+            General
+        }
+        n if numbered(n, "f", 31)
+            || numbered(n, "ft", 11)
+            || numbered(n, "fs", 11)
+            || numbered(n, "fa", 7) =>
+        {
+            Float
+        }
+        n if numbered(n, "v", 31) => Vector,
+        _ => Control,
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use RegCategory::*;
+
+    #[test]
+    fn test_x86_categories() {
+        let x86 = |n| categorize(Some(Arch::X86), n);
+        for n in ["rax", "r15", "r9d", "eax", "sil", "rip"] {
+            assert_eq!(x86(n), General, "{n}");
+        }
+        assert_eq!(x86("eflags"), Flags);
+        assert_eq!(x86("fs_base"), Segment);
+        assert_eq!(x86("cs"), Segment);
+        assert_eq!(x86("st7"), Float);
+        assert_eq!(x86("fctrl"), Float);
+        for n in ["xmm0", "ymm15", "zmm31", "k3", "ymm0h"] {
+            assert_eq!(x86(n), Vector, "{n}");
+        }
+        assert_eq!(x86("mxcsr"), Control);
+        assert_eq!(x86("orig_rax"), Control);
+        // Not an extended GP register
+        assert_eq!(x86("r16"), Control);
+    }
+
+    #[test]
+    fn test_toggle_pin() {
+        let mut pinned = vec![];
+        assert!(toggle_pin(&mut pinned, "rsp"));
+        assert!(toggle_pin(&mut pinned, "rip"));
+        assert!(toggle_pin(&mut pinned, "rdi"));
+        assert_eq!(pinned, ["rsp", "rip", "rdi"]);
+
+        // Unpinning keeps the others in order
+        assert!(toggle_pin(&mut pinned, "rip"));
+        assert_eq!(pinned, ["rsp", "rdi"]);
+
+        for n in ["rsi", "rdx", "rcx", "r8"] {
+            assert!(toggle_pin(&mut pinned, n));
+        }
+        assert_eq!(pinned.len(), MAX_PINNED);
+        assert!(!toggle_pin(&mut pinned, "r9"));
+        assert!(!pinned.contains(&"r9".to_string()));
+        // Full, but unpinning still works
+        assert!(toggle_pin(&mut pinned, "rsp"));
+        assert_eq!(pinned.len(), MAX_PINNED - 1);
+    }
+
+    #[test]
+    fn test_arm_and_riscv_categories() {
+        let arm = |n| categorize(Some(Arch::Arm), n);
+        assert_eq!(arm("x29"), General);
+        assert_eq!(arm("sp"), General);
+        assert_eq!(arm("cpsr"), Flags);
+        assert_eq!(arm("s0"), Float);
+        assert_eq!(arm("d31"), Float);
+        assert_eq!(arm("v7"), Vector);
+        assert_eq!(arm("z0"), Vector);
+        assert_eq!(arm("fpsr"), Control);
+
+        // Same name, different meaning on RISC-V
+        assert_eq!(categorize(None, "s0"), General);
+        assert_eq!(categorize(None, "a7"), General);
+        assert_eq!(categorize(None, "ft0"), Float);
+        assert_eq!(categorize(None, "fcsr"), Control);
+    }
+}
@@ -0,0 +1,220 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+// Files bigger than this are skipped (generated code, blobs)
+const MAX_FILE_BYTES: u64 = 2 << 20;
+
+// ─── Query ────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug)]
+pub struct SearchQuery {
+    pub needle: String,
+    /// Lowercase extensions without the dot; empty = every file.
+    pub extensions: Vec<String>,
+    pub max_results: usize,
+}
+
+/// `"c, h,.rs"` → ["c", "h", "rs"].
+pub fn parse_extensions(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|e| e.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+// ─── Hits ─────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub path: String,
+    pub line: u32,
+    pub snippet: String,
+    /// Lower is better, see `rank`.
+    pub rank: u8,
+}
+
+/// 0 = whole-word match with the same case, 1 = same-case substring,
+/// 2 = case-insensitive substring. None if `line` doesn't contain `needle`.
+pub fn rank(line: &str, needle: &str) -> Option<u8> {
+    if needle.is_empty() {
+        return None;
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let whole_word = line.match_indices(needle).any(|(i, _)| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + needle.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    });
+    if whole_word {
+        Some(0)
+    } else if line.contains(needle) {
+        Some(1)
+    } else if line.to_lowercase().contains(&needle.to_lowercase()) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Best matches first, then by file and line.
+pub fn sort_hits(hits: &mut [SearchHit]) {
+    hits.sort_by(|a, b| (a.rank, &a.path, a.line).cmp(&(b.rank, &b.path, b.line)));
+}
+
+// ─── Walk ─────────────────────────────────────────────────────────────────────
+
+/// Searches every matching file under `roots`, calling `on_hit` for each hit.
+/// Stops after `max_results` hits or when `on_hit` returns false.
+pub fn search_tree(
+    roots: &[PathBuf],
+    query: &SearchQuery,
+    mut on_hit: impl FnMut(SearchHit) -> bool,
+) {
+    let mut found = 0;
+    let mut stack: Vec<PathBuf> = roots.to_vec();
+
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+        entries.sort();
+
+        for path in entries {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            if name.starts_with('.') || name == "target" {
+                continue;
+            }
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            if !wanted(&path, &query.extensions) {
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                continue; // binario o sin permisos
+            };
+            for (i, line) in text.lines().enumerate() {
+                let Some(rank) = rank(line, &query.needle) else {
+                    continue;
+                };
+                let hit = SearchHit {
+                    path: path.to_string_lossy().into_owned(),
+                    line: i as u32 + 1,
+                    snippet: line.trim().to_owned(),
+                    rank,
+                };
+                found += 1;
+                if !on_hit(hit) || found >= query.max_results {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn wanted(path: &Path, extensions: &[String]) -> bool {
+    let small = path
+        .metadata()
+        .map(|m| m.len() <= MAX_FILE_BYTES)
+        .unwrap_or(false);
+    let ext_ok = extensions.is_empty()
+        || path
+            .extension()
+            .map(|e| extensions.contains(&e.to_string_lossy().to_ascii_lowercase()))
+            .unwrap_or(false);
+    small && ext_ok
+}
+
+/// Runs `search_tree` on a background thread, streaming hits as they're found.
+/// Dropping the receiver cancels the search.
+pub fn spawn_search(roots: Vec<PathBuf>, query: SearchQuery) -> Receiver<SearchHit> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        search_tree(&roots, &query, |hit| tx.send(hit).is_ok());
+    });
+    rx
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("gdb_gui_search_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/.git")).unwrap();
+        std::fs::write(
+            root.join("src/main.c"),
+            "int count_items(void);\nint main(void) {\n    return count;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/util.h"), "#define COUNT 3\n").unwrap();
+        std::fs::write(root.join("src/notes.txt"), "count\n").unwrap();
+        std::fs::write(root.join("src/.git/HEAD"), "count\n").unwrap();
+        root
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(rank("return count;", "count"), Some(0));
+        assert_eq!(rank("count_items()", "count"), Some(1));
+        assert_eq!(rank("#define COUNT 3", "count"), Some(2));
+        assert_eq!(rank("nothing here", "count"), None);
+        assert_eq!(rank("anything", ""), None);
+    }
+
+    #[test]
+    fn test_search_and_rank() {
+        let root = fixture();
+        let query = SearchQuery {
+            needle: "count".into(),
+            extensions: parse_extensions("c, .h"),
+            max_results: 100,
+        };
+        let mut hits = vec![];
+        search_tree(std::slice::from_ref(&root), &query, |h| {
+            hits.push(h);
+            true
+        });
+        sort_hits(&mut hits);
+
+        let summary: Vec<_> = hits
+            .iter()
+            .map(|h| {
+                let file = Path::new(&h.path).file_name().unwrap().to_string_lossy();
+                (h.rank, format!("{file}:{}", h.line))
+            })
+            .collect();
+        // notes.txt is filtered by extension, .git is skipped
+        assert_eq!(
+            summary,
+            vec![
+                (0, "main.c:3".to_string()),
+                (1, "main.c:1".to_string()),
+                (2, "util.h:1".to_string()),
+            ]
+        );
+        assert_eq!(hits[0].snippet, "return count;");
+
+        let capped = SearchQuery {
+            max_results: 1,
+            ..query
+        };
+        let mut n = 0;
+        search_tree(std::slice::from_ref(&root), &capped, |_| {
+            n += 1;
+            true
+        });
+        assert_eq!(n, 1);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use super::bookmarks::Bookmarks;
+use super::command::{Command, ForkMode};
+use super::editor;
+use super::launch::LaunchTable;
+use super::layout::LayoutPreset;
+use super::registers::RegCategory;
+use super::verbosity::Verbosity;
+
+/// Executables remembered for the welcome screen.
+const MAX_RECENT: usize = 8;
+
+// ─── Settings ─────────────────────────────────────────────────────────────────
+
+/// User preferences kept across sessions in eframe's storage.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Commands section buttons: (label, GDB command).
+    pub custom_commands: Vec<(String, String)>,
+    pub follow_fork: ForkMode,
+    pub detach_on_fork: bool,
+    /// Run GDB in async mode; some remote targets misbehave with it.
+    pub mi_async: bool,
+    /// `set substitute-path` rules: (build-time prefix, local prefix).
+    pub substitute_paths: Vec<(String, String)>,
+    /// Save a crash report on the first fatal signal of each run.
+    pub crash_reports: bool,
+    /// Register groups shown in the Registers tab.
+    pub register_categories: Vec<RegCategory>,
+    /// Registers always shown in a strip above the source.
+    pub pinned_registers: Vec<String>,
+    /// Header above the source view with the file actually loaded.
+    pub show_source_path: bool,
+    /// Values of locals at the end of the lines that mention them.
+    pub inline_values: bool,
+    /// `@ 0x…` after the location in the top bar.
+    pub show_pc: bool,
+    /// Reload shown sources when they change on disk instead of asking.
+    pub auto_reload_sources: bool,
+    /// MI traffic shown in the Console tab.
+    pub console_verbosity: Verbosity,
+    /// Launch configs per executable path.
+    pub launch: LaunchTable,
+    /// Bookmarked source lines per file.
+    pub bookmarks: Bookmarks,
+    /// Sent after every stop, like GDB's `hook-stop`.
+    pub stop_commands: Vec<String>,
+    /// Last layout preset picked.
+    pub layout: LayoutPreset,
+    /// Evaluate errno on every stop, and the return value at a syscall stop,
+    /// and show them in the status bar.
+    pub show_errno: bool,
+    /// Executables loaded before, most recent first.
+    pub recent_executables: Vec<String>,
+    /// "Open in editor" command, with `{file}` and `{line}` placeholders.
+    pub editor_command: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            custom_commands: vec![],
+            // Same as GDB's own defaults
+            follow_fork: ForkMode::Parent,
+            detach_on_fork: true,
+            // GDB starts in sync mode; async lets Pause interrupt a running target
+            mi_async: true,
+            substitute_paths: vec![],
+            crash_reports: false,
+            register_categories: RegCategory::default_shown(),
+            pinned_registers: vec![],
+            show_source_path: true,
+            inline_values: true,
+            show_pc: true,
+            auto_reload_sources: false,
+            console_verbosity: Verbosity::Normal,
+            launch: LaunchTable::new(),
+            bookmarks: Bookmarks::new(),
+            stop_commands: vec![],
+            layout: LayoutPreset::Source,
+            show_errno: false,
+            recent_executables: vec![],
+            editor_command: editor::DEFAULT_COMMAND.into(),
+        }
+    }
+}
+
+impl Settings {
+    /// `-gdb-set` commands to apply these settings to a fresh GDB session.
+    pub fn gdb_commands(&self) -> Vec<Command> {
+        let mut cmds = vec![
+            Command::SetFollowForkMode(self.follow_fork),
+            Command::SetDetachOnFork(self.detach_on_fork),
+        ];
+        cmds.extend(
+            self.substitute_paths
+                .iter()
+                .map(|(from, to)| Command::SubstitutePath {
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+        );
+        cmds
+    }
+
+    /// Moves `path` to the front of the recent executables.
+    pub fn remember_executable(&mut self, path: &str) {
+        self.recent_executables.retain(|p| p != path);
+        self.recent_executables.insert(0, path.to_owned());
+        self.recent_executables.truncate(MAX_RECENT);
+    }
+
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        storage
+            .and_then(|s| eframe::get_value(s, eframe::APP_KEY))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::launch::{LaunchConfig, LaunchConfigs};
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemStorage(HashMap<String, String>);
+
+    impl eframe::Storage for MemStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_owned(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    #[test]
+    fn test_custom_commands_round_trip() {
+        let settings = Settings {
+            custom_commands: vec![
+                ("heap".into(), "info proc mappings".into()),
+                ("args \"quoted\"".into(), "p argv[1]".into()),
+            ],
+            follow_fork: ForkMode::Child,
+            detach_on_fork: false,
+            mi_async: false,
+            substitute_paths: vec![("/builds/app".into(), "/home/me/app".into())],
+            crash_reports: true,
+            register_categories: vec![RegCategory::Vector, RegCategory::Segment],
+            pinned_registers: vec!["rsp".into(), "rip".into()],
+            show_source_path: false,
+            inline_values: false,
+            show_pc: false,
+            auto_reload_sources: true,
+            console_verbosity: Verbosity::Debug,
+            launch: LaunchTable::from([(
+                "/home/me/app/target/debug/app".into(),
+                LaunchConfigs {
+                    configs: vec![LaunchConfig {
+                        name: "fixture".into(),
+                        args: "--input data.bin".into(),
+                        env: vec![("RUST_BACKTRACE".into(), "1".into())],
+                        cwd: "/home/me/app".into(),
+                        stdin: String::new(),
+                        stdout: "out.log".into(),
+                    }],
+                    active: 0,
+                },
+            )]),
+            bookmarks: Bookmarks::from([("src/main.c".into(), vec![12, 40])]),
+            stop_commands: vec!["info registers eflags".into(), "x/4gx $sp".into()],
+            layout: LayoutPreset::Mixed,
+            show_errno: true,
+            recent_executables: vec!["/home/me/app/target/debug/app".into()],
+            editor_command: "$EDITOR +{line} {file}".into(),
+        };
+        let mut storage = MemStorage::default();
+        settings.save(&mut storage);
+        assert_eq!(Settings::load(Some(&storage)), settings);
+    }
+
+    #[test]
+    fn test_remember_executable() {
+        let mut settings = Settings::default();
+        for n in 0..10 {
+            settings.remember_executable(&format!("./app{n}"));
+        }
+        assert_eq!(settings.recent_executables.len(), MAX_RECENT);
+        assert_eq!(settings.recent_executables[0], "./app9");
+        // Loading one again moves it up instead of repeating it
+        settings.remember_executable("./app5");
+        assert_eq!(settings.recent_executables[..2], ["./app5", "./app9"]);
+        assert_eq!(settings.recent_executables.len(), MAX_RECENT);
+    }
+
+    #[test]
+    fn test_load_defaults() {
+        assert_eq!(Settings::load(None), Settings::default());
+
+        // Unknown or missing fields from other versions don't lose the rest
+        let mut storage = MemStorage::default();
+        eframe::Storage::set_string(
+            &mut storage,
+            eframe::APP_KEY,
+            "(custom_commands: [(\"a\", \"b\")], future: 1)".into(),
+        );
+        assert_eq!(
+            Settings::load(Some(&storage)).custom_commands,
+            vec![("a".to_string(), "b".to_string())]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use super::command::Command;
+use crate::state::{Frame, PauseState, StopReason};
+
+/// Finishes in a row before giving up: a callback-heavy library can bounce
+/// between frames without ever returning to ours.
+const MAX_FINISHES: u32 = 32;
+
+/// Where library sources live when the source path doesn't say where ours do.
+const SYSTEM_PREFIXES: [&str; 4] = ["/usr/", "/lib/", "/lib64/", "/opt/"];
+
+/// A frame worth stopping in. With directories on GDB's source path, user
+/// code is what's under them; otherwise anything outside the system prefixes.
+/// Relative paths are relative to the compilation dir, so they're ours.
+pub fn is_user_frame(frame: &Frame, source_dirs: &[String]) -> bool {
+    let Some(file) = frame.file.as_deref().map(Path::new) else {
+        return false;
+    };
+    if file.is_relative() {
+        return true;
+    }
+    // `$cdir`/`$cwd` are GDB's placeholders, not places
+    let mut dirs = source_dirs
+        .iter()
+        .filter(|d| !d.starts_with('$'))
+        .peekable();
+    if dirs.peek().is_some() {
+        dirs.any(|d| file.starts_with(d))
+    } else {
+        !SYSTEM_PREFIXES.iter().any(|p| file.starts_with(p))
+    }
+}
+
+// ─── Smart step ───────────────────────────────────────────────────────────────
+
+/// Step Into that doesn't strand you in libc: after the step, finish out of
+/// non-user frames until back in user code or something else stops us.
+#[derive(Default)]
+pub struct SmartStep {
+    /// Finishes issued so far; None when not stepping
+    finishes: Option<u32>,
+}
+
+impl SmartStep {
+    pub fn start(&mut self) -> Command {
+        self.finishes = Some(0);
+        Command::Step
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.finishes.is_some()
+    }
+
+    pub fn cancel(&mut self) {
+        self.finishes = None;
+    }
+
+    /// The next command after a stop, or None once we're done and the stop
+    /// should be shown like any other.
+    pub fn on_stop(&mut self, pause: &PauseState, source_dirs: &[String]) -> Option<Command> {
+        let finishes = self.finishes?;
+        let ours = matches!(
+            pause.stop_reason,
+            StopReason::EndStepping | StopReason::FunctionFinished
+        );
+        if !ours || finishes >= MAX_FINISHES || is_user_frame(&pause.frame, source_dirs) {
+            self.finishes = None;
+            return None;
+        }
+        self.finishes = Some(finishes + 1);
+        Some(Command::Finish)
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pause(file: Option<&str>, stop_reason: StopReason) -> PauseState {
+        PauseState {
+            thread_id: 1,
+            frame: Frame {
+                level: 0,
+                args: vec![],
+                addr: 0x401000,
+                function: "f".into(),
+                file: file.map(Into::into),
+                line: file.map(|_| 1),
+            },
+            stack: vec![],
+            stop_reason,
+        }
+    }
+
+    #[test]
+    fn test_is_user_frame() {
+        let frame = |file: &str| pause(Some(file), StopReason::EndStepping).frame;
+        let dirs = vec!["/home/me/proj".to_string(), "$cdir".into(), "$cwd".into()];
+        assert!(is_user_frame(&frame("/home/me/proj/src/main.c"), &dirs));
+        assert!(!is_user_frame(&frame("/home/me/other/lib.c"), &dirs));
+        assert!(is_user_frame(&frame("main.c"), &dirs));
+        assert!(!is_user_frame(
+            &pause(None, StopReason::EndStepping).frame,
+            &dirs
+        ));
+
+        // Only placeholders: fall back to the system prefixes
+        let defaults = vec!["$cdir".to_string(), "$cwd".into()];
+        assert!(is_user_frame(&frame("/home/me/other/lib.c"), &defaults));
+        assert!(!is_user_frame(
+            &frame("/usr/src/glibc/string/strlen.c"),
+            &defaults
+        ));
+    }
+
+    #[test]
+    fn test_smart_step_decisions() {
+        let dirs = vec!["/src".to_string()];
+        let mut smart = SmartStep::default();
+
+        // Not stepping: nothing to do
+        assert_eq!(
+            smart.on_stop(&pause(Some("/usr/libc.c"), StopReason::EndStepping), &dirs),
+            None
+        );
+
+        // Stepped into libc: finish out, then stop back in user code
+        assert_eq!(smart.start(), Command::Step);
+        let libc = pause(Some("/usr/src/libc/printf.c"), StopReason::EndStepping);
+        assert_eq!(smart.on_stop(&libc, &dirs), Some(Command::Finish));
+        let deeper = pause(None, StopReason::FunctionFinished);
+        assert_eq!(smart.on_stop(&deeper, &dirs), Some(Command::Finish));
+        let home = pause(Some("/src/main.c"), StopReason::FunctionFinished);
+        assert_eq!(smart.on_stop(&home, &dirs), None);
+        assert!(!smart.is_active());
+
+        // A breakpoint inside the library stops it there
+        smart.start();
+        assert_eq!(
+            smart.on_stop(
+                &pause(Some("/usr/x.c"), StopReason::BreakpointHit(2)),
+                &dirs
+            ),
+            None
+        );
+
+        // And it doesn't finish forever
+        smart.start();
+        for _ in 0..MAX_FINISHES {
+            assert_eq!(smart.on_stop(&libc, &dirs), Some(Command::Finish));
+        }
+        assert_eq!(smart.on_stop(&libc, &dirs), None);
+    }
+}
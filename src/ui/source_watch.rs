@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::{Duration, Instant};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Quiet time after the last change before reporting a file: editors save
+/// in several writes, and some by writing a temp file and renaming it.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+// ─── Source watch ─────────────────────────────────────────────────────────────
+
+/// Notices when the source files on screen change on disk. Directories are
+/// watched rather than files so saves that replace the file still count.
+pub struct SourceWatch {
+    /// None when the platform watcher couldn't start; nothing is reported then.
+    watcher: Option<RecommendedWatcher>,
+    rx: Option<Receiver<notify::Result<Event>>>,
+    /// Resolved path → the name the UI knows the file by
+    files: HashMap<PathBuf, String>,
+    dirs: HashSet<PathBuf>,
+    /// Name → time of its latest change not reported yet
+    changed: HashMap<String, Instant>,
+}
+
+impl SourceWatch {
+    /// Starts the watcher; the error says why there's none.
+    pub fn new() -> (Self, Option<String>) {
+        let (tx, rx) = channel();
+        let (watcher, rx, error) = match notify::recommended_watcher(tx) {
+            Ok(w) => (Some(w), Some(rx), None),
+            Err(e) => (None, None, Some(e.to_string())),
+        };
+        let watch = Self {
+            watcher,
+            rx,
+            files: HashMap::new(),
+            dirs: HashSet::new(),
+            changed: HashMap::new(),
+        };
+        (watch, error)
+    }
+
+    /// Watches exactly these files, (resolved path, name) pairs. Cheap when
+    /// nothing changed, so it can run every frame.
+    pub fn set(&mut self, files: Vec<(PathBuf, String)>) {
+        let files: HashMap<_, _> = files.into_iter().collect();
+        if files == self.files {
+            return;
+        }
+        let dirs: HashSet<_> = files
+            .keys()
+            .filter_map(|p| p.parent())
+            .map(Path::to_path_buf)
+            .collect();
+        if let Some(watcher) = &mut self.watcher {
+            for dir in self.dirs.difference(&dirs) {
+                let _ = watcher.unwatch(dir);
+            }
+            // A directory that can't be watched just never reports
+            for dir in dirs.difference(&self.dirs) {
+                let _ = watcher.watch(dir, RecursiveMode::NonRecursive);
+            }
+        }
+        self.changed
+            .retain(|name, _| files.values().any(|n| n == name));
+        self.files = files;
+        self.dirs = dirs;
+    }
+
+    /// Records the watched files an event touches.
+    pub fn on_event(&mut self, event: &Event, now: Instant) {
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+            return;
+        }
+        for path in &event.paths {
+            if let Some(name) = self.files.get(path) {
+                self.changed.insert(name.clone(), now);
+            }
+        }
+    }
+
+    /// Names of the files that changed and have been quiet for `DEBOUNCE`.
+    pub fn poll(&mut self, now: Instant) -> Vec<String> {
+        let events: Vec<_> = match &self.rx {
+            Some(rx) => rx.try_iter().filter_map(Result::ok).collect(),
+            None => vec![],
+        };
+        for event in &events {
+            self.on_event(event, now);
+        }
+
+        let mut ready: Vec<_> = self
+            .changed
+            .iter()
+            .filter(|(_, at)| now.duration_since(**at) >= DEBOUNCE)
+            .map(|(name, _)| name.clone())
+            .collect();
+        ready.sort();
+        self.changed.retain(|name, _| !ready.contains(name));
+        ready
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange, ModifyKind};
+
+    #[test]
+    fn test_change_is_debounced() {
+        let (mut watch, _) = SourceWatch::new();
+        watch.set(vec![(PathBuf::from("/src/app/main.c"), "main.c".into())]);
+
+        let t0 = Instant::now();
+        let write = |path: &str| {
+            Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+                .add_path(PathBuf::from(path))
+        };
+        watch.on_event(&write("/src/app/main.c"), t0);
+        // Other files in the directory and mere reads don't count
+        watch.on_event(&write("/src/app/util.c"), t0);
+        watch.on_event(
+            &Event::new(EventKind::Access(AccessKind::Any)).add_path("/src/app/main.c".into()),
+            t0,
+        );
+        assert!(watch.poll(t0).is_empty());
+
+        // A second write restarts the wait
+        let t1 = t0 + DEBOUNCE / 2;
+        watch.on_event(&write("/src/app/main.c"), t1);
+        assert!(watch.poll(t0 + DEBOUNCE).is_empty());
+        assert_eq!(watch.poll(t1 + DEBOUNCE), vec!["main.c".to_string()]);
+        assert!(watch.poll(t1 + DEBOUNCE * 2).is_empty());
+
+        // Saved by rename: the new file shows up as created
+        watch.on_event(
+            &Event::new(EventKind::Create(CreateKind::File)).add_path("/src/app/main.c".into()),
+            t1,
+        );
+        // No longer on screen: dropped
+        watch.set(vec![]);
+        assert!(watch.poll(t1 + DEBOUNCE).is_empty());
+    }
+}
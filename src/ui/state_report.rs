@@ -0,0 +1,234 @@
+use std::fmt::Write;
+
+use crate::state::{BreakpointKind, DebuggerState, ProgramState, StopReason};
+
+/// Source lines shown on each side of the current one.
+const CONTEXT: u32 = 5;
+
+// ─── State report ─────────────────────────────────────────────────────────────
+
+/// The session as a Markdown document for bug reports: executable, stop,
+/// breakpoints, stack, source around the current line, locals and registers.
+/// `source` is the current frame's file, one entry per line.
+pub fn render(state: &DebuggerState, source: Option<&[String]>) -> String {
+    let mut out = String::from("# GDB GUI state report\n\n");
+    let exe = state.persistent.executable.as_deref().unwrap_or("(none)");
+    let _ = writeln!(out, "- **Executable:** `{exe}`");
+    let program = match &state.program {
+        ProgramState::NoProgramLoaded => "no program loaded".to_owned(),
+        ProgramState::ProgramLoaded => "loaded, not running".to_owned(),
+        ProgramState::Running => "running".to_owned(),
+        ProgramState::Paused => "paused".to_owned(),
+        ProgramState::Exited { code, signal } => match (code, signal) {
+            (_, Some(sig)) => format!("exited (signal {sig})"),
+            (Some(code), None) => format!("exited (code {code})"),
+            (None, None) => "exited".to_owned(),
+        },
+    };
+    let _ = writeln!(out, "- **State:** {program}");
+    if let Some(pause) = &state.pause {
+        let reason = match &pause.stop_reason {
+            StopReason::BreakpointHit(id) => format!("breakpoint {id}"),
+            StopReason::WatchpointTrigger { id, expr, old, new } => match (old, new) {
+                (Some(old), Some(new)) => format!("watchpoint {id} `{expr}`: {old} → {new}"),
+                _ => format!("watchpoint {id} `{expr}`"),
+            },
+            StopReason::EndStepping => "end of step".into(),
+            StopReason::FunctionFinished => "function finished".into(),
+            StopReason::Signal(sig) => format!("signal {sig}"),
+            StopReason::SyscallReturn(name) => format!("return from syscall `{name}`"),
+            StopReason::Unknown => "unknown".into(),
+        };
+        let frame = &pause.frame;
+        let _ = writeln!(
+            out,
+            "- **Stop reason:** {reason} (thread {})",
+            pause.thread_id
+        );
+        let _ = match (&frame.file, frame.line) {
+            (Some(file), Some(line)) => writeln!(
+                out,
+                "- **Location:** `{}` at `{file}:{line}` (0x{:x})",
+                frame.function, frame.addr
+            ),
+            _ => writeln!(
+                out,
+                "- **Location:** `{}` at 0x{:x}",
+                frame.function, frame.addr
+            ),
+        };
+    }
+
+    out.push_str("\n## Breakpoints\n\n");
+    if state.persistent.breakpoints.is_empty() {
+        out.push_str("None.\n");
+    }
+    for bp in &state.persistent.breakpoints {
+        let what = bp.what.as_deref().unwrap_or_default();
+        let location = match bp.kind {
+            BreakpointKind::Line | BreakpointKind::Trace => format!("`{}:{}`", bp.file, bp.line),
+            _ => format!("`{what}`"),
+        };
+        let kind = match bp.kind {
+            BreakpointKind::Line | BreakpointKind::Address => "breakpoint",
+            BreakpointKind::Watch => "watchpoint",
+            BreakpointKind::Catch => "catchpoint",
+            BreakpointKind::Trace => "tracepoint",
+        };
+        let _ = write!(out, "- {} {kind} at {location}", bp.id);
+        if !bp.enabled {
+            out.push_str(", disabled");
+        }
+        if bp.pending {
+            out.push_str(", pending");
+        }
+        if let Some(cond) = &bp.condition {
+            let _ = write!(out, ", if `{cond}`");
+        }
+        if let Some(msg) = &bp.log_message {
+            let _ = write!(out, ", logs `{msg}`");
+        }
+        out.push('\n');
+    }
+
+    let Some(pause) = &state.pause else {
+        return out;
+    };
+
+    out.push_str("\n## Stack\n\n```\n");
+    for frame in &pause.stack {
+        let location = match (&frame.file, frame.line) {
+            (Some(file), Some(line)) => format!("{file}:{line}"),
+            _ => "??".into(),
+        };
+        let _ = writeln!(
+            out,
+            "#{:<3} 0x{:016x} in {} at {location}",
+            frame.level, frame.addr, frame.function
+        );
+    }
+    out.push_str("```\n");
+
+    if let (Some(lines), Some(current)) = (source, pause.frame.line) {
+        let first = current.saturating_sub(CONTEXT).max(1);
+        let last = (current + CONTEXT).min(lines.len() as u32);
+        if first <= last {
+            out.push_str("\n## Source\n\n```\n");
+            for number in first..=last {
+                let marker = if number == current { '>' } else { ' ' };
+                let text = &lines[number as usize - 1];
+                let _ = writeln!(out, "{marker}{number:>5}  {text}");
+            }
+            out.push_str("```\n");
+        }
+    }
+
+    out.push_str("\n## Locals\n\n```\n");
+    for var in &state.locals {
+        let _ = writeln!(out, "{} {} = {}", var.type_, var.name, var.value);
+    }
+    out.push_str("```\n");
+
+    if !state.registers.is_empty() {
+        out.push_str("\n## Registers\n\n```\n");
+        for reg in &state.registers {
+            let name = state
+                .register_names
+                .get(reg.number as usize)
+                .map(String::as_str)
+                .filter(|n| !n.is_empty())
+                .unwrap_or(&reg.name);
+            let _ = writeln!(out, "{name:<8} {}", reg.value);
+        }
+        out.push_str("```\n");
+    }
+    out
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Breakpoint, Frame, PauseState, Register, StateEvent, Variable};
+
+    #[test]
+    fn test_state_report_sections() {
+        let mut state = DebuggerState::new();
+        state.apply(StateEvent::ProgramLoaded {
+            executable: "./app".into(),
+        });
+        state.apply(StateEvent::BreakpointAdded {
+            breakpoint: Breakpoint {
+                id: 1,
+                kind: BreakpointKind::Line,
+                file: "/src/main.c".into(),
+                line: 4,
+                enabled: true,
+                temporary: false,
+                condition: Some("i > 2".into()),
+                ignore_count: 0,
+                pending: false,
+                log_message: None,
+                original_location: None,
+                what: None,
+                commands: vec![],
+            },
+        });
+        let frame = Frame {
+            level: 0,
+            args: vec![],
+            addr: 0x401126,
+            function: "main".into(),
+            file: Some("/src/main.c".into()),
+            line: Some(4),
+        };
+        state.apply(StateEvent::ProgramPaused {
+            pause: PauseState {
+                thread_id: 1,
+                frame: frame.clone(),
+                stack: vec![frame],
+                stop_reason: StopReason::BreakpointHit(1),
+            },
+        });
+        state.apply(StateEvent::LocalsUpdated {
+            vars: vec![Variable {
+                name: "i".into(),
+                value: "3".into(),
+                type_: "int".into(),
+            }],
+        });
+        state.apply(StateEvent::RegisterNamesReceived {
+            names: vec!["rax".into()],
+        });
+        state.apply(StateEvent::RegistersUpdated {
+            registers: vec![Register {
+                number: 0,
+                name: String::new(),
+                value: "0x2a".into(),
+            }],
+        });
+        let source: Vec<String> = (1..=6).map(|n| format!("line {n}")).collect();
+
+        let report = render(&state, Some(&source));
+        assert!(report.starts_with("# GDB GUI state report\n"));
+        assert!(report.contains("- **Executable:** `./app`"));
+        assert!(report.contains("- **Stop reason:** breakpoint 1 (thread 1)"));
+        assert!(report.contains("- 1 breakpoint at `/src/main.c:4`, if `i > 2`"));
+        assert!(
+            report.contains("## Stack\n\n```\n#0   0x0000000000401126 in main at /src/main.c:4")
+        );
+        // Clipped to the file: lines 1..=6 around line 4
+        assert!(report.contains("     1  line 1\n"));
+        assert!(report.contains(">    4  line 4\n"));
+        assert!(report.contains("     6  line 6\n```"));
+        assert!(report.contains("## Locals\n\n```\nint i = 3\n```"));
+        assert!(report.contains("## Registers\n\n```\nrax      0x2a\n```"));
+
+        // Not paused: no stop sections
+        state.apply(StateEvent::ProgramKilled);
+        let report = render(&state, None);
+        assert!(report.contains("## Breakpoints"));
+        assert!(!report.contains("## Stack"));
+    }
+}
@@ -0,0 +1,194 @@
+use super::command::Command;
+use crate::state::{PauseState, StopReason};
+
+/// Steps before giving up on the condition ever holding.
+pub const MAX_STEPS: u32 = 1000;
+
+/// Whether a GDB value reads as true: anything but zero, `false` or a null
+/// pointer. Pointers come as `(int *) 0x0`, chars as `0 '\000'`.
+pub fn is_true(value: &str) -> bool {
+    let value = value.trim();
+    let value = match value.strip_prefix('(') {
+        Some(rest) => rest.split_once(") ").map_or(rest, |(_, v)| v),
+        None => value,
+    };
+    let first = value.split_whitespace().next().unwrap_or_default();
+    !matches!(first, "" | "0" | "0x0" | "false")
+}
+
+/// Why a step-until run ended.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The expression held after this many steps
+    Hit(u32),
+    /// `MAX_STEPS` steps and still false
+    Limit,
+    /// GDB couldn't evaluate the expression
+    Error(String),
+    /// Something else stopped the program: a breakpoint, a signal, the end
+    Interrupted,
+}
+
+// ─── Driver ───────────────────────────────────────────────────────────────────
+
+/// A software watchpoint for when the hardware ones run out: Step, evaluate
+/// the condition, Step again while it's false. Views aren't refreshed in
+/// between, only once the run ends.
+#[derive(Default)]
+pub struct StepUntil {
+    pub expr: String,
+    /// Steps taken so far; None when not stepping
+    steps: Option<u32>,
+    /// Waiting for the condition's value rather than a stop
+    evaluating: bool,
+}
+
+impl StepUntil {
+    pub fn start(&mut self, expr: impl Into<String>) -> Command {
+        self.expr = expr.into();
+        self.steps = Some(0);
+        self.evaluating = false;
+        Command::Step
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.steps.is_some()
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps.unwrap_or_default()
+    }
+
+    pub fn cancel(&mut self) {
+        self.steps = None;
+        self.evaluating = false;
+    }
+
+    /// Whether an `Evaluate(expr)` result belongs to this run.
+    pub fn awaits(&self, expr: &str) -> bool {
+        self.evaluating && expr == self.expr
+    }
+
+    /// After a stop: evaluate the condition if it was our step that ended,
+    /// otherwise the run is over.
+    pub fn on_stop(&mut self, pause: &PauseState) -> Result<Command, Outcome> {
+        let steps = self.steps.ok_or(Outcome::Interrupted)?;
+        if !matches!(
+            pause.stop_reason,
+            StopReason::EndStepping | StopReason::FunctionFinished
+        ) {
+            self.cancel();
+            return Err(Outcome::Interrupted);
+        }
+        self.steps = Some(steps + 1);
+        self.evaluating = true;
+        Ok(Command::Evaluate(self.expr.clone()))
+    }
+
+    /// With the condition's value: step again, or the reason to stop.
+    pub fn on_result(&mut self, value: Result<String, String>) -> Result<Command, Outcome> {
+        self.evaluating = false;
+        let steps = self.steps.ok_or(Outcome::Interrupted)?;
+        let outcome = match value {
+            Ok(value) if is_true(&value) => Outcome::Hit(steps),
+            Ok(_) if steps >= MAX_STEPS => Outcome::Limit,
+            Ok(_) => return Ok(Command::Step),
+            Err(msg) => Outcome::Error(msg),
+        };
+        self.cancel();
+        Err(outcome)
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Frame;
+
+    fn pause(stop_reason: StopReason) -> PauseState {
+        PauseState {
+            thread_id: 1,
+            frame: Frame {
+                level: 0,
+                args: vec![],
+                addr: 0x401000,
+                function: "main".into(),
+                file: Some("main.c".into()),
+                line: Some(3),
+            },
+            stack: vec![],
+            stop_reason,
+        }
+    }
+
+    /// Drives a run against `values`, the condition after each step.
+    fn run(until: &mut StepUntil, values: impl Fn(u32) -> Result<String, String>) -> Outcome {
+        assert_eq!(until.start("i == 3"), Command::Step);
+        loop {
+            let eval = until.on_stop(&pause(StopReason::EndStepping));
+            assert_eq!(eval, Ok(Command::Evaluate("i == 3".into())));
+            assert!(until.awaits("i == 3"));
+            match until.on_result(values(until.steps())) {
+                Ok(cmd) => assert_eq!(cmd, Command::Step),
+                Err(outcome) => return outcome,
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_true() {
+        assert!(is_true("1"));
+        assert!(is_true("true"));
+        assert!(is_true("(int *) 0x4052a0"));
+        assert!(is_true("65 'A'"));
+        assert!(!is_true("0"));
+        assert!(!is_true("false"));
+        assert!(!is_true("(int *) 0x0"));
+        assert!(!is_true("0 '\\000'"));
+    }
+
+    #[test]
+    fn test_step_until_driver() {
+        let mut until = StepUntil::default();
+
+        // True after the third step
+        let outcome = run(&mut until, |step| Ok(u32::from(step == 3).to_string()));
+        assert_eq!(outcome, Outcome::Hit(3));
+        assert!(!until.is_active());
+
+        // Never true: capped
+        assert_eq!(run(&mut until, |_| Ok("0".into())), Outcome::Limit);
+        assert!(!until.is_active());
+
+        // A bad expression ends it on the first evaluation
+        let err = "No symbol \"j\" in current context.";
+        assert_eq!(
+            run(&mut until, |_| Err(err.into())),
+            Outcome::Error(err.into())
+        );
+
+        // Results for other expressions aren't ours
+        until.start("i == 3");
+        assert!(!until.awaits("i == 3"));
+        until.on_stop(&pause(StopReason::EndStepping)).unwrap();
+        assert!(!until.awaits("other"));
+
+        // A breakpoint hit while stepping ends the run
+        until.start("i == 3");
+        assert_eq!(
+            until.on_stop(&pause(StopReason::BreakpointHit(1))),
+            Err(Outcome::Interrupted)
+        );
+        assert!(!until.is_active());
+
+        // Cancelled: later stops are shown like any other
+        until.start("i == 3");
+        until.cancel();
+        assert_eq!(
+            until.on_stop(&pause(StopReason::EndStepping)),
+            Err(Outcome::Interrupted)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use super::command::Command;
+
+/// Commands that would resume or end the inferior. A stop hook running one
+/// would fight the stepping buttons and the views' own refresh.
+const RESUMING: [&str; 22] = [
+    "run", "r", "start", "starti", "continue", "c", "step", "s", "next", "n", "stepi", "si",
+    "nexti", "ni", "finish", "fin", "until", "u", "advance", "jump", "signal", "kill",
+];
+
+// ─── Stop hook ────────────────────────────────────────────────────────────────
+
+/// What to send after a stop for the user's stop commands, like GDB's
+/// `hook-stop`. Blank lines, `#` comments and anything that resumes the
+/// program are skipped.
+pub fn stop_commands(lines: &[String]) -> Vec<Command> {
+    lines
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !resumes(l))
+        .map(|l| Command::StopHook(l.to_owned()))
+        .collect()
+}
+
+pub fn resumes(line: &str) -> bool {
+    let word = line.split_whitespace().next().unwrap_or("");
+    RESUMING.contains(&word) || word.starts_with("-exec-")
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_command_dispatch() {
+        let lines: Vec<String> = [
+            "info registers eflags",
+            "  ",
+            "# x/4gx $sp",
+            "p counter",
+            "continue",
+            "-exec-next",
+            "s",
+        ]
+        .map(Into::into)
+        .into();
+        assert_eq!(
+            stop_commands(&lines),
+            vec![
+                Command::StopHook("info registers eflags".into()),
+                Command::StopHook("p counter".into()),
+            ]
+        );
+        assert!(stop_commands(&[]).is_empty());
+        // Only whole command words count
+        assert!(!resumes("show confirm"));
+        assert!(resumes("until 42"));
+    }
+}
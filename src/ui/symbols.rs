@@ -0,0 +1,141 @@
+use std::time::{Duration, Instant};
+
+use super::command::Command;
+use crate::state::Symbol;
+
+/// Functions listed per search; a regex matching more is asked to narrow down.
+pub const MAX_SYMBOLS: usize = 500;
+
+/// Quiet time after the last keystroke before the query goes out.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Where to break for `symbol`: its file too when it has one, so a static
+/// function doesn't match every file's copy.
+pub fn location(symbol: &Symbol) -> String {
+    match &symbol.file {
+        Some(file) => format!("{file}:{}", symbol.name),
+        None => symbol.name.clone(),
+    }
+}
+
+// ─── Search ───────────────────────────────────────────────────────────────────
+
+/// The Symbols section: a regex typed in, queried once typing pauses.
+#[derive(Default)]
+pub struct SymbolSearch {
+    pub input: String,
+    edited: Option<Instant>,
+    /// Regex of the last query sent; answers for older ones are dropped
+    sent: Option<String>,
+    pub results: Vec<Symbol>,
+    /// GDB had more than `MAX_SYMBOLS` matches
+    pub truncated: bool,
+}
+
+impl SymbolSearch {
+    pub fn edited(&mut self, now: Instant) {
+        self.edited = Some(now);
+    }
+
+    /// The query to send once `DEBOUNCE` has passed since the last edit.
+    pub fn poll(&mut self, now: Instant) -> Option<Command> {
+        let edited = self.edited?;
+        if now.duration_since(edited) < DEBOUNCE {
+            return None;
+        }
+        self.edited = None;
+        let regex = self.input.trim();
+        if regex.is_empty() {
+            self.sent = None;
+            self.results.clear();
+            self.truncated = false;
+            return None;
+        }
+        if self.sent.as_deref() == Some(regex) {
+            return None;
+        }
+        self.sent = Some(regex.to_owned());
+        Some(Command::SearchSymbols {
+            regex: regex.to_owned(),
+            // One more than shown, to know there were more
+            max: MAX_SYMBOLS + 1,
+        })
+    }
+
+    pub fn on_result(&mut self, regex: &str, mut symbols: Vec<Symbol>) {
+        if self.sent.as_deref() != Some(regex) {
+            return;
+        }
+        self.truncated = symbols.len() > MAX_SYMBOLS;
+        symbols.truncate(MAX_SYMBOLS);
+        self.results = symbols;
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.edited.is_some()
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str) -> Symbol {
+        Symbol {
+            name: name.into(),
+            file: None,
+            line: None,
+            addr: Some(0x401000),
+        }
+    }
+
+    #[test]
+    fn test_debounced_search() {
+        let t0 = Instant::now();
+        let mut search = SymbolSearch::default();
+        assert_eq!(search.poll(t0), None);
+
+        search.input = "pa".into();
+        search.edited(t0);
+        search.input = "parse".into();
+        search.edited(t0 + Duration::from_millis(100));
+        assert_eq!(search.poll(t0 + Duration::from_millis(300)), None);
+        let cmd = search.poll(t0 + Duration::from_millis(400));
+        assert_eq!(
+            cmd,
+            Some(Command::SearchSymbols {
+                regex: "parse".into(),
+                max: MAX_SYMBOLS + 1,
+            })
+        );
+        assert_eq!(search.poll(t0 + Duration::from_secs(1)), None);
+
+        // Answers to an older regex are stale
+        search.on_result("pa", vec![symbol("pause")]);
+        assert!(search.results.is_empty());
+
+        // Capped
+        let many = (0..MAX_SYMBOLS + 1)
+            .map(|i| symbol(&format!("f{i}")))
+            .collect();
+        search.on_result("parse", many);
+        assert_eq!(search.results.len(), MAX_SYMBOLS);
+        assert!(search.truncated);
+
+        // Clearing the input clears the list without a query
+        search.input.clear();
+        search.edited(t0 + Duration::from_secs(2));
+        assert_eq!(search.poll(t0 + Duration::from_secs(3)), None);
+        assert!(search.results.is_empty() && !search.truncated);
+    }
+
+    #[test]
+    fn test_symbol_location() {
+        let mut sym = symbol("parse_line");
+        assert_eq!(location(&sym), "parse_line");
+        sym.file = Some("/src/util.c".into());
+        assert_eq!(location(&sym), "/src/util.c:parse_line");
+    }
+}
@@ -0,0 +1,271 @@
+use crate::state::Language;
+
+/// What a stretch of a source line is, for coloring.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Token {
+    Plain,
+    Keyword,
+    Str,
+    Number,
+    Comment,
+    /// `#include`, `#define`… in C and C++
+    Directive,
+}
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "bool", "true", "false", "NULL",
+];
+
+const CPP_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "catch",
+    "class",
+    "constexpr",
+    "const_cast",
+    "decltype",
+    "delete",
+    "dynamic_cast",
+    "explicit",
+    "friend",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "nullptr",
+    "operator",
+    "override",
+    "private",
+    "protected",
+    "public",
+    "reinterpret_cast",
+    "static_cast",
+    "template",
+    "this",
+    "throw",
+    "try",
+    "typename",
+    "using",
+    "virtual",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+const GO_KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+    "nil",
+    "true",
+    "false",
+];
+
+fn is_keyword(word: &str, language: Language) -> bool {
+    match language {
+        Language::C => C_KEYWORDS.contains(&word),
+        Language::Cpp => C_KEYWORDS.contains(&word) || CPP_KEYWORDS.contains(&word),
+        Language::Rust => RUST_KEYWORDS.contains(&word),
+        Language::Go => GO_KEYWORDS.contains(&word),
+        Language::Unknown => false,
+    }
+}
+
+/// `line` split into colored stretches covering all of it. One line at a
+/// time: a `/* … */` spanning lines only gets its first line colored. With
+/// the language unknown the line stays plain.
+pub fn tokens(line: &str, language: Language) -> Vec<(Token, &str)> {
+    if language == Language::Unknown {
+        return vec![(Token::Plain, line)];
+    }
+    let c_like = matches!(language, Language::C | Language::Cpp);
+    if c_like && line.trim_start().starts_with('#') {
+        return vec![(Token::Directive, line)];
+    }
+
+    let bytes = line.as_bytes();
+    let mut out = vec![];
+    let mut plain = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let (token, end) = match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => (Token::Comment, bytes.len()),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = line[i + 2..].find("*/").map_or(bytes.len(), |e| i + e + 4);
+                (Token::Comment, end)
+            }
+            b'"' => (Token::Str, quoted_end(bytes, i, b'"')),
+            b'`' if language == Language::Go => (Token::Str, quoted_end(bytes, i, b'`')),
+            // Rust's 'a lifetimes aren't characters
+            b'\'' if language != Language::Rust || is_rust_char(bytes, i) => {
+                (Token::Str, quoted_end(bytes, i, b'\''))
+            }
+            c if c.is_ascii_digit() && !continues_word(bytes, i) => {
+                (Token::Number, word_end(bytes, i))
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let end = word_end(bytes, i);
+                if is_keyword(&line[i..end], language) {
+                    (Token::Keyword, end)
+                } else {
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        if plain < i {
+            out.push((Token::Plain, &line[plain..i]));
+        }
+        out.push((token, &line[i..end]));
+        i = end;
+        plain = end;
+    }
+    if plain < bytes.len() || out.is_empty() {
+        out.push((Token::Plain, &line[plain..]));
+    }
+    out
+}
+
+/// Just past the closing `quote`, skipping escapes; the end of the line if
+/// it never closes.
+fn quoted_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quote != b'`' => i += 2,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// `'x'` or `'\n'` rather than a lifetime.
+fn is_rust_char(bytes: &[u8], start: usize) -> bool {
+    match bytes.get(start + 1) {
+        Some(b'\\') => true,
+        Some(_) => {
+            let next = char_len(bytes, start + 1);
+            bytes.get(start + 1 + next) == Some(&b'\'')
+        }
+        None => false,
+    }
+}
+
+/// Byte length of the UTF-8 character at `i`.
+fn char_len(bytes: &[u8], i: usize) -> usize {
+    match bytes[i] {
+        b if b >= 0xf0 => 4,
+        b if b >= 0xe0 => 3,
+        b if b >= 0xc0 => 2,
+        _ => 1,
+    }
+}
+
+fn continues_word(bytes: &[u8], i: usize) -> bool {
+    i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')
+}
+
+fn word_end(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_'))
+        .map_or(bytes.len(), |n| start + n)
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Token::*;
+
+    #[test]
+    fn test_tokens_per_language() {
+        assert_eq!(
+            tokens("    int x = 0x2a; // answer", Language::C),
+            vec![
+                (Plain, "    "),
+                (Keyword, "int"),
+                (Plain, " x = "),
+                (Number, "0x2a"),
+                (Plain, "; "),
+                (Comment, "// answer"),
+            ]
+        );
+        assert_eq!(
+            tokens("#include <stdio.h>", Language::Cpp),
+            vec![(Directive, "#include <stdio.h>")]
+        );
+        // C++ adds its keywords to C's
+        assert_eq!(tokens("this", Language::Cpp), vec![(Keyword, "this")]);
+        assert_eq!(tokens("this", Language::C), vec![(Plain, "this")]);
+
+        assert_eq!(
+            tokens(r#"let s: &'a str = "a\"b";"#, Language::Rust),
+            vec![
+                (Keyword, "let"),
+                (Plain, " s: &'a str = "),
+                (Str, r#""a\"b""#),
+                (Plain, ";"),
+            ]
+        );
+        assert_eq!(
+            tokens("c == 'x' /* y */ x1", Language::Rust),
+            vec![
+                (Plain, "c == "),
+                (Str, "'x'"),
+                (Plain, " "),
+                (Comment, "/* y */"),
+                (Plain, " x1"),
+            ]
+        );
+        assert_eq!(
+            tokens("func f() { s := `raw\\` }", Language::Go),
+            vec![
+                (Keyword, "func"),
+                (Plain, " f() { s := "),
+                (Str, "`raw\\`"),
+                (Plain, " }"),
+            ]
+        );
+
+        // Unknown language, empty and unterminated lines
+        assert_eq!(tokens("int x;", Language::Unknown), vec![(Plain, "int x;")]);
+        assert_eq!(tokens("", Language::C), vec![(Plain, "")]);
+        assert_eq!(tokens("/* open", Language::C), vec![(Comment, "/* open")]);
+    }
+}
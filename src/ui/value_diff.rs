@@ -0,0 +1,307 @@
+use super::command::Command;
+
+/// `{a = 1, b = {c = 2}}` → ["a = 1", "b = {c = 2}"]. Anything that isn't a
+/// brace-delimited aggregate is returned whole.
+pub fn top_level_fields(value: &str) -> Vec<&str> {
+    let Some(inner) = value
+        .trim()
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+    else {
+        return vec![value.trim()];
+    };
+
+    let mut fields = vec![];
+    let (mut depth, mut in_str, mut start) = (0i32, false, 0);
+    let mut prev = '\0';
+    for (i, c) in inner.char_indices() {
+        match c {
+            '"' if prev != '\\' => in_str = !in_str,
+            '{' | '[' | '(' if !in_str => depth += 1,
+            '}' | ']' | ')' if !in_str => depth -= 1,
+            ',' if !in_str && depth == 0 => {
+                fields.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    fields.push(inner[start..].trim());
+    fields.retain(|f| !f.is_empty());
+    fields
+}
+
+// ─── Value tree ───────────────────────────────────────────────────────────────
+
+/// A GDB value split along its braces. Array elements are named `[i]`.
+#[derive(Debug, PartialEq)]
+pub enum ValueTree {
+    Leaf(String),
+    Node(Vec<(String, ValueTree)>),
+}
+
+impl ValueTree {
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        if !value.starts_with('{') {
+            return ValueTree::Leaf(value.to_owned());
+        }
+        let children = top_level_fields(value)
+            .into_iter()
+            .enumerate()
+            .map(|(i, field)| {
+                // Elements have no names; a string one may well contain ` = `
+                let named = !field.starts_with(['{', '"']);
+                match field.split_once(" = ").filter(|_| named) {
+                    Some((name, v)) => (name.to_owned(), ValueTree::parse(v)),
+                    None => (format!("[{i}]"), ValueTree::parse(field)),
+                }
+            })
+            .collect();
+        ValueTree::Node(children)
+    }
+
+    fn child(&self, name: &str) -> Option<&ValueTree> {
+        match self {
+            ValueTree::Leaf(_) => None,
+            ValueTree::Node(children) => children.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+        }
+    }
+
+    /// The value in one cell: aggregates are opened as rows of their own.
+    fn cell(&self) -> String {
+        match self {
+            ValueTree::Leaf(v) => v.clone(),
+            ValueTree::Node(_) => "{…}".into(),
+        }
+    }
+}
+
+// ─── Diff ─────────────────────────────────────────────────────────────────────
+
+/// One row of the two-column diff. A side is None where that value has no
+/// such field.
+#[derive(Debug, PartialEq)]
+pub struct DiffRow {
+    pub depth: usize,
+    pub name: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub changed: bool,
+}
+
+/// Rows comparing `left` with `right`, fields matched by name. Aggregates
+/// that differ are opened; equal ones stay a single row.
+pub fn diff_values(left: &str, right: &str) -> Vec<DiffRow> {
+    let mut rows = vec![];
+    push_diff(
+        &mut rows,
+        0,
+        String::new(),
+        Some(&ValueTree::parse(left)),
+        Some(&ValueTree::parse(right)),
+    );
+    rows
+}
+
+fn push_diff(
+    rows: &mut Vec<DiffRow>,
+    depth: usize,
+    name: String,
+    left: Option<&ValueTree>,
+    right: Option<&ValueTree>,
+) {
+    let changed = left != right;
+    rows.push(DiffRow {
+        depth,
+        name,
+        left: left.map(ValueTree::cell),
+        right: right.map(ValueTree::cell),
+        changed,
+    });
+    if !changed {
+        return;
+    }
+    // Left's fields in order, then those only the right has
+    let mut names: Vec<&str> = vec![];
+    for tree in [left, right].into_iter().flatten() {
+        if let ValueTree::Node(children) = tree {
+            for (n, _) in children {
+                if !names.contains(&n.as_str()) {
+                    names.push(n);
+                }
+            }
+        }
+    }
+    for n in names {
+        push_diff(
+            rows,
+            depth + 1,
+            n.to_owned(),
+            left.and_then(|t| t.child(n)),
+            right.and_then(|t| t.child(n)),
+        );
+    }
+}
+
+// ─── Compare ──────────────────────────────────────────────────────────────────
+
+/// Two expressions evaluated side by side. Pinning the left one keeps its
+/// value from the stop it was taken at, for before/after comparisons.
+#[derive(Default)]
+pub struct Compare {
+    pub exprs: [String; 2],
+    pub values: [Option<Result<String, String>>; 2],
+    pub pinned: bool,
+    pending: [bool; 2],
+}
+
+impl Compare {
+    /// Evaluations for the sides not pinned, forgetting their old values.
+    pub fn requests(&mut self) -> Vec<Command> {
+        let mut cmds = vec![];
+        for i in 0..2 {
+            let expr = self.exprs[i].trim();
+            if (i == 0 && self.pinned && self.values[0].is_some()) || expr.is_empty() {
+                continue;
+            }
+            self.values[i] = None;
+            self.pending[i] = true;
+            cmds.push(Command::Evaluate(expr.to_owned()));
+        }
+        cmds
+    }
+
+    /// The side an `Evaluate(expr)` result answers; left first.
+    fn slot(&self, expr: &str) -> Option<usize> {
+        (0..2).find(|&i| self.pending[i] && self.exprs[i].trim() == expr)
+    }
+
+    pub fn awaits(&self, expr: &str) -> bool {
+        self.slot(expr).is_some()
+    }
+
+    pub fn fill(&mut self, expr: &str, value: Result<String, String>) {
+        if let Some(i) = self.slot(expr) {
+            self.pending[i] = false;
+            self.values[i] = Some(value);
+        }
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        depth: usize,
+        name: &str,
+        left: Option<&str>,
+        right: Option<&str>,
+        changed: bool,
+    ) -> DiffRow {
+        DiffRow {
+            depth,
+            name: name.into(),
+            left: left.map(Into::into),
+            right: right.map(Into::into),
+            changed,
+        }
+    }
+
+    #[test]
+    fn test_top_level_fields() {
+        assert_eq!(
+            top_level_fields(r#"{a = 1, b = {c = 2, d = 3}, s = "x, y"}"#),
+            vec!["a = 1", "b = {c = 2, d = 3}", r#"s = "x, y""#]
+        );
+        assert_eq!(top_level_fields("0x4052a0"), vec!["0x4052a0"]);
+    }
+
+    #[test]
+    fn test_parse_value_tree() {
+        use ValueTree::{Leaf, Node};
+        assert_eq!(
+            ValueTree::parse(r#"{n = 2, xs = {1, 2}, s = "a = b", p = {x = 0}}"#),
+            Node(vec![
+                ("n".into(), Leaf("2".into())),
+                (
+                    "xs".into(),
+                    Node(vec![
+                        ("[0]".into(), Leaf("1".into())),
+                        ("[1]".into(), Leaf("2".into())),
+                    ])
+                ),
+                ("s".into(), Leaf(r#""a = b""#.into())),
+                ("p".into(), Node(vec![("x".into(), Leaf("0".into()))])),
+            ])
+        );
+        assert_eq!(ValueTree::parse(" 42 "), Leaf("42".into()));
+    }
+
+    #[test]
+    fn test_structural_diff() {
+        let before = "{id = 7, pos = {x = 1, y = 2}, tags = {3, 4}, name = 0x0}";
+        let after = "{id = 7, pos = {x = 1, y = 5}, tags = {3, 4, 9}, next = 0x4052a0}";
+        assert_eq!(
+            diff_values(before, after),
+            vec![
+                row(0, "", Some("{…}"), Some("{…}"), true),
+                row(1, "id", Some("7"), Some("7"), false),
+                row(1, "pos", Some("{…}"), Some("{…}"), true),
+                row(2, "x", Some("1"), Some("1"), false),
+                row(2, "y", Some("2"), Some("5"), true),
+                row(1, "tags", Some("{…}"), Some("{…}"), true),
+                row(2, "[0]", Some("3"), Some("3"), false),
+                row(2, "[1]", Some("4"), Some("4"), false),
+                row(2, "[2]", None, Some("9"), true),
+                row(1, "name", Some("0x0"), None, true),
+                row(1, "next", None, Some("0x4052a0"), true),
+            ]
+        );
+
+        // Equal aggregates stay closed
+        assert_eq!(
+            diff_values("{a = {b = 1}}", "{a = {b = 1}}"),
+            vec![row(0, "", Some("{…}"), Some("{…}"), false)]
+        );
+        // A scalar turned aggregate opens the side that has fields
+        assert_eq!(
+            diff_values("0", "{a = 1}"),
+            vec![
+                row(0, "", Some("0"), Some("{…}"), true),
+                row(1, "a", None, Some("1"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_requests() {
+        let mut cmp = Compare {
+            exprs: ["*p".into(), "*p".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            cmp.requests(),
+            vec![
+                Command::Evaluate("*p".into()),
+                Command::Evaluate("*p".into())
+            ]
+        );
+        assert!(!cmp.awaits("p"));
+        cmp.fill("*p", Ok("{x = 1}".into()));
+        assert!(cmp.awaits("*p"));
+        cmp.fill("*p", Ok("{x = 1}".into()));
+        assert!(!cmp.awaits("*p"));
+
+        // Pinned: only the right side is taken again at the next stop
+        cmp.pinned = true;
+        assert_eq!(cmp.requests(), vec![Command::Evaluate("*p".into())]);
+        cmp.fill("*p", Ok("{x = 2}".into()));
+        assert_eq!(cmp.values[0], Some(Ok("{x = 1}".into())));
+        assert_eq!(cmp.values[1], Some(Ok("{x = 2}".into())));
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+// ─── Console verbosity ────────────────────────────────────────────────────────
+
+/// How much MI traffic the Console tab shows. The log keeps every line, so
+/// switching level applies to what's already there too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Verbosity {
+    /// GDB's console text and our own messages
+    Quiet,
+    /// Plus the `> …` echo of each command sent
+    #[default]
+    Normal,
+    /// Plus every raw MI record, `&` logs included
+    Debug,
+}
+
+impl Verbosity {
+    pub const ALL: [Verbosity; 3] = [Verbosity::Quiet, Verbosity::Normal, Verbosity::Debug];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Verbosity::Quiet => "Quiet",
+            Verbosity::Normal => "Normal",
+            Verbosity::Debug => "Debug",
+        }
+    }
+
+    pub fn admits(self, kind: LineKind) -> bool {
+        match kind {
+            LineKind::Message => true,
+            LineKind::Echo => self != Verbosity::Quiet,
+            LineKind::Record => self == Verbosity::Debug,
+        }
+    }
+}
+
+/// Where a console line came from. Set by whoever logs it: GDB's own text
+/// can start with anything, `=> 0x…` from `disassemble` included.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineKind {
+    /// Parsed `~` text, `[UI]` and `[ERROR]` lines
+    Message,
+    /// `> -exec-run`
+    Echo,
+    /// `12^done`, `*stopped,…`, `=thread-created,…`, `~"…"`, `&"…"`
+    Record,
+}
+
+/// Everything the Console tab can show, each line with its kind.
+#[derive(Default)]
+pub struct ConsoleLog {
+    lines: Vec<(LineKind, String)>,
+}
+
+impl ConsoleLog {
+    /// Our own messages and GDB's parsed text.
+    pub fn push(&mut self, text: String) {
+        self.lines.push((LineKind::Message, text));
+    }
+
+    pub fn push_kind(&mut self, kind: LineKind, text: String) {
+        self.lines.push((kind, text));
+    }
+
+    pub fn shown(&self, level: Verbosity) -> impl Iterator<Item = &str> {
+        self.lines
+            .iter()
+            .filter(move |(kind, _)| level.admits(*kind))
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_levels() {
+        use LineKind::*;
+        let lines = [
+            (Message, "Breakpoint 1, main () at main.c:5"),
+            (Message, "[UI] ✓ Loaded main.c"),
+            (Message, "[ERROR] No symbol table is loaded."),
+            // Parsed text that looks like a record or an echo is still text
+            (Message, "=> 0x401136 <main+4>:\tmov    $0x0,%eax"),
+            (Message, "~/bin/app: No such file"),
+            (Message, "> 3"),
+            (Echo, "> -exec-run"),
+            (Record, "3^running"),
+            (Record, "*stopped,reason=\"end-stepping-range\""),
+            (Record, "=thread-group-started,id=\"i1\""),
+            (Record, "~\"Starting program\\n\""),
+            (Record, "&\"warning: no symbols\\n\""),
+            (Record, "(gdb) "),
+        ];
+        let mut log = ConsoleLog::default();
+        for (kind, text) in lines {
+            log.push_kind(kind, text.into());
+        }
+        let shown = |v: Verbosity| log.shown(v).collect::<Vec<_>>();
+        assert_eq!(
+            shown(Verbosity::Quiet),
+            lines[..6].iter().map(|(_, t)| *t).collect::<Vec<_>>()
+        );
+        assert_eq!(shown(Verbosity::Normal).len(), 7);
+        assert_eq!(shown(Verbosity::Normal)[6], "> -exec-run");
+        assert_eq!(shown(Verbosity::Debug).len(), lines.len());
+    }
+}
@@ -0,0 +1,90 @@
+//! Runs a canned script through `gdb_gui --headless`: GDB, the MI parser and
+//! `DebuggerState` end to end. Needs gdb and cc, so it only runs with
+//! `cargo test -- --ignored`; the driver itself is tested against a fake
+//! GDB in `src/headless.rs`.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const PROGRAM: &str = "int main(void) {
+    int x = 41;
+    x++;
+    return 0;
+}
+";
+
+const SCRIPT: &str = "# canned session
+break main
+run
+next
+print x
+continue
+";
+
+fn available(tool: &str) -> bool {
+    Command::new(tool)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn build_fixture() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gdb_gui_headless_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let src = dir.join("main.c");
+    let exe = dir.join("main");
+    std::fs::write(&src, PROGRAM).unwrap();
+    let status = Command::new("cc")
+        .args(["-g", "-O0", "-o"])
+        .arg(&exe)
+        .arg(&src)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    exe
+}
+
+#[test]
+#[ignore = "needs gdb and cc"]
+fn headless_script() {
+    for tool in ["gdb", "cc"] {
+        assert!(available(tool), "{tool} isn't installed");
+    }
+    let exe = build_fixture();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_gdb_gui"))
+        .arg("--headless")
+        .arg(&exe)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(SCRIPT.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{stdout}");
+    for expected in [
+        "loaded ",
+        "breakpoint 1 at ",
+        "paused: breakpoint 1 in main at main.c:2",
+        "paused: step in main at main.c:3",
+        "x = 41",
+        "exited (code 0)",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "missing {expected:?} in:\n{stdout}"
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(exe.parent().unwrap());
+}